@@ -11,8 +11,21 @@
 //! Evaluation semantics: default deny, deny wins at equal specificity,
 //! most specific matching rule takes precedence.
 
-use crate::KervyxError;
+use crate::telemetry;
+use crate::SteleError;
+
+#[cfg(feature = "std")]
 use std::collections::HashMap;
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+pub mod visitor;
 
 // ---------------------------------------------------------------------------
 // Types
@@ -28,24 +41,40 @@ pub enum StatementType {
 }
 
 /// A simple condition comparing a context field to a value.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Condition {
     pub field: String,
     pub operator: String,
     pub value: String,
 }
 
+/// A boolean expression tree over `when` conditions.
+///
+/// Precedence from loosest to tightest: `Or` < `And` < `Not` <
+/// `Compare`/parentheses, matching `parse_or` / `parse_and` / `parse_not`
+/// / `parse_primary` in the recursive-descent parser below.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionExpr {
+    Compare(Condition),
+    And(Box<ConditionExpr>, Box<ConditionExpr>),
+    Or(Box<ConditionExpr>, Box<ConditionExpr>),
+    Not(Box<ConditionExpr>),
+}
+
 /// A CCL statement (permit, deny, require, or limit).
 #[derive(Debug, Clone)]
 pub struct Statement {
     pub stmt_type: StatementType,
     pub action: String,
     pub resource: String,
-    pub condition: Option<Condition>,
+    pub condition: Option<ConditionExpr>,
     pub metric: Option<String>,
     pub limit: Option<f64>,
     pub period: Option<f64>,
     pub time_unit: Option<String>,
+    /// Source span of this statement (a single line, by grammar), for
+    /// pointing [`analyze`]'s findings at the offending statement.
+    pub span: Span,
 }
 
 /// A parsed CCL document containing categorized statement arrays.
@@ -86,6 +115,31 @@ pub struct NarrowingResult {
     pub violations: Vec<NarrowingViolation>,
 }
 
+/// A location in CCL source text, for pointing a [`Diagnostic`] at the
+/// exact token that triggered it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+}
+
+/// Severity of a [`Diagnostic`] produced by [`parse_with_diagnostics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single parse problem located in the source, as reported by
+/// [`parse_with_diagnostics`].
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub message: String,
+    pub span: Span,
+    pub severity: Severity,
+}
+
 // ---------------------------------------------------------------------------
 // Token types for the lexer
 // ---------------------------------------------------------------------------
@@ -127,13 +181,15 @@ struct Token {
     value: String,
     line: usize,
     column: usize,
+    /// Number of source characters this token spans, for diagnostic spans.
+    length: usize,
 }
 
 // ---------------------------------------------------------------------------
 // Lexer
 // ---------------------------------------------------------------------------
 
-fn tokenize(source: &str) -> Vec<Token> {
+fn tokenize(source: &str) -> Result<Vec<Token>, SteleError> {
     let chars: Vec<char> = source.chars().collect();
     let mut tokens: Vec<Token> = Vec::new();
     let mut pos = 0;
@@ -199,6 +255,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                             value: "\n".to_string(),
                             line: start_line,
                             column: start_col,
+                            length: 1,
                         });
                     }
                 }
@@ -218,6 +275,7 @@ fn tokenize(source: &str) -> Vec<Token> {
             }
             tokens.push(Token {
                 token_type: TokenType::Comment,
+                length: comment.chars().count(),
                 value: comment,
                 line: start_line,
                 column: start_col,
@@ -225,28 +283,115 @@ fn tokenize(source: &str) -> Vec<Token> {
             continue;
         }
 
-        // Single-quoted strings
+        // Single-quoted strings, with escape-sequence support: \n \t \r
+        // \\ \' and \uXXXX. Decoded eagerly, so `value` holds the literal
+        // string content (e.g. an actual newline, not the two characters
+        // `\` `n`) -- `serialize_condition_expr` re-escapes it on the way
+        // back out so round-tripping through `parse`/`serialize` is exact.
         if ch == '\'' {
             let start_line = line;
             let start_col = column;
             pos += 1; // consume opening quote
             column += 1;
             let mut s = String::new();
-            while pos < chars.len() && chars[pos] != '\'' {
-                if chars[pos] == '\n' {
-                    line += 1;
-                    column = 0;
+            let mut body_len = 0usize; // source characters consumed inside the quotes
+            let closed = loop {
+                if pos >= chars.len() || chars[pos] == '\n' {
+                    break false;
+                }
+                if chars[pos] == '\'' {
+                    pos += 1;
+                    column += 1;
+                    break true;
+                }
+                if chars[pos] == '\\' {
+                    let esc_line = line;
+                    let esc_col = column;
+                    if pos + 1 >= chars.len() {
+                        return Err(SteleError::CCLParseError(format!(
+                            "Unterminated escape sequence in string literal at line {} column {}",
+                            esc_line, esc_col,
+                        )));
+                    }
+                    match chars[pos + 1] {
+                        'n' => {
+                            s.push('\n');
+                            pos += 2;
+                            column += 2;
+                            body_len += 2;
+                        }
+                        't' => {
+                            s.push('\t');
+                            pos += 2;
+                            column += 2;
+                            body_len += 2;
+                        }
+                        'r' => {
+                            s.push('\r');
+                            pos += 2;
+                            column += 2;
+                            body_len += 2;
+                        }
+                        '\\' => {
+                            s.push('\\');
+                            pos += 2;
+                            column += 2;
+                            body_len += 2;
+                        }
+                        '\'' => {
+                            s.push('\'');
+                            pos += 2;
+                            column += 2;
+                            body_len += 2;
+                        }
+                        'u' => {
+                            if pos + 6 > chars.len() {
+                                return Err(SteleError::CCLParseError(format!(
+                                    "Incomplete \\u escape in string literal at line {} column {}",
+                                    esc_line, esc_col,
+                                )));
+                            }
+                            let hex: String = chars[pos + 2..pos + 6].iter().collect();
+                            let code = u32::from_str_radix(&hex, 16).map_err(|_| {
+                                SteleError::CCLParseError(format!(
+                                    "Invalid \\u escape '{}' in string literal at line {} column {}",
+                                    hex, esc_line, esc_col,
+                                ))
+                            })?;
+                            let decoded = char::from_u32(code).ok_or_else(|| {
+                                SteleError::CCLParseError(format!(
+                                    "Invalid unicode code point '\\u{}' in string literal at line {} column {}",
+                                    hex, esc_line, esc_col,
+                                ))
+                            })?;
+                            s.push(decoded);
+                            pos += 6;
+                            column += 6;
+                            body_len += 6;
+                        }
+                        other => {
+                            return Err(SteleError::CCLParseError(format!(
+                                "Unknown escape sequence '\\{}' in string literal at line {} column {}",
+                                other, esc_line, esc_col,
+                            )));
+                        }
+                    }
+                    continue;
                 }
                 s.push(chars[pos]);
                 pos += 1;
                 column += 1;
-            }
-            if pos < chars.len() {
-                pos += 1; // consume closing quote
-                column += 1;
+                body_len += 1;
+            };
+            if !closed {
+                return Err(SteleError::CCLParseError(format!(
+                    "Unterminated string literal starting at line {} column {}",
+                    start_line, start_col,
+                )));
             }
             tokens.push(Token {
                 token_type: TokenType::StringLit,
+                length: body_len + 2,
                 value: s,
                 line: start_line,
                 column: start_col,
@@ -261,6 +406,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                 value: "(".to_string(),
                 line,
                 column,
+                length: 1,
             });
             pos += 1;
             column += 1;
@@ -272,6 +418,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                 value: ")".to_string(),
                 line,
                 column,
+                length: 1,
             });
             pos += 1;
             column += 1;
@@ -285,6 +432,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                 value: "[".to_string(),
                 line,
                 column,
+                length: 1,
             });
             pos += 1;
             column += 1;
@@ -296,6 +444,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                 value: "]".to_string(),
                 line,
                 column,
+                length: 1,
             });
             pos += 1;
             column += 1;
@@ -309,6 +458,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                 value: ",".to_string(),
                 line,
                 column,
+                length: 1,
             });
             pos += 1;
             column += 1;
@@ -322,6 +472,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                 value: "!=".to_string(),
                 line,
                 column,
+                length: 2,
             });
             pos += 2;
             column += 2;
@@ -333,6 +484,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                 value: "<=".to_string(),
                 line,
                 column,
+                length: 2,
             });
             pos += 2;
             column += 2;
@@ -344,6 +496,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                 value: ">=".to_string(),
                 line,
                 column,
+                length: 2,
             });
             pos += 2;
             column += 2;
@@ -355,6 +508,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                 value: "<".to_string(),
                 line,
                 column,
+                length: 1,
             });
             pos += 1;
             column += 1;
@@ -366,6 +520,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                 value: ">".to_string(),
                 line,
                 column,
+                length: 1,
             });
             pos += 1;
             column += 1;
@@ -377,6 +532,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                 value: "=".to_string(),
                 line,
                 column,
+                length: 1,
             });
             pos += 1;
             column += 1;
@@ -397,6 +553,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                     value: "**".to_string(),
                     line: start_line,
                     column: start_col,
+                    length: 2,
                 });
             } else {
                 tokens.push(Token {
@@ -404,23 +561,77 @@ fn tokenize(source: &str) -> Vec<Token> {
                     value: "*".to_string(),
                     line: start_line,
                     column: start_col,
+                    length: 1,
                 });
             }
             continue;
         }
 
-        // Numbers
+        // Numbers: an integer part, an optional `.digits` fractional
+        // part, and an optional `e`/`E` exponent with an optional sign.
         if ch.is_ascii_digit() {
             let start_line = line;
             let start_col = column;
             let mut num = String::new();
+
             while pos < chars.len() && chars[pos].is_ascii_digit() {
                 num.push(chars[pos]);
                 pos += 1;
                 column += 1;
             }
+
+            // Fractional part: only consume the `.` if a digit follows,
+            // so `1.field` still lexes as Number + Dot + Identifier.
+            if pos + 1 < chars.len() && chars[pos] == '.' && chars[pos + 1].is_ascii_digit() {
+                num.push(chars[pos]);
+                pos += 1;
+                column += 1;
+                while pos < chars.len() && chars[pos].is_ascii_digit() {
+                    num.push(chars[pos]);
+                    pos += 1;
+                    column += 1;
+                }
+            }
+
+            // A second decimal point right after the first (e.g. `1.2.3`) is malformed.
+            if pos + 1 < chars.len() && chars[pos] == '.' && chars[pos + 1].is_ascii_digit() {
+                return Err(SteleError::CCLParseError(format!(
+                    "Malformed number '{}' at line {} column {}: unexpected second decimal point",
+                    num, start_line, start_col,
+                )));
+            }
+
+            // Exponent: `e`/`E`, optional sign, then at least one digit.
+            if pos < chars.len() && (chars[pos] == 'e' || chars[pos] == 'E') {
+                let mut peek = pos + 1;
+                if peek < chars.len() && (chars[peek] == '+' || chars[peek] == '-') {
+                    peek += 1;
+                }
+                if peek < chars.len() && chars[peek].is_ascii_digit() {
+                    num.push(chars[pos]);
+                    pos += 1;
+                    column += 1;
+                    if chars[pos] == '+' || chars[pos] == '-' {
+                        num.push(chars[pos]);
+                        pos += 1;
+                        column += 1;
+                    }
+                    while pos < chars.len() && chars[pos].is_ascii_digit() {
+                        num.push(chars[pos]);
+                        pos += 1;
+                        column += 1;
+                    }
+                } else {
+                    return Err(SteleError::CCLParseError(format!(
+                        "Malformed number '{}{}' at line {} column {}: exponent marker with no digits",
+                        num, chars[pos], start_line, start_col,
+                    )));
+                }
+            }
+
             tokens.push(Token {
                 token_type: TokenType::Number,
+                length: num.chars().count(),
                 value: num,
                 line: start_line,
                 column: start_col,
@@ -443,6 +654,7 @@ fn tokenize(source: &str) -> Vec<Token> {
             if word_operators.contains(&ident.as_str()) {
                 tokens.push(Token {
                     token_type: TokenType::Operator,
+                    length: ident.chars().count(),
                     value: ident,
                     line: start_line,
                     column: start_col,
@@ -455,6 +667,7 @@ fn tokenize(source: &str) -> Vec<Token> {
             if let Some(kw) = keywords.get(lower.as_str()) {
                 tokens.push(Token {
                     token_type: kw.clone(),
+                    length: ident.chars().count(),
                     value: ident,
                     line: start_line,
                     column: start_col,
@@ -464,6 +677,7 @@ fn tokenize(source: &str) -> Vec<Token> {
 
             tokens.push(Token {
                 token_type: TokenType::Identifier,
+                length: ident.chars().count(),
                 value: ident,
                 line: start_line,
                 column: start_col,
@@ -478,6 +692,7 @@ fn tokenize(source: &str) -> Vec<Token> {
                 value: ".".to_string(),
                 line,
                 column,
+                length: 1,
             });
             pos += 1;
             column += 1;
@@ -496,6 +711,7 @@ fn tokenize(source: &str) -> Vec<Token> {
             }
             tokens.push(Token {
                 token_type: TokenType::StringLit,
+                length: path.chars().count(),
                 value: path,
                 line: start_line,
                 column: start_col,
@@ -513,9 +729,10 @@ fn tokenize(source: &str) -> Vec<Token> {
         value: String::new(),
         line,
         column,
+        length: 0,
     });
 
-    tokens
+    Ok(tokens)
 }
 
 // ---------------------------------------------------------------------------
@@ -552,11 +769,11 @@ impl Parser {
         self.current().token_type == *tt
     }
 
-    fn expect(&mut self, tt: &TokenType, msg: &str) -> Result<Token, KervyxError> {
+    fn expect(&mut self, tt: &TokenType, msg: &str) -> Result<Token, SteleError> {
         if self.current().token_type == *tt {
             Ok(self.advance())
         } else {
-            Err(KervyxError::CCLParseError(format!(
+            Err(SteleError::CCLParseError(format!(
                 "{}, but got '{}' at line {} column {}",
                 msg,
                 self.current().value,
@@ -570,6 +787,20 @@ impl Parser {
         self.current().token_type == TokenType::Eof
     }
 
+    /// Compute the [`Span`] covering everything consumed since `start`
+    /// (the statement's leading keyword token), up to and including the
+    /// most recently consumed token. CCL statements never span more
+    /// than one source line, so `length` is just the column delta.
+    fn span_from(&self, start: &Token) -> Span {
+        let end = &self.tokens[self.pos.saturating_sub(1)];
+        let length = (end.column + end.length).saturating_sub(start.column);
+        Span {
+            line: start.line,
+            column: start.column,
+            length: length.max(1),
+        }
+    }
+
     fn skip_newlines_and_comments(&mut self) {
         while self.pos < self.tokens.len()
             && (self.current().token_type == TokenType::Newline
@@ -579,7 +810,7 @@ impl Parser {
         }
     }
 
-    fn parse(&mut self) -> Result<CCLDocument, KervyxError> {
+    fn parse(&mut self) -> Result<CCLDocument, SteleError> {
         let mut statements = Vec::new();
 
         self.skip_newlines_and_comments();
@@ -603,12 +834,77 @@ impl Parser {
         Ok(build_document(statements))
     }
 
-    fn parse_statement(&mut self) -> Result<Statement, KervyxError> {
+    /// Like [`Self::parse`], but never bails out on the first syntax
+    /// error: each failed statement is recorded as a [`Diagnostic`] and
+    /// the parser [`Self::synchronize`]s to the next statement instead
+    /// of stopping, so a caller sees every error in one pass.
+    fn parse_recovering(&mut self) -> (CCLDocument, Vec<Diagnostic>) {
+        let mut statements = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        self.skip_newlines_and_comments();
+
+        while !self.is_at_end() {
+            let tt = &self.current().token_type;
+            if *tt == TokenType::Newline || *tt == TokenType::Comment {
+                self.advance();
+                self.skip_newlines_and_comments();
+                continue;
+            }
+            if *tt == TokenType::Eof {
+                break;
+            }
+
+            match self.parse_statement() {
+                Ok(stmt) => statements.push(stmt),
+                Err(e) => {
+                    let tok = self.current().clone();
+                    diagnostics.push(Diagnostic {
+                        message: ccl_error_message(&e),
+                        span: Span {
+                            line: tok.line,
+                            column: tok.column,
+                            length: tok.length.max(1),
+                        },
+                        severity: Severity::Error,
+                    });
+                    self.synchronize();
+                }
+            }
+            self.skip_newlines_and_comments();
+        }
+
+        (build_document(statements), diagnostics)
+    }
+
+    /// Recover from a syntax error by advancing until a safe resumption
+    /// point: a newline immediately followed by a statement-start
+    /// keyword (`permit`/`deny`/`require`/`limit`), or EOF. Leaves the
+    /// parser positioned at that newline, ready for the caller's normal
+    /// `skip_newlines_and_comments` + `parse_statement` to pick back up.
+    fn synchronize(&mut self) {
+        while !self.is_at_end() {
+            if self.current().token_type == TokenType::Newline {
+                let next_pos = (self.pos + 1).min(self.tokens.len() - 1);
+                match self.tokens[next_pos].token_type {
+                    TokenType::Permit
+                    | TokenType::Deny
+                    | TokenType::Require
+                    | TokenType::Limit
+                    | TokenType::Eof => return,
+                    _ => {}
+                }
+            }
+            self.advance();
+        }
+    }
+
+    fn parse_statement(&mut self) -> Result<Statement, SteleError> {
         match self.current().token_type {
             TokenType::Permit | TokenType::Deny => self.parse_permit_deny(),
             TokenType::Require => self.parse_require(),
             TokenType::Limit => self.parse_limit(),
-            _ => Err(KervyxError::CCLParseError(format!(
+            _ => Err(SteleError::CCLParseError(format!(
                 "Expected statement keyword (permit, deny, require, or limit), got '{}' at line {} column {}",
                 self.current().value,
                 self.current().line,
@@ -617,7 +913,8 @@ impl Parser {
         }
     }
 
-    fn parse_permit_deny(&mut self) -> Result<Statement, KervyxError> {
+    fn parse_permit_deny(&mut self) -> Result<Statement, SteleError> {
+        let start = self.current().clone();
         let keyword = self.advance();
         let stmt_type = if keyword.token_type == TokenType::Permit {
             StatementType::Permit
@@ -654,10 +951,12 @@ impl Parser {
             limit: None,
             period: None,
             time_unit: None,
+            span: self.span_from(&start),
         })
     }
 
-    fn parse_require(&mut self) -> Result<Statement, KervyxError> {
+    fn parse_require(&mut self) -> Result<Statement, SteleError> {
+        let start = self.current().clone();
         self.advance(); // consume 'require'
         let action = self.parse_action()?;
         self.expect(&TokenType::On, "Expected 'on' after action")?;
@@ -686,10 +985,12 @@ impl Parser {
             limit: None,
             period: None,
             time_unit: None,
+            span: self.span_from(&start),
         })
     }
 
-    fn parse_limit(&mut self) -> Result<Statement, KervyxError> {
+    fn parse_limit(&mut self) -> Result<Statement, SteleError> {
+        let start = self.current().clone();
         self.advance(); // consume 'limit'
         let action = self.parse_action()?;
 
@@ -698,7 +999,7 @@ impl Parser {
         let count: f64 = count_tok
             .value
             .parse()
-            .map_err(|_| KervyxError::CCLParseError(format!("Invalid count number: {}", count_tok.value)))?;
+            .map_err(|_| SteleError::CCLParseError(format!("Invalid count number: {}", count_tok.value)))?;
 
         self.expect(&TokenType::Per, "Expected 'per' in limit statement")?;
 
@@ -707,7 +1008,7 @@ impl Parser {
         let raw_period: f64 = period_tok
             .value
             .parse()
-            .map_err(|_| KervyxError::CCLParseError(format!("Invalid period number: {}", period_tok.value)))?;
+            .map_err(|_| SteleError::CCLParseError(format!("Invalid period number: {}", period_tok.value)))?;
 
         // Parse time unit
         let unit_tok = self.expect(&TokenType::TimeUnit, "Expected time unit (seconds, minutes, hours, days)")?;
@@ -731,10 +1032,11 @@ impl Parser {
             limit: Some(count),
             period: Some(period_seconds),
             time_unit: Some(unit_value),
+            span: self.span_from(&start),
         })
     }
 
-    fn parse_action(&mut self) -> Result<String, KervyxError> {
+    fn parse_action(&mut self) -> Result<String, SteleError> {
         let mut parts = Vec::new();
 
         if self.check(&TokenType::DoubleWildcard) {
@@ -748,7 +1050,7 @@ impl Parser {
         } else if self.check(&TokenType::Identifier) {
             parts.push(self.advance().value);
         } else {
-            return Err(KervyxError::CCLParseError(format!(
+            return Err(SteleError::CCLParseError(format!(
                 "Expected action identifier, got '{}' at line {} column {}",
                 self.current().value,
                 self.current().line,
@@ -767,7 +1069,7 @@ impl Parser {
                 parts.push("**".to_string());
                 self.advance();
             } else {
-                return Err(KervyxError::CCLParseError(format!(
+                return Err(SteleError::CCLParseError(format!(
                     "Expected identifier or wildcard after dot, got '{}' at line {} column {}",
                     self.current().value,
                     self.current().line,
@@ -779,7 +1081,7 @@ impl Parser {
         Ok(parts.join("."))
     }
 
-    fn parse_resource(&mut self) -> Result<String, KervyxError> {
+    fn parse_resource(&mut self) -> Result<String, SteleError> {
         match self.current().token_type {
             TokenType::StringLit => Ok(self.advance().value),
             TokenType::Wildcard => {
@@ -791,7 +1093,7 @@ impl Parser {
                 Ok("**".to_string())
             }
             TokenType::Identifier => Ok(self.advance().value),
-            _ => Err(KervyxError::CCLParseError(format!(
+            _ => Err(SteleError::CCLParseError(format!(
                 "Expected resource (string or pattern), got '{}' at line {} column {}",
                 self.current().value,
                 self.current().line,
@@ -800,13 +1102,60 @@ impl Parser {
         }
     }
 
-    fn parse_condition(&mut self) -> Result<Condition, KervyxError> {
+    /// Parse a full boolean `when` expression. Precedence (loosest to
+    /// tightest): `or` < `and` < `not` < comparison/parentheses.
+    fn parse_condition(&mut self) -> Result<ConditionExpr, SteleError> {
+        self.parse_or()
+    }
+
+    fn parse_or(&mut self) -> Result<ConditionExpr, SteleError> {
+        let mut expr = self.parse_and()?;
+        while self.check(&TokenType::Or) {
+            self.advance();
+            let rhs = self.parse_and()?;
+            expr = ConditionExpr::Or(Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<ConditionExpr, SteleError> {
+        let mut expr = self.parse_not()?;
+        while self.check(&TokenType::And) {
+            self.advance();
+            let rhs = self.parse_not()?;
+            expr = ConditionExpr::And(Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<ConditionExpr, SteleError> {
+        if self.check(&TokenType::Not) {
+            self.advance();
+            // Unary, right-associative, binds tightest after parentheses.
+            let inner = self.parse_not()?;
+            return Ok(ConditionExpr::Not(Box::new(inner)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<ConditionExpr, SteleError> {
+        if self.check(&TokenType::LParen) {
+            self.advance();
+            let expr = self.parse_or()?;
+            self.expect(&TokenType::RParen, "Expected ')' to close grouped condition")?;
+            return Ok(expr);
+        }
+        Ok(ConditionExpr::Compare(self.parse_comparison()?))
+    }
+
+    /// Parse a single `field operator value` comparison leaf.
+    fn parse_comparison(&mut self) -> Result<Condition, SteleError> {
         // Parse the field
         let field = self.parse_field()?;
 
         // Parse the operator
         if self.current().token_type != TokenType::Operator {
-            return Err(KervyxError::CCLParseError(format!(
+            return Err(SteleError::CCLParseError(format!(
                 "Expected operator after field '{}', got '{}' at line {} column {}",
                 field,
                 self.current().value,
@@ -826,9 +1175,9 @@ impl Parser {
         })
     }
 
-    fn parse_field(&mut self) -> Result<String, KervyxError> {
+    fn parse_field(&mut self) -> Result<String, SteleError> {
         if self.current().token_type != TokenType::Identifier {
-            return Err(KervyxError::CCLParseError(format!(
+            return Err(SteleError::CCLParseError(format!(
                 "Expected field identifier, got '{}' at line {} column {}",
                 self.current().value,
                 self.current().line,
@@ -841,7 +1190,7 @@ impl Parser {
         while self.check(&TokenType::Dot) {
             self.advance();
             if self.current().token_type != TokenType::Identifier {
-                return Err(KervyxError::CCLParseError(format!(
+                return Err(SteleError::CCLParseError(format!(
                     "Expected identifier after dot in field, got '{}' at line {} column {}",
                     self.current().value,
                     self.current().line,
@@ -855,12 +1204,12 @@ impl Parser {
         Ok(field)
     }
 
-    fn parse_value(&mut self) -> Result<String, KervyxError> {
+    fn parse_value(&mut self) -> Result<String, SteleError> {
         match self.current().token_type {
             TokenType::StringLit => Ok(self.advance().value),
             TokenType::Number => Ok(self.advance().value),
             TokenType::Identifier => Ok(self.advance().value),
-            _ => Err(KervyxError::CCLParseError(format!(
+            _ => Err(SteleError::CCLParseError(format!(
                 "Expected value, got '{}' at line {} column {}",
                 self.current().value,
                 self.current().line,
@@ -870,6 +1219,15 @@ impl Parser {
     }
 }
 
+/// Unwrap the single `CCLParseError` variant `parse_recovering` ever
+/// constructs, for embedding its message in a [`Diagnostic`].
+fn ccl_error_message(err: &SteleError) -> String {
+    match err {
+        SteleError::CCLParseError(msg) => msg.clone(),
+        other => other.to_string(),
+    }
+}
+
 fn time_unit_multiplier(unit: &str) -> f64 {
     match unit {
         "second" | "seconds" => 1.0,
@@ -910,8 +1268,11 @@ fn build_document(statements: Vec<Statement>) -> CCLDocument {
 
 /// Parse CCL source text into a `CCLDocument`.
 ///
+/// A thin wrapper over [`parse_with_diagnostics`] that surfaces the
+/// first error as a `Result` for callers that just want pass/fail.
+///
 /// # Errors
-/// Returns `KervyxError::CCLParseError` if the source contains syntax errors.
+/// Returns `SteleError::CCLParseError` if the source contains syntax errors.
 ///
 /// # Example
 /// ```
@@ -919,10 +1280,31 @@ fn build_document(statements: Vec<Statement>) -> CCLDocument {
 /// let doc = parse("permit read on '/data/**'").unwrap();
 /// assert_eq!(doc.permits.len(), 1);
 /// ```
-pub fn parse(source: &str) -> Result<CCLDocument, KervyxError> {
-    let tokens = tokenize(source);
+pub fn parse(source: &str) -> Result<CCLDocument, SteleError> {
+    let (doc, diagnostics) = parse_with_diagnostics(source)?;
+    if let Some(first_error) = diagnostics.iter().find(|d| d.severity == Severity::Error) {
+        return Err(SteleError::CCLParseError(format!(
+            "{} at line {} column {}",
+            first_error.message, first_error.span.line, first_error.span.column
+        )));
+    }
+    Ok(doc)
+}
+
+/// Parse CCL source text into a `CCLDocument`, collecting every syntax
+/// error as a [`Diagnostic`] instead of stopping at the first one.
+///
+/// The returned document only contains the statements that parsed
+/// successfully; `diagnostics` is empty when the source is well-formed.
+/// Useful for editor tooling that wants to underline every mistake in
+/// one pass rather than forcing a fix-one-rerun loop.
+///
+/// Lexical errors (an unterminated string, a bad escape, a malformed
+/// number) are not recovered from: the first one is returned as `Err`.
+pub fn parse_with_diagnostics(source: &str) -> Result<(CCLDocument, Vec<Diagnostic>), SteleError> {
+    let tokens = tokenize(source)?;
     let mut parser = Parser::new(tokens);
-    parser.parse()
+    Ok(parser.parse_recovering())
 }
 
 /// Match an action string against a dot-separated pattern.
@@ -930,6 +1312,9 @@ pub fn parse(source: &str) -> Result<CCLDocument, KervyxError> {
 /// Segments are split on `.`. Wildcard rules:
 /// - `*` matches exactly one segment
 /// - `**` matches zero or more segments
+/// - `?` within a segment matches any single character
+/// - `{a,b,c}` as a whole segment matches if the target segment equals
+///   any alternative (alternatives may themselves use `*`/`?`)
 pub fn match_action(pattern: &str, action: &str) -> bool {
     let pattern_parts: Vec<&str> = pattern.split('.').collect();
     let action_parts: Vec<&str> = action.split('.').collect();
@@ -941,6 +1326,9 @@ pub fn match_action(pattern: &str, action: &str) -> bool {
 /// Leading and trailing slashes are normalized. Wildcard rules:
 /// - `*` matches exactly one path segment
 /// - `**` matches zero or more segments
+/// - `?` within a segment matches any single character
+/// - `{a,b,c}` as a whole segment matches if the target segment equals
+///   any alternative (alternatives may themselves use `*`/`?`)
 pub fn match_resource(pattern: &str, resource: &str) -> bool {
     let norm_pattern = pattern.trim_matches('/');
     let norm_resource = resource.trim_matches('/');
@@ -960,7 +1348,9 @@ pub fn match_resource(pattern: &str, resource: &str) -> bool {
     match_segments(&pattern_parts, 0, &resource_parts, 0)
 }
 
-/// Generic segment matcher supporting `*` (single) and `**` (multi) wildcards.
+/// Generic segment matcher supporting `*` (single) and `**` (multi)
+/// whole-segment wildcards, plus per-segment `?` and `{a,b,c}` handled
+/// by [`segment_matches`].
 fn match_segments(pattern: &[&str], pi: usize, target: &[&str], ti: usize) -> bool {
     let mut pi = pi;
     let mut ti = ti;
@@ -983,8 +1373,7 @@ fn match_segments(pattern: &[&str], pi: usize, target: &[&str], ti: usize) -> bo
             continue;
         }
 
-        // Literal match
-        if p != target[ti] {
+        if !segment_matches(p, target[ti]) {
             return false;
         }
         pi += 1;
@@ -999,9 +1388,33 @@ fn match_segments(pattern: &[&str], pi: usize, target: &[&str], ti: usize) -> bo
     pi == pattern.len() && ti == target.len()
 }
 
+/// Match a single segment that isn't the whole-segment `*`/`**`
+/// wildcards: either a `{a,b,c}` brace alternation (matching if
+/// `target` matches any alternative) or a literal possibly containing
+/// `?`/`*`, matched with the same [`glob_matches`] engine used by the
+/// `matches` condition operator.
+fn segment_matches(pattern_segment: &str, target_segment: &str) -> bool {
+    match brace_alternatives(pattern_segment) {
+        Some(alts) => alts.iter().any(|alt| glob_matches(alt, target_segment)),
+        None => glob_matches(pattern_segment, target_segment),
+    }
+}
+
+/// If `segment` is a whole `{a,b,c}` brace group, split it into its
+/// comma-separated alternatives. Returns `None` for a plain segment.
+fn brace_alternatives(segment: &str) -> Option<Vec<&str>> {
+    if segment.len() >= 2 && segment.starts_with('{') && segment.ends_with('}') {
+        Some(segment[1..segment.len() - 1].split(',').collect())
+    } else {
+        None
+    }
+}
+
 /// Calculate the specificity score of an action+resource pattern pair.
 ///
-/// Scoring per segment: literal = 2, `*` = 1, `**` = 0.
+/// Scoring per segment: literal = 2, `*` = 1, `**` = 0. A `{a,b,c}`
+/// brace set or a segment containing `?` is neither `*` nor `**`, so
+/// it falls through to the literal score of 2.
 fn specificity(action_pattern: &str, resource_pattern: &str) -> i32 {
     let mut score = 0i32;
 
@@ -1027,7 +1440,153 @@ fn specificity(action_pattern: &str, resource_pattern: &str) -> i32 {
     score
 }
 
-/// Evaluate a condition against a context map.
+/// Match `text` against a shell-style glob `pattern`, for the `matches`
+/// condition operator.
+///
+/// Supports `*` (any run of characters, including none), `?` (any
+/// single character), `[...]` character classes (with `a-z`-style
+/// ranges and `[!...]` negation), and `\` to escape the next character
+/// literally. An empty pattern matches only the empty string. An
+/// unterminated `[` (no closing `]`) is treated as a literal `[`.
+///
+/// Uses the classic linear two-pointer algorithm: advance through
+/// `pattern` and `text` together, remembering the most recent `*`'s
+/// position in both strings; on a mismatch, if a `*` was seen, rewind
+/// to just after it and retry against one more character of `text`.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+
+    let mut pi = 0usize;
+    let mut ti = 0usize;
+    let mut star: Option<usize> = None;
+    let mut star_ti = 0usize;
+
+    while ti < t.len() {
+        if pi < p.len() {
+            match p[pi] {
+                '*' => {
+                    star = Some(pi);
+                    star_ti = ti;
+                    pi += 1;
+                    continue;
+                }
+                '?' => {
+                    pi += 1;
+                    ti += 1;
+                    continue;
+                }
+                '[' => {
+                    if let Some((matched, class_end)) = match_char_class(&p, pi, t[ti]) {
+                        if matched {
+                            pi = class_end;
+                            ti += 1;
+                            continue;
+                        }
+                    } else if p[pi] == t[ti] {
+                        // Unterminated `[`: treat as a literal.
+                        pi += 1;
+                        ti += 1;
+                        continue;
+                    }
+                }
+                '\\' if pi + 1 < p.len() => {
+                    if p[pi + 1] == t[ti] {
+                        pi += 2;
+                        ti += 1;
+                        continue;
+                    }
+                }
+                c if c == t[ti] => {
+                    pi += 1;
+                    ti += 1;
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        // Mismatch (or pattern exhausted): backtrack to the last `*`.
+        match star {
+            Some(s) => {
+                pi = s + 1;
+                star_ti += 1;
+                ti = star_ti;
+            }
+            None => return false,
+        }
+    }
+
+    // Trailing `*`s collapse to match the empty remainder.
+    while pi < p.len() && p[pi] == '*' {
+        pi += 1;
+    }
+
+    pi == p.len()
+}
+
+/// Try to match `[...]` (a character class starting at `p[start]`)
+/// against `c`. Returns `Some((matched, index_after_class))` if a
+/// closing `]` was found, or `None` if the class is unterminated.
+fn match_char_class(p: &[char], start: usize, c: char) -> Option<(bool, usize)> {
+    let mut i = start + 1;
+    let negate = i < p.len() && p[i] == '!';
+    if negate {
+        i += 1;
+    }
+    let members_start = i;
+
+    // Find the closing `]`, which may not be the first char after the
+    // opening (a leading `]` is a literal member of the class).
+    let mut j = i;
+    if j < p.len() && p[j] == ']' {
+        j += 1;
+    }
+    while j < p.len() && p[j] != ']' {
+        j += 1;
+    }
+    if j >= p.len() {
+        return None;
+    }
+    let class_end = j + 1;
+
+    let mut matched = false;
+    let mut k = members_start;
+    while k < j {
+        if k + 2 < j && p[k + 1] == '-' {
+            let (lo, hi) = (p[k], p[k + 2]);
+            if lo <= c && c <= hi {
+                matched = true;
+            }
+            k += 3;
+        } else {
+            if p[k] == c {
+                matched = true;
+            }
+            k += 1;
+        }
+    }
+
+    Some((matched != negate, class_end))
+}
+
+/// Evaluate a boolean `when` expression tree against a context map,
+/// short-circuiting `and`/`or` the way the language's precedence
+/// (`or` < `and` < `not` < comparison) implies.
+fn evaluate_condition_expr(expr: &ConditionExpr, context: &HashMap<String, String>) -> bool {
+    match expr {
+        ConditionExpr::Compare(cond) => evaluate_condition(cond, context),
+        ConditionExpr::And(lhs, rhs) => {
+            evaluate_condition_expr(lhs, context) && evaluate_condition_expr(rhs, context)
+        }
+        ConditionExpr::Or(lhs, rhs) => {
+            evaluate_condition_expr(lhs, context) || evaluate_condition_expr(rhs, context)
+        }
+        ConditionExpr::Not(inner) => !evaluate_condition_expr(inner, context),
+    }
+}
+
+/// Evaluate a single `field operator value` comparison against a context map.
 fn evaluate_condition(condition: &Condition, context: &HashMap<String, String>) -> bool {
     let field_value = match context.get(&condition.field) {
         Some(v) => v.clone(),
@@ -1069,10 +1628,7 @@ fn evaluate_condition(condition: &Condition, context: &HashMap<String, String>)
         "not_contains" => !field_value.contains(&condition.value),
         "starts_with" => field_value.starts_with(&condition.value),
         "ends_with" => field_value.ends_with(&condition.value),
-        "matches" => {
-            // Simple prefix/suffix matching since we don't pull in regex
-            field_value == condition.value
-        }
+        "matches" => glob_matches(&condition.value, &field_value),
         _ => false,
     }
 }
@@ -1096,11 +1652,10 @@ pub fn evaluate(
     // Collect matching permits
     for stmt in &doc.permits {
         if match_action(&stmt.action, action) && match_resource(&stmt.resource, resource) {
-            if stmt.condition.is_none()
-                || stmt
-                    .condition
-                    .as_ref()
-                    .map_or(true, |c| evaluate_condition(c, context))
+            if stmt
+                .condition
+                .as_ref()
+                .map_or(true, |c| evaluate_condition_expr(c, context))
             {
                 matched_permit_deny.push(stmt.clone());
                 all_matches.push(stmt.clone());
@@ -1111,11 +1666,10 @@ pub fn evaluate(
     // Collect matching denies
     for stmt in &doc.denies {
         if match_action(&stmt.action, action) && match_resource(&stmt.resource, resource) {
-            if stmt.condition.is_none()
-                || stmt
-                    .condition
-                    .as_ref()
-                    .map_or(true, |c| evaluate_condition(c, context))
+            if stmt
+                .condition
+                .as_ref()
+                .map_or(true, |c| evaluate_condition_expr(c, context))
             {
                 matched_permit_deny.push(stmt.clone());
                 all_matches.push(stmt.clone());
@@ -1126,11 +1680,10 @@ pub fn evaluate(
     // Collect matching obligations (don't affect permit/deny)
     for stmt in &doc.obligations {
         if match_action(&stmt.action, action) && match_resource(&stmt.resource, resource) {
-            if stmt.condition.is_none()
-                || stmt
-                    .condition
-                    .as_ref()
-                    .map_or(true, |c| evaluate_condition(c, context))
+            if stmt
+                .condition
+                .as_ref()
+                .map_or(true, |c| evaluate_condition_expr(c, context))
             {
                 all_matches.push(stmt.clone());
             }
@@ -1139,6 +1692,7 @@ pub fn evaluate(
 
     // No matching permit/deny rules: default deny
     if matched_permit_deny.is_empty() {
+        telemetry::record_decision(false);
         return EvaluationResult {
             permitted: false,
             matched_rule: None,
@@ -1170,6 +1724,7 @@ pub fn evaluate(
 
     let winner = &matched_permit_deny[0];
     let permitted = winner.stmt_type == StatementType::Permit;
+    telemetry::record_decision(permitted);
 
     EvaluationResult {
         permitted,
@@ -1183,6 +1738,239 @@ pub fn evaluate(
     }
 }
 
+/// A node in a [`CompiledPolicy`]'s segment trie.
+///
+/// `literal` continues the trie for an exact segment at this depth.
+/// `star`/`double_star` are buckets of rule indices whose pattern has
+/// a `*`/`**` wildcard starting at this depth (`star` also takes any
+/// segment with an in-segment glob such as `fil?` or `{a,b}`) -- insertion stops there
+/// rather than indexing past the wildcard, so these buckets (and the
+/// exact matcher re-check in [`CompiledPolicy::evaluate`]) are what
+/// keep wildcard rules correct. `end` holds rules whose pattern is a
+/// plain literal chain that ends exactly at this depth.
+#[derive(Default)]
+struct SegmentNode {
+    literal: HashMap<String, SegmentNode>,
+    star: Vec<usize>,
+    double_star: Vec<usize>,
+    end: Vec<usize>,
+}
+
+impl SegmentNode {
+    fn insert(&mut self, segments: &[&str], rule_idx: usize) {
+        let mut node = self;
+        for seg in segments {
+            match *seg {
+                "**" => {
+                    node.double_star.push(rule_idx);
+                    return;
+                }
+                "*" => {
+                    node.star.push(rule_idx);
+                    return;
+                }
+                // An in-segment glob (`file*`, `fil?`, `{a,b}`) can match
+                // many literal segments, so it is bucketed like `*`.
+                glob if glob.contains(|c: char| matches!(c, '*' | '?' | '{' | '[' | '\\')) => {
+                    node.star.push(rule_idx);
+                    return;
+                }
+                literal => {
+                    node = node
+                        .literal
+                        .entry(literal.to_string())
+                        .or_insert_with(SegmentNode::default);
+                }
+            }
+        }
+        node.end.push(rule_idx);
+    }
+
+    /// Collect every rule index that *might* match `segments`: the
+    /// `*`/`**` buckets of every node on the literal descent path, plus
+    /// the `end` bucket if the path fully consumes `segments`. This is
+    /// a superset of the true matches -- [`CompiledPolicy::evaluate`]
+    /// re-checks each candidate with the exact [`match_action`] /
+    /// [`match_resource`] matchers.
+    fn collect_candidates(&self, segments: &[&str], into: &mut [bool]) {
+        let mut node = self;
+        let mut depth = 0;
+        loop {
+            for &idx in &node.star {
+                into[idx] = true;
+            }
+            for &idx in &node.double_star {
+                into[idx] = true;
+            }
+            if depth == segments.len() {
+                for &idx in &node.end {
+                    into[idx] = true;
+                }
+                break;
+            }
+            match node.literal.get(segments[depth]) {
+                Some(child) => {
+                    node = child;
+                    depth += 1;
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+fn resource_segments(resource: &str) -> Vec<&str> {
+    let normalized = resource.trim_matches('/');
+    if normalized.is_empty() {
+        Vec::new()
+    } else {
+        normalized.split('/').collect()
+    }
+}
+
+/// A precompiled index over a [`CCLDocument`]'s permit/deny rules, for
+/// repeated [`CompiledPolicy::evaluate`] calls against a large policy
+/// without [`evaluate`]'s per-call linear scan.
+///
+/// Indexes rules in two segment tries (one over `.`-separated action
+/// segments, one over `/`-separated resource segments), in the spirit
+/// of Aho-Corasick multi-pattern dispatch: a query descends each trie
+/// following its real segments, gathering every rule that could match
+/// (see [`SegmentNode::collect_candidates`]) in time proportional to
+/// the query's depth rather than the rule count. The action and
+/// resource candidate sets are intersected, each survivor is
+/// re-verified with the exact matchers, and the result is resolved
+/// exactly like [`evaluate`] (specificity order, deny wins ties) --
+/// so `CompiledPolicy::evaluate` is a drop-in, faster replacement for
+/// `evaluate` on a document that doesn't change between queries.
+pub struct CompiledPolicy {
+    rules: Vec<Statement>,
+    obligations: Vec<Statement>,
+    action_trie: SegmentNode,
+    resource_trie: SegmentNode,
+}
+
+impl CompiledPolicy {
+    /// Build the index from a parsed document. Do this once and reuse
+    /// the result across every `evaluate` call against that document.
+    pub fn compile(doc: &CCLDocument) -> CompiledPolicy {
+        let rules: Vec<Statement> = doc
+            .permits
+            .iter()
+            .chain(doc.denies.iter())
+            .cloned()
+            .collect();
+
+        let mut action_trie = SegmentNode::default();
+        let mut resource_trie = SegmentNode::default();
+        for (idx, rule) in rules.iter().enumerate() {
+            let action_segs: Vec<&str> = rule.action.split('.').collect();
+            action_trie.insert(&action_segs, idx);
+            resource_trie.insert(&resource_segments(&rule.resource), idx);
+        }
+
+        CompiledPolicy {
+            rules,
+            obligations: doc.obligations.clone(),
+            action_trie,
+            resource_trie,
+        }
+    }
+
+    /// Evaluate an action/resource pair against this index. Produces
+    /// the same [`EvaluationResult`] as calling [`evaluate`] on the
+    /// document this was compiled from.
+    pub fn evaluate(
+        &self,
+        action: &str,
+        resource: &str,
+        context: &HashMap<String, String>,
+    ) -> EvaluationResult {
+        let action_segs: Vec<&str> = action.split('.').collect();
+        let resource_segs = resource_segments(resource);
+
+        let mut is_action_candidate = vec![false; self.rules.len()];
+        self.action_trie
+            .collect_candidates(&action_segs, &mut is_action_candidate);
+        let mut is_resource_candidate = vec![false; self.rules.len()];
+        self.resource_trie
+            .collect_candidates(&resource_segs, &mut is_resource_candidate);
+
+        let mut all_matches: Vec<Statement> = Vec::new();
+        let mut matched_permit_deny: Vec<Statement> = Vec::new();
+
+        for (idx, stmt) in self.rules.iter().enumerate() {
+            if !is_action_candidate[idx] || !is_resource_candidate[idx] {
+                continue;
+            }
+            if match_action(&stmt.action, action)
+                && match_resource(&stmt.resource, resource)
+                && stmt
+                    .condition
+                    .as_ref()
+                    .map_or(true, |c| evaluate_condition_expr(c, context))
+            {
+                matched_permit_deny.push(stmt.clone());
+                all_matches.push(stmt.clone());
+            }
+        }
+
+        for stmt in &self.obligations {
+            if match_action(&stmt.action, action)
+                && match_resource(&stmt.resource, resource)
+                && stmt
+                    .condition
+                    .as_ref()
+                    .map_or(true, |c| evaluate_condition_expr(c, context))
+            {
+                all_matches.push(stmt.clone());
+            }
+        }
+
+        if matched_permit_deny.is_empty() {
+            return EvaluationResult {
+                permitted: false,
+                matched_rule: None,
+                all_matches,
+                reason: "No matching rules found; default deny".to_string(),
+                severity: None,
+            };
+        }
+
+        matched_permit_deny.sort_by(|a, b| {
+            let spec_a = specificity(&a.action, &a.resource);
+            let spec_b = specificity(&b.action, &b.resource);
+
+            match spec_b.cmp(&spec_a) {
+                std::cmp::Ordering::Equal => {
+                    let a_is_deny = a.stmt_type == StatementType::Deny;
+                    let b_is_deny = b.stmt_type == StatementType::Deny;
+                    match (a_is_deny, b_is_deny) {
+                        (true, false) => std::cmp::Ordering::Less,
+                        (false, true) => std::cmp::Ordering::Greater,
+                        _ => std::cmp::Ordering::Equal,
+                    }
+                }
+                other => other,
+            }
+        });
+
+        let winner = &matched_permit_deny[0];
+        let permitted = winner.stmt_type == StatementType::Permit;
+
+        EvaluationResult {
+            permitted,
+            matched_rule: Some(winner.clone()),
+            all_matches,
+            reason: format!(
+                "Matched {:?} rule for {} on {}",
+                winner.stmt_type, winner.action, winner.resource
+            ),
+            severity: None,
+        }
+    }
+}
+
 /// Check whether an action has exceeded its rate limit.
 ///
 /// Finds the most specific matching limit statement, then checks whether
@@ -1233,13 +2021,77 @@ pub fn check_rate_limit(
     }
 
     let remaining = (count_limit - current_count).max(0);
+    let exceeded = current_count >= count_limit;
+    telemetry::record_rate_limit_check(metric, exceeded);
     RateLimitResult {
-        exceeded: current_count >= count_limit,
+        exceeded,
         remaining,
         limit: count_limit,
     }
 }
 
+/// Enforce a rate limit across evaluations, backed by a [`Store`](crate::store::Store).
+///
+/// Unlike [`check_rate_limit`], which is stateless and trusts the caller
+/// to track `current_count`/`window_start` itself, this records `action`
+/// as used by `covenant_id` at `now_ms`, prunes usages older than the
+/// matched limit statement's period (so a store's per-action history
+/// never grows past one window), and counts what's left to decide
+/// whether the limit is exceeded.
+///
+/// Returns a zero-limit, never-exceeded result if no `limit` statement
+/// matches `action`, same as `check_rate_limit`.
+///
+/// # Errors
+/// Propagates any `Store` error from recording, pruning, or counting usage.
+#[cfg(feature = "std")]
+pub fn enforce_rate_limit<S: crate::store::Store>(
+    doc: &CCLDocument,
+    action: &str,
+    covenant_id: &str,
+    now_ms: i64,
+    store: &mut S,
+) -> Result<RateLimitResult, crate::SteleError> {
+    let mut matched_limit: Option<&Statement> = None;
+    let mut best_specificity = -1i32;
+
+    for limit_stmt in &doc.limits {
+        if match_action(&limit_stmt.action, action) {
+            let spec = specificity(&limit_stmt.action, "");
+            if spec > best_specificity {
+                best_specificity = spec;
+                matched_limit = Some(limit_stmt);
+            }
+        }
+    }
+
+    let limit_stmt = match matched_limit {
+        Some(s) => s,
+        None => {
+            return Ok(RateLimitResult {
+                exceeded: false,
+                remaining: i64::MAX,
+                limit: 0,
+            })
+        }
+    };
+
+    let count_limit = limit_stmt.limit.unwrap_or(0.0) as i64;
+    let period_ms = (limit_stmt.period.unwrap_or(0.0) * 1000.0) as i64;
+    let window_start_ms = now_ms - period_ms;
+
+    store.record_usage(covenant_id, action, now_ms)?;
+    store.prune_usage(covenant_id, action, window_start_ms)?;
+    let current_count = store.count_usage(covenant_id, action, window_start_ms)?;
+
+    let remaining = (count_limit - current_count).max(0);
+    Ok(RateLimitResult {
+        exceeded: current_count > count_limit,
+        remaining,
+        limit: count_limit,
+    })
+}
+
 /// Validate that a child CCL document only narrows (restricts) the parent.
 ///
 /// Violations occur when:
@@ -1309,12 +2161,51 @@ fn patterns_overlap(pattern1: &str, pattern2: &str) -> bool {
     }
 }
 
+/// Turn a wildcard pattern into one concrete string it matches, for
+/// probing overlap against another pattern: `*`/`?` each become a
+/// single `x`, and a `{a,b,c}` brace group collapses to its first
+/// alternative (one concrete member is enough to detect overlap).
 fn pattern_to_concrete(pattern: &str) -> String {
-    pattern.replace("**", "x").replace('*', "x")
+    let mut result = String::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' | '?' => result.push('x'),
+            '{' => {
+                let mut first_alt = String::new();
+                let mut past_first = false;
+                for c2 in chars.by_ref() {
+                    if c2 == '}' {
+                        break;
+                    }
+                    if c2 == ',' {
+                        past_first = true;
+                        continue;
+                    }
+                    if !past_first {
+                        first_alt.push(c2);
+                    }
+                }
+                if first_alt.is_empty() {
+                    result.push('x');
+                } else {
+                    result.push_str(&first_alt);
+                }
+            }
+            other => result.push(other),
+        }
+    }
+
+    result
 }
 
 /// Check if child_pattern is a subset of (at most as broad as) parent_pattern.
-fn is_subset_pattern(child_pattern: &str, parent_pattern: &str, separator: &str) -> bool {
+///
+/// `pub(crate)` so [`crate::identity::delegation`] can reuse the same
+/// hierarchical narrowing rules for capability-pattern attenuation
+/// rather than reimplementing `**`/`*` subset matching a second time.
+pub(crate) fn is_subset_pattern(child_pattern: &str, parent_pattern: &str, separator: &str) -> bool {
     if parent_pattern == "**" {
         return true;
     }
@@ -1382,13 +2273,32 @@ fn is_subset_segments(child: &[&str], ci: usize, parent: &[&str], pi: usize) ->
         return is_subset_segments(child, ci + 1, parent, pi + 1);
     }
 
-    // Both literals: must match exactly
-    if c_seg != p_seg {
+    // Both literals (possibly brace sets): child must be no broader than parent.
+    if !segment_is_subset(c_seg, p_seg) {
         return false;
     }
     is_subset_segments(child, ci + 1, parent, pi + 1)
 }
 
+/// Is `child_segment` at most as broad as `parent_segment`, where
+/// either may be a `{a,b,c}` brace set? A child brace set is a subset
+/// iff every one of its alternatives appears in the parent's brace
+/// set; a plain child literal is a subset of a parent brace set iff
+/// it appears among the parent's alternatives.
+fn segment_is_subset(child_segment: &str, parent_segment: &str) -> bool {
+    match (
+        brace_alternatives(child_segment),
+        brace_alternatives(parent_segment),
+    ) {
+        (Some(child_alts), Some(parent_alts)) => {
+            child_alts.iter().all(|c| parent_alts.contains(c))
+        }
+        (Some(child_alts), None) => child_alts.iter().all(|c| *c == parent_segment),
+        (None, Some(parent_alts)) => parent_alts.contains(&child_segment),
+        (None, None) => child_segment == parent_segment,
+    }
+}
+
 /// Merge a parent and child CCL document with deny-wins semantics.
 ///
 /// - All denies from both parent and child are included.
@@ -1428,11 +2338,230 @@ pub fn merge(parent: &CCLDocument, child: &CCLDocument) -> CCLDocument {
     build_document(statements)
 }
 
-/// Serialize a CCL document back to human-readable CCL source text.
-pub fn serialize(doc: &CCLDocument) -> String {
-    let mut lines = Vec::new();
+/// Run a static lint pass over a parsed document, surfacing problems the
+/// evaluator would otherwise just silently resolve (or hide) at
+/// runtime: dead rules, duplicated statements, conditions that can
+/// never be satisfied, and rate limits that disagree with each other.
+///
+/// Each finding is a [`Diagnostic`] anchored at the offending
+/// statement's span: `Severity::Warning` for dead/duplicate/ambiguous
+/// rules, `Severity::Error` for a condition that can never be true.
+pub fn analyze(doc: &CCLDocument) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    analyze_dead_permits(doc, &mut diagnostics);
+    analyze_duplicate_statements(doc, &mut diagnostics);
+    analyze_unsatisfiable_conditions(doc, &mut diagnostics);
+    analyze_overlapping_limits(doc, &mut diagnostics);
+
+    diagnostics
+}
 
-    for stmt in &doc.statements {
+/// A `permit` is dead if some `deny` matches everything it matches (its
+/// action/resource patterns are a superset, per [`is_subset_pattern`])
+/// at equal or greater specificity -- the deny wins at a tie, so the
+/// permit can never actually grant access.
+fn analyze_dead_permits(doc: &CCLDocument, diagnostics: &mut Vec<Diagnostic>) {
+    for permit in &doc.permits {
+        let permit_spec = specificity(&permit.action, &permit.resource);
+        for deny in &doc.denies {
+            if is_subset_pattern(&permit.action, &deny.action, ".")
+                && is_subset_pattern(&permit.resource, &deny.resource, "/")
+                && specificity(&deny.action, &deny.resource) >= permit_spec
+            {
+                diagnostics.push(Diagnostic {
+                    message: format!(
+                        "permit {} on '{}' is dead: shadowed by deny {} on '{}'",
+                        permit.action, permit.resource, deny.action, deny.resource
+                    ),
+                    span: permit.span,
+                    severity: Severity::Warning,
+                });
+                break;
+            }
+        }
+    }
+}
+
+/// Flag statements that are exact duplicates of an earlier statement in
+/// the document (same type, action, resource, condition, and limit
+/// fields -- everything but source span).
+fn analyze_duplicate_statements(doc: &CCLDocument, diagnostics: &mut Vec<Diagnostic>) {
+    for (i, stmt) in doc.statements.iter().enumerate() {
+        for earlier in &doc.statements[..i] {
+            if statements_equal_ignoring_span(stmt, earlier) {
+                diagnostics.push(Diagnostic {
+                    message: format!(
+                        "duplicate of the statement at line {}",
+                        earlier.span.line
+                    ),
+                    span: stmt.span,
+                    severity: Severity::Warning,
+                });
+                break;
+            }
+        }
+    }
+}
+
+fn statements_equal_ignoring_span(a: &Statement, b: &Statement) -> bool {
+    a.stmt_type == b.stmt_type
+        && a.action == b.action
+        && a.resource == b.resource
+        && a.condition == b.condition
+        && a.metric == b.metric
+        && a.limit == b.limit
+        && a.period == b.period
+        && a.time_unit == b.time_unit
+}
+
+/// Flag `when` conditions that can never be true, by flattening the
+/// top-level `and` conjuncts of each statement's condition (stopping at
+/// any `or`/`not`, which break the "all must hold" guarantee) and
+/// pairwise-checking same-field comparisons for a contradiction.
+fn analyze_unsatisfiable_conditions(doc: &CCLDocument, diagnostics: &mut Vec<Diagnostic>) {
+    for stmt in &doc.statements {
+        let expr = match stmt.condition {
+            Some(ref expr) => expr,
+            None => continue,
+        };
+        let mut conjuncts = Vec::new();
+        collect_and_conjuncts(expr, &mut conjuncts);
+
+        for i in 0..conjuncts.len() {
+            for j in (i + 1)..conjuncts.len() {
+                if let Some(reason) = conjunction_conflict(conjuncts[i], conjuncts[j]) {
+                    diagnostics.push(Diagnostic {
+                        message: format!("condition can never be satisfied: {}", reason),
+                        span: stmt.span,
+                        severity: Severity::Error,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Collect every `Compare` leaf reachable from `expr` without crossing
+/// an `or`/`not` boundary -- these are exactly the comparisons that
+/// must *all* hold for the condition to be true.
+fn collect_and_conjuncts<'a>(expr: &'a ConditionExpr, out: &mut Vec<&'a Condition>) {
+    match expr {
+        ConditionExpr::Compare(cond) => out.push(cond),
+        ConditionExpr::And(lhs, rhs) => {
+            collect_and_conjuncts(lhs, out);
+            collect_and_conjuncts(rhs, out);
+        }
+        ConditionExpr::Or(_, _) | ConditionExpr::Not(_) => {}
+    }
+}
+
+/// Check whether two same-field comparisons required to hold
+/// simultaneously are mutually exclusive, e.g. `x = 'a'` with `x =
+/// 'b'`, `x = 'a'` with `x != 'a'`, or a numeric range that's empty
+/// (`n > 5` with `n < 3`).
+fn conjunction_conflict(a: &Condition, b: &Condition) -> Option<String> {
+    if a.field != b.field {
+        return None;
+    }
+
+    if a.operator == "=" && b.operator == "=" && a.value != b.value {
+        return Some(format!(
+            "'{}' cannot equal both '{}' and '{}'",
+            a.field, a.value, b.value
+        ));
+    }
+    if (a.operator == "=" && b.operator == "!=" || a.operator == "!=" && b.operator == "=")
+        && a.value == b.value
+    {
+        return Some(format!(
+            "'{}' cannot both equal and not equal '{}'",
+            a.field, a.value
+        ));
+    }
+
+    if let (Ok(av), Ok(bv)) = (a.value.parse::<f64>(), b.value.parse::<f64>()) {
+        if let Some(reason) = numeric_range_conflict(&a.field, a.operator.as_str(), av, b.operator.as_str(), bv)
+        {
+            return Some(reason);
+        }
+        if let Some(reason) = numeric_range_conflict(&b.field, b.operator.as_str(), bv, a.operator.as_str(), av)
+        {
+            return Some(reason);
+        }
+    }
+
+    None
+}
+
+/// Treat `(lower_op, lower_val)` as a lower bound and `(upper_op,
+/// upper_val)` as an upper bound on the same field, and check whether
+/// the resulting range is empty.
+fn numeric_range_conflict(
+    field: &str,
+    lower_op: &str,
+    lower_val: f64,
+    upper_op: &str,
+    upper_val: f64,
+) -> Option<String> {
+    let lower = match lower_op {
+        ">" => Some((lower_val, false)),
+        ">=" => Some((lower_val, true)),
+        _ => None,
+    }?;
+    let upper = match upper_op {
+        "<" => Some((upper_val, false)),
+        "<=" => Some((upper_val, true)),
+        _ => None,
+    }?;
+
+    let (lo, lo_inclusive) = lower;
+    let (hi, hi_inclusive) = upper;
+    let empty = if lo_inclusive && hi_inclusive {
+        lo > hi
+    } else {
+        lo >= hi
+    };
+
+    if empty {
+        Some(format!(
+            "'{}' cannot be both {} {} and {} {}",
+            field, lower_op, lower_val, upper_op, upper_val
+        ))
+    } else {
+        None
+    }
+}
+
+/// Flag `limit` statements whose action patterns overlap (so the same
+/// metric event could match both) but whose windows disagree, since
+/// it's ambiguous which one governs.
+fn analyze_overlapping_limits(doc: &CCLDocument, diagnostics: &mut Vec<Diagnostic>) {
+    for (i, a) in doc.limits.iter().enumerate() {
+        for b in &doc.limits[..i] {
+            if !patterns_overlap(&a.action, &b.action) {
+                continue;
+            }
+            if a.period == b.period && a.time_unit == b.time_unit {
+                continue;
+            }
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "limit {} overlaps the limit at line {} with a different window",
+                    a.action, b.span.line
+                ),
+                span: a.span,
+                severity: Severity::Warning,
+            });
+        }
+    }
+}
+
+/// Serialize a CCL document back to human-readable CCL source text.
+pub fn serialize(doc: &CCLDocument) -> String {
+    let mut lines = Vec::new();
+
+    for stmt in &doc.statements {
         lines.push(serialize_statement(stmt));
     }
 
@@ -1442,23 +2571,23 @@ pub fn serialize(doc: &CCLDocument) -> String {
 fn serialize_statement(stmt: &Statement) -> String {
     match stmt.stmt_type {
         StatementType::Permit => {
-            let mut line = format!("permit {} on '{}'", stmt.action, stmt.resource);
+            let mut line = format!("permit {} on '{}'", stmt.action, escape_ccl_string(&stmt.resource));
             if let Some(ref cond) = stmt.condition {
-                line.push_str(&format!(" when {} {} '{}'", cond.field, cond.operator, cond.value));
+                line.push_str(&format!(" when {}", serialize_condition_expr(cond)));
             }
             line
         }
         StatementType::Deny => {
-            let mut line = format!("deny {} on '{}'", stmt.action, stmt.resource);
+            let mut line = format!("deny {} on '{}'", stmt.action, escape_ccl_string(&stmt.resource));
             if let Some(ref cond) = stmt.condition {
-                line.push_str(&format!(" when {} {} '{}'", cond.field, cond.operator, cond.value));
+                line.push_str(&format!(" when {}", serialize_condition_expr(cond)));
             }
             line
         }
         StatementType::Require => {
-            let mut line = format!("require {} on '{}'", stmt.action, stmt.resource);
+            let mut line = format!("require {} on '{}'", stmt.action, escape_ccl_string(&stmt.resource));
             if let Some(ref cond) = stmt.condition {
-                line.push_str(&format!(" when {} {} '{}'", cond.field, cond.operator, cond.value));
+                line.push_str(&format!(" when {}", serialize_condition_expr(cond)));
             }
             line
         }
@@ -1471,6 +2600,66 @@ fn serialize_statement(stmt: &Statement) -> String {
     }
 }
 
+/// Escape a decoded string literal's content back into CCL's
+/// single-quoted escape syntax, so re-parsing it reproduces the same
+/// value byte-for-byte (in particular, a literal newline or backslash
+/// must never reach the output unescaped -- either would corrupt the
+/// surrounding single-line statement or be misread as a new escape).
+fn escape_ccl_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            '\\' => out.push_str("\\\\"),
+            '\'' => out.push_str("\\'"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Serialize a `when` expression tree back to CCL source, adding
+/// parentheses only where needed to preserve precedence (`or` < `and` <
+/// `not` < comparison) on re-parse.
+fn serialize_condition_expr(expr: &ConditionExpr) -> String {
+    serialize_condition_expr_prec(expr, 0)
+}
+
+fn serialize_condition_expr_prec(expr: &ConditionExpr, min_prec: u8) -> String {
+    let (text, prec) = match expr {
+        ConditionExpr::Compare(cond) => (
+            format!("{} {} '{}'", cond.field, cond.operator, escape_ccl_string(&cond.value)),
+            3,
+        ),
+        ConditionExpr::Not(inner) => {
+            (format!("not {}", serialize_condition_expr_prec(inner, 2)), 2)
+        }
+        ConditionExpr::And(lhs, rhs) => (
+            format!(
+                "{} and {}",
+                serialize_condition_expr_prec(lhs, 1),
+                serialize_condition_expr_prec(rhs, 2)
+            ),
+            1,
+        ),
+        ConditionExpr::Or(lhs, rhs) => (
+            format!(
+                "{} or {}",
+                serialize_condition_expr_prec(lhs, 0),
+                serialize_condition_expr_prec(rhs, 0)
+            ),
+            0,
+        ),
+    };
+    if prec < min_prec {
+        format!("({})", text)
+    } else {
+        text
+    }
+}
+
 fn best_time_unit(seconds: f64) -> (i64, &'static str) {
     let s = seconds as i64;
     if s > 0 && s % 86400 == 0 {
@@ -1526,6 +2715,55 @@ mod tests {
         assert!(match_resource("/data/*", "/data/users"));
     }
 
+    #[test]
+    fn test_match_action_brace_alternation() {
+        assert!(match_action("{read,list}", "read"));
+        assert!(match_action("{read,list}", "list"));
+        assert!(!match_action("{read,list}", "write"));
+    }
+
+    #[test]
+    fn test_match_resource_brace_alternation() {
+        assert!(match_resource("/data/{users,teams}/*", "/data/users/123"));
+        assert!(match_resource("/data/{users,teams}/*", "/data/teams/42"));
+        assert!(!match_resource("/data/{users,teams}/*", "/data/audit/1"));
+    }
+
+    #[test]
+    fn test_match_single_char_wildcard() {
+        assert!(match_action("fil?", "file"));
+        assert!(!match_action("fil?", "files"));
+        assert!(match_resource("/data/item?", "/data/item1"));
+    }
+
+    #[test]
+    fn test_brace_alternatives_may_contain_wildcards() {
+        assert!(match_action("{file*,dir?}", "filename"));
+        assert!(match_action("{file*,dir?}", "dirs"));
+        assert!(!match_action("{file*,dir?}", "other"));
+    }
+
+    #[test]
+    fn test_specificity_treats_brace_and_question_mark_as_literal() {
+        assert_eq!(specificity("{read,list}", ""), specificity("read", ""));
+        assert_eq!(specificity("fil?", ""), specificity("file", ""));
+    }
+
+    #[test]
+    fn test_patterns_overlap_brace_aware() {
+        assert!(patterns_overlap("{read,list}", "read"));
+        assert!(patterns_overlap("{read,list}", "{list,write}"));
+        assert!(!patterns_overlap("{read,list}", "write"));
+    }
+
+    #[test]
+    fn test_is_subset_pattern_brace_aware() {
+        assert!(is_subset_pattern("{read,list}", "{read,list,write}", "."));
+        assert!(!is_subset_pattern("{read,list,delete}", "{read,list,write}", "."));
+        assert!(is_subset_pattern("read", "{read,list}", "."));
+        assert!(!is_subset_pattern("write", "{read,list}", "."));
+    }
+
     #[test]
     fn test_evaluate_default_deny() {
         let doc = parse("permit read on '/allowed'").unwrap();
@@ -1558,4 +2796,440 @@ mod tests {
         assert!(serialized.contains("permit"));
         assert!(serialized.contains("read"));
     }
+
+    #[test]
+    fn test_parse_compound_condition_and_or_not() {
+        let doc = parse(
+            "permit read on 'docs' when dept = 'eng' and (level >= 3 or owner = true) and not archived = true",
+        )
+        .unwrap();
+        assert_eq!(doc.permits.len(), 1);
+        match doc.permits[0].condition {
+            Some(ConditionExpr::And(..)) => {}
+            ref other => panic!("expected top-level And, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_evaluate_and_short_circuits() {
+        let doc = parse("permit read on 'docs' when dept = 'eng' and level >= 3").unwrap();
+        let mut ctx = HashMap::new();
+        ctx.insert("dept".to_string(), "eng".to_string());
+        ctx.insert("level".to_string(), "1".to_string());
+        assert!(!evaluate(&doc, "read", "docs", &ctx).permitted);
+
+        ctx.insert("level".to_string(), "5".to_string());
+        assert!(evaluate(&doc, "read", "docs", &ctx).permitted);
+    }
+
+    #[test]
+    fn test_evaluate_or_grouping() {
+        let doc =
+            parse("permit read on 'docs' when dept = 'eng' and (level >= 3 or owner = true)")
+                .unwrap();
+        let mut ctx = HashMap::new();
+        ctx.insert("dept".to_string(), "eng".to_string());
+        ctx.insert("level".to_string(), "1".to_string());
+        ctx.insert("owner".to_string(), "true".to_string());
+        assert!(evaluate(&doc, "read", "docs", &ctx).permitted);
+    }
+
+    #[test]
+    fn test_evaluate_not() {
+        let doc = parse("permit read on 'docs' when not archived = true").unwrap();
+        let mut ctx = HashMap::new();
+        ctx.insert("archived".to_string(), "false".to_string());
+        assert!(evaluate(&doc, "read", "docs", &ctx).permitted);
+
+        ctx.insert("archived".to_string(), "true".to_string());
+        assert!(!evaluate(&doc, "read", "docs", &ctx).permitted);
+    }
+
+    #[test]
+    fn test_serialize_compound_condition_roundtrip() {
+        let source =
+            "permit read on 'docs' when dept = 'eng' and (level >= 3 or owner = true) and not archived = true";
+        let doc = parse(source).unwrap();
+        let serialized = serialize(&doc);
+        let reparsed = parse(&serialized).unwrap();
+        assert_eq!(doc.permits[0].condition, reparsed.permits[0].condition);
+    }
+
+    #[test]
+    fn test_condition_expr_precedence_without_parens() {
+        // `not` binds tighter than `and`, which binds tighter than `or`,
+        // so this parses as `(dept = 'eng') or ((not archived = true) and owner = true)`.
+        let doc = parse(
+            "permit read on 'docs' when dept = 'eng' or not archived = true and owner = true",
+        )
+        .unwrap();
+        match doc.permits[0].condition {
+            Some(ConditionExpr::Or(ref lhs, ref rhs)) => {
+                assert!(matches!(**lhs, ConditionExpr::Compare(_)));
+                match **rhs {
+                    ConditionExpr::And(ref and_lhs, _) => {
+                        assert!(matches!(**and_lhs, ConditionExpr::Not(_)));
+                    }
+                    ref other => panic!("expected And on the right of Or, got {:?}", other),
+                }
+            }
+            ref other => panic!("expected top-level Or, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics_reports_every_error() {
+        let source = "permit read on\ndeny write on '/secret'\nrequire\npermit list on '/ok'";
+        let (doc, diagnostics) = parse_with_diagnostics(source).unwrap();
+
+        assert_eq!(diagnostics.len(), 2, "diagnostics: {:?}", diagnostics);
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+
+        assert_eq!(doc.statements.len(), 2);
+        assert_eq!(doc.denies.len(), 1);
+        assert_eq!(doc.permits.len(), 1);
+        assert_eq!(doc.permits[0].resource, "/ok");
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics_clean_source_has_no_diagnostics() {
+        let (doc, diagnostics) = parse_with_diagnostics("permit read on '/data/**'").unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(doc.permits.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_still_fails_fast_on_first_error() {
+        let result = parse("permit read on");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_string_literal_escape_sequences() {
+        let doc = parse(r"permit read on 'line1\nline2\ttabbed\\backslash\'quote'").unwrap();
+        assert_eq!(
+            doc.permits[0].resource,
+            "line1\nline2\ttabbed\\backslash'quote"
+        );
+    }
+
+    #[test]
+    fn test_string_literal_unicode_escape() {
+        let doc = parse("permit read on 'A\\u0042C'").unwrap();
+        assert_eq!(doc.permits[0].resource, "ABC");
+    }
+
+    #[test]
+    fn test_string_literal_invalid_unicode_escape_is_an_error() {
+        let result = parse(r"permit read on '\uZZZZ'");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_string_literal_unknown_escape_is_an_error() {
+        let result = parse(r"permit read on '\q'");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_string_literal_unterminated_is_an_error() {
+        let result = parse("permit read on 'unterminated");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_string_literal_escape_roundtrip() {
+        let source = r"permit read on 'has\nnewline and \'quote\''";
+        let doc = parse(source).unwrap();
+        let serialized = serialize(&doc);
+        let reparsed = parse(&serialized).unwrap();
+        assert_eq!(doc.permits[0].resource, reparsed.permits[0].resource);
+    }
+
+    #[test]
+    fn test_parse_limit_decimal_count() {
+        let doc = parse("limit writes 2.5 per 1 hours").unwrap();
+        assert_eq!(doc.limits[0].limit, Some(2.5));
+    }
+
+    #[test]
+    fn test_parse_limit_scientific_notation() {
+        let doc = parse("limit calls 1e3 per 1 days").unwrap();
+        assert_eq!(doc.limits[0].limit, Some(1000.0));
+    }
+
+    #[test]
+    fn test_parse_limit_negative_exponent() {
+        let doc = parse("limit calls 1.5e-1 per 1 days").unwrap();
+        assert_eq!(doc.limits[0].limit, Some(0.15));
+    }
+
+    #[test]
+    fn test_parse_number_malformed_second_decimal_point() {
+        let result = parse("limit calls 1.2.3 per 1 days");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_number_malformed_dangling_exponent() {
+        let result = parse("limit calls 1e per 1 days");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_action_dot_access_still_lexes_as_dot() {
+        let doc = parse("permit file.read on '/data/**'").unwrap();
+        assert_eq!(doc.permits[0].action, "file.read");
+    }
+
+    #[test]
+    fn test_analyze_flags_dead_permit_shadowed_by_deny() {
+        let doc = parse("permit read on '/data/**'\ndeny read on '/data/**'").unwrap();
+        let diagnostics = analyze(&doc);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Warning && d.message.contains("is dead")));
+    }
+
+    #[test]
+    fn test_analyze_ignores_permit_not_fully_shadowed() {
+        let doc = parse("permit read on '/data/**'\ndeny read on '/data/secret'").unwrap();
+        let diagnostics = analyze(&doc);
+        assert!(!diagnostics.iter().any(|d| d.message.contains("is dead")));
+    }
+
+    #[test]
+    fn test_analyze_flags_exact_duplicate_statements() {
+        let doc = parse("permit read on '/data/**'\npermit read on '/data/**'").unwrap();
+        let diagnostics = analyze(&doc);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Warning && d.message.contains("duplicate")));
+    }
+
+    #[test]
+    fn test_analyze_flags_unsatisfiable_equality_condition() {
+        let doc = parse("permit read on '/data' when dept = 'eng' and dept = 'sales'").unwrap();
+        let diagnostics = analyze(&doc);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error && d.message.contains("can never be satisfied")));
+    }
+
+    #[test]
+    fn test_analyze_flags_unsatisfiable_numeric_range() {
+        let doc = parse("permit read on '/data' when n > 5 and n < 3").unwrap();
+        let diagnostics = analyze(&doc);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error && d.message.contains("can never be satisfied")));
+    }
+
+    #[test]
+    fn test_analyze_ignores_satisfiable_or_condition() {
+        let doc = parse("permit read on '/data' when dept = 'eng' or dept = 'sales'").unwrap();
+        let diagnostics = analyze(&doc);
+        assert!(!diagnostics.iter().any(|d| d.severity == Severity::Error));
+    }
+
+    #[test]
+    fn test_analyze_flags_overlapping_limits_with_different_windows() {
+        let doc = parse("limit api.call 100 per 1 hours\nlimit api.call 50 per 1 days").unwrap();
+        let diagnostics = analyze(&doc);
+        assert!(diagnostics.iter().any(|d| d.message.contains("overlaps")));
+    }
+
+    #[test]
+    fn test_analyze_ignores_identical_limit_windows() {
+        let doc = parse("limit api.call 100 per 1 hours\nlimit api.call 50 per 1 hours").unwrap();
+        let diagnostics = analyze(&doc);
+        assert!(!diagnostics.iter().any(|d| d.message.contains("overlaps")));
+    }
+
+    #[test]
+    fn test_analyze_clean_document_has_no_diagnostics() {
+        let doc = parse("permit read on '/data/**'\ndeny write on '/secret'").unwrap();
+        assert!(analyze(&doc).is_empty());
+    }
+
+    #[test]
+    fn test_glob_matches_star() {
+        assert!(glob_matches("/tmp/*.log", "/tmp/app.log"));
+        assert!(!glob_matches("/tmp/*.log", "/tmp/app.txt"));
+        assert!(glob_matches("*", "anything"));
+        assert!(glob_matches("*", ""));
+    }
+
+    #[test]
+    fn test_glob_matches_question_mark() {
+        assert!(glob_matches("file?.txt", "file1.txt"));
+        assert!(!glob_matches("file?.txt", "file12.txt"));
+    }
+
+    #[test]
+    fn test_glob_matches_character_class() {
+        assert!(glob_matches("file[0-9].txt", "file5.txt"));
+        assert!(!glob_matches("file[0-9].txt", "filea.txt"));
+        assert!(glob_matches("file[!0-9].txt", "filea.txt"));
+        assert!(!glob_matches("file[!0-9].txt", "file5.txt"));
+    }
+
+    #[test]
+    fn test_glob_matches_escaped_char() {
+        assert!(glob_matches(r"file\*.txt", "file*.txt"));
+        assert!(!glob_matches(r"file\*.txt", "fileX.txt"));
+    }
+
+    #[test]
+    fn test_glob_matches_empty_pattern() {
+        assert!(glob_matches("", ""));
+        assert!(!glob_matches("", "x"));
+    }
+
+    #[test]
+    fn test_glob_matches_unterminated_class_is_literal() {
+        assert!(glob_matches("a[bc", "a[bc"));
+        assert!(!glob_matches("a[bc", "abc"));
+    }
+
+    #[test]
+    fn test_evaluate_condition_matches_operator_uses_glob() {
+        let mut ctx = HashMap::new();
+        ctx.insert("path".to_string(), "/tmp/app.log".to_string());
+        let doc = parse("permit read on '/x' when path matches '/tmp/*.log'").unwrap();
+        let result = evaluate(&doc, "read", "/x", &ctx);
+        assert!(result.permitted);
+    }
+
+    #[test]
+    fn test_compiled_policy_matches_evaluate_default_deny() {
+        let doc = parse("permit read on '/allowed'").unwrap();
+        let compiled = CompiledPolicy::compile(&doc);
+        let ctx = HashMap::new();
+        assert_eq!(
+            compiled.evaluate("write", "/allowed", &ctx).permitted,
+            evaluate(&doc, "write", "/allowed", &ctx).permitted
+        );
+    }
+
+    #[test]
+    fn test_compiled_policy_matches_evaluate_permit() {
+        let doc = parse("permit read on '/data/**'").unwrap();
+        let compiled = CompiledPolicy::compile(&doc);
+        let ctx = HashMap::new();
+        let result = compiled.evaluate("read", "/data/users", &ctx);
+        assert!(result.permitted);
+    }
+
+    #[test]
+    fn test_compiled_policy_matches_evaluate_deny_wins() {
+        let doc = parse("permit read on '/data/**'\ndeny read on '/data/secret'").unwrap();
+        let compiled = CompiledPolicy::compile(&doc);
+        let ctx = HashMap::new();
+        let result = compiled.evaluate("read", "/data/secret", &ctx);
+        assert!(!result.permitted);
+        assert_eq!(result.matched_rule.unwrap().stmt_type, StatementType::Deny);
+    }
+
+    #[test]
+    fn test_compiled_policy_respects_specificity() {
+        let doc = parse("permit read on '/data/**'\ndeny read on '/data/public/**'\npermit read on '/data/public/index'").unwrap();
+        let compiled = CompiledPolicy::compile(&doc);
+        let ctx = HashMap::new();
+        let result = compiled.evaluate("read", "/data/public/index", &ctx);
+        assert!(result.permitted);
+    }
+
+    #[test]
+    fn test_compiled_policy_honors_condition() {
+        let doc = parse("permit read on '/data' when role = 'admin'").unwrap();
+        let compiled = CompiledPolicy::compile(&doc);
+        let mut ctx = HashMap::new();
+        ctx.insert("role".to_string(), "guest".to_string());
+        assert!(!compiled.evaluate("read", "/data", &ctx).permitted);
+        ctx.insert("role".to_string(), "admin".to_string());
+        assert!(compiled.evaluate("read", "/data", &ctx).permitted);
+    }
+
+    #[test]
+    fn test_compiled_policy_wildcard_action() {
+        let doc = parse("permit file.* on '/data'\ndeny file.delete on '/data'").unwrap();
+        let compiled = CompiledPolicy::compile(&doc);
+        let ctx = HashMap::new();
+        assert!(compiled.evaluate("file.read", "/data", &ctx).permitted);
+        assert!(!compiled.evaluate("file.delete", "/data", &ctx).permitted);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_enforce_rate_limit_tracks_usage_across_calls() {
+        use crate::store::MemoryStore;
+
+        let doc = parse("limit api.call 2 per 1 hours").unwrap();
+        let mut store = MemoryStore::new();
+
+        let first = enforce_rate_limit(&doc, "api.call", "covenant-1", 0, &mut store).unwrap();
+        assert!(!first.exceeded);
+        assert_eq!(first.remaining, 1);
+
+        let second = enforce_rate_limit(&doc, "api.call", "covenant-1", 1_000, &mut store).unwrap();
+        assert!(!second.exceeded);
+        assert_eq!(second.remaining, 0);
+
+        let third = enforce_rate_limit(&doc, "api.call", "covenant-1", 2_000, &mut store).unwrap();
+        assert!(third.exceeded);
+        assert_eq!(third.remaining, 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_enforce_rate_limit_window_rollover() {
+        use crate::store::MemoryStore;
+
+        let doc = parse("limit api.call 1 per 1 hours").unwrap();
+        let mut store = MemoryStore::new();
+        let hour_ms = 3_600_000;
+
+        let first = enforce_rate_limit(&doc, "api.call", "covenant-1", 0, &mut store).unwrap();
+        assert!(!first.exceeded);
+
+        let still_in_window = enforce_rate_limit(&doc, "api.call", "covenant-1", hour_ms - 1, &mut store).unwrap();
+        assert!(still_in_window.exceeded);
+
+        // Both earlier usages (at 0 and hour_ms - 1) are older than one hour here.
+        let after_rollover = enforce_rate_limit(&doc, "api.call", "covenant-1", 2 * hour_ms, &mut store).unwrap();
+        assert!(!after_rollover.exceeded, "usage from the expired window should have been pruned");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_enforce_rate_limit_distinct_actions_share_covenant() {
+        use crate::store::MemoryStore;
+
+        let doc = parse("limit api.call 1 per 1 hours\nlimit api.upload 1 per 1 hours").unwrap();
+        let mut store = MemoryStore::new();
+
+        let call = enforce_rate_limit(&doc, "api.call", "covenant-1", 0, &mut store).unwrap();
+        assert!(!call.exceeded);
+
+        // A separate action for the same covenant has its own independent counter.
+        let upload = enforce_rate_limit(&doc, "api.upload", "covenant-1", 0, &mut store).unwrap();
+        assert!(!upload.exceeded);
+
+        let second_call = enforce_rate_limit(&doc, "api.call", "covenant-1", 1, &mut store).unwrap();
+        assert!(second_call.exceeded);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_enforce_rate_limit_no_matching_limit() {
+        use crate::store::MemoryStore;
+
+        let doc = parse("permit read on '/data'").unwrap();
+        let mut store = MemoryStore::new();
+
+        let result = enforce_rate_limit(&doc, "api.call", "covenant-1", 0, &mut store).unwrap();
+        assert!(!result.exceeded);
+        assert_eq!(result.limit, 0);
+    }
 }
@@ -0,0 +1,229 @@
+//! Visitor/walker API over the parsed CCL AST.
+//!
+//! The `ccl` module otherwise only exposes evaluation, so tooling that
+//! wants to lint, analyze, or transform constraints has to re-implement
+//! tree traversal from scratch. This module exposes that traversal as a
+//! pair of visitor traits -- `ASTVisitor` (mutable) and `ASTConstVisitor`
+//! (read-only) -- with pre-order and post-order hooks per node kind, so
+//! callers can build collectors and checkers in one pass, the way a
+//! Solidity AST visitor collects function definitions.
+
+use super::{CCLDocument, Condition, ConditionExpr, Statement};
+use crate::SteleError;
+
+/// A mutable, fallible visitor over the CCL AST.
+///
+/// Every hook has a default no-op implementation, so implementors only
+/// override the node kinds they care about. Returning `Err` from any
+/// hook short-circuits the remainder of the traversal.
+pub trait ASTVisitor {
+    fn visit_document(&mut self, _doc: &CCLDocument) -> Result<(), SteleError> {
+        Ok(())
+    }
+    fn end_visit_document(&mut self, _doc: &CCLDocument) -> Result<(), SteleError> {
+        Ok(())
+    }
+
+    fn visit_rule(&mut self, _stmt: &Statement) -> Result<(), SteleError> {
+        Ok(())
+    }
+    fn end_visit_rule(&mut self, _stmt: &Statement) -> Result<(), SteleError> {
+        Ok(())
+    }
+
+    fn visit_condition(&mut self, _cond: &Condition) -> Result<(), SteleError> {
+        Ok(())
+    }
+    fn end_visit_condition(&mut self, _cond: &Condition) -> Result<(), SteleError> {
+        Ok(())
+    }
+
+    fn visit_literal(&mut self, _value: &str) -> Result<(), SteleError> {
+        Ok(())
+    }
+    fn end_visit_literal(&mut self, _value: &str) -> Result<(), SteleError> {
+        Ok(())
+    }
+}
+
+/// A read-only, fallible visitor over the CCL AST.
+///
+/// Mirrors `ASTVisitor` but takes `&self`, for visitors that only need
+/// to observe the tree (e.g. collecting referenced field names).
+pub trait ASTConstVisitor {
+    fn visit_document(&self, _doc: &CCLDocument) -> Result<(), SteleError> {
+        Ok(())
+    }
+    fn end_visit_document(&self, _doc: &CCLDocument) -> Result<(), SteleError> {
+        Ok(())
+    }
+
+    fn visit_rule(&self, _stmt: &Statement) -> Result<(), SteleError> {
+        Ok(())
+    }
+    fn end_visit_rule(&self, _stmt: &Statement) -> Result<(), SteleError> {
+        Ok(())
+    }
+
+    fn visit_condition(&self, _cond: &Condition) -> Result<(), SteleError> {
+        Ok(())
+    }
+    fn end_visit_condition(&self, _cond: &Condition) -> Result<(), SteleError> {
+        Ok(())
+    }
+
+    fn visit_literal(&self, _value: &str) -> Result<(), SteleError> {
+        Ok(())
+    }
+    fn end_visit_literal(&self, _value: &str) -> Result<(), SteleError> {
+        Ok(())
+    }
+}
+
+impl CCLDocument {
+    /// Dispatch a mutable visitor over every statement in the document.
+    pub fn accept<V: ASTVisitor>(&self, visitor: &mut V) -> Result<(), SteleError> {
+        visitor.visit_document(self)?;
+        for stmt in &self.statements {
+            stmt.accept(visitor)?;
+        }
+        visitor.end_visit_document(self)
+    }
+
+    /// Dispatch a read-only visitor over every statement in the document.
+    pub fn accept_const<V: ASTConstVisitor>(&self, visitor: &V) -> Result<(), SteleError> {
+        visitor.visit_document(self)?;
+        for stmt in &self.statements {
+            stmt.accept_const(visitor)?;
+        }
+        visitor.end_visit_document(self)
+    }
+}
+
+impl Statement {
+    /// Dispatch a mutable visitor over this statement and its condition.
+    pub fn accept<V: ASTVisitor>(&self, visitor: &mut V) -> Result<(), SteleError> {
+        visitor.visit_rule(self)?;
+        if let Some(ref cond) = self.condition {
+            cond.accept(visitor)?;
+        }
+        visitor.end_visit_rule(self)
+    }
+
+    /// Dispatch a read-only visitor over this statement and its condition.
+    pub fn accept_const<V: ASTConstVisitor>(&self, visitor: &V) -> Result<(), SteleError> {
+        visitor.visit_rule(self)?;
+        if let Some(ref cond) = self.condition {
+            cond.accept_const(visitor)?;
+        }
+        visitor.end_visit_rule(self)
+    }
+}
+
+impl Condition {
+    /// Dispatch a mutable visitor over this condition's field/value leaves.
+    pub fn accept<V: ASTVisitor>(&self, visitor: &mut V) -> Result<(), SteleError> {
+        visitor.visit_condition(self)?;
+        visitor.visit_literal(&self.value)?;
+        visitor.end_visit_literal(&self.value)?;
+        visitor.end_visit_condition(self)
+    }
+
+    /// Dispatch a read-only visitor over this condition's field/value leaves.
+    pub fn accept_const<V: ASTConstVisitor>(&self, visitor: &V) -> Result<(), SteleError> {
+        visitor.visit_condition(self)?;
+        visitor.visit_literal(&self.value)?;
+        visitor.end_visit_literal(&self.value)?;
+        visitor.end_visit_condition(self)
+    }
+}
+
+impl ConditionExpr {
+    /// Dispatch a mutable visitor over every comparison leaf in this
+    /// boolean expression tree, recursing through `and`/`or`/`not` nodes.
+    pub fn accept<V: ASTVisitor>(&self, visitor: &mut V) -> Result<(), SteleError> {
+        match self {
+            ConditionExpr::Compare(cond) => cond.accept(visitor),
+            ConditionExpr::And(lhs, rhs) | ConditionExpr::Or(lhs, rhs) => {
+                lhs.accept(visitor)?;
+                rhs.accept(visitor)
+            }
+            ConditionExpr::Not(inner) => inner.accept(visitor),
+        }
+    }
+
+    /// Dispatch a read-only visitor over every comparison leaf in this
+    /// boolean expression tree, recursing through `and`/`or`/`not` nodes.
+    pub fn accept_const<V: ASTConstVisitor>(&self, visitor: &V) -> Result<(), SteleError> {
+        match self {
+            ConditionExpr::Compare(cond) => cond.accept_const(visitor),
+            ConditionExpr::And(lhs, rhs) | ConditionExpr::Or(lhs, rhs) => {
+                lhs.accept_const(visitor)?;
+                rhs.accept_const(visitor)
+            }
+            ConditionExpr::Not(inner) => inner.accept_const(visitor),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ccl::parse;
+
+    #[derive(Default)]
+    struct FieldCollector {
+        fields: Vec<String>,
+    }
+
+    impl ASTVisitor for FieldCollector {
+        fn visit_condition(&mut self, cond: &Condition) -> Result<(), SteleError> {
+            self.fields.push(cond.field.clone());
+            Ok(())
+        }
+    }
+
+    struct RuleCounter {
+        count: std::cell::Cell<usize>,
+    }
+
+    impl ASTConstVisitor for RuleCounter {
+        fn visit_rule(&self, _stmt: &Statement) -> Result<(), SteleError> {
+            self.count.set(self.count.get() + 1);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_collects_condition_fields() {
+        let doc = parse("permit read on '/data/**' when role = 'admin'").unwrap();
+        let mut collector = FieldCollector::default();
+        doc.accept(&mut collector).unwrap();
+        assert_eq!(collector.fields, vec!["role".to_string()]);
+    }
+
+    #[test]
+    fn test_const_visitor_counts_rules() {
+        let doc = parse("permit read on '/a'\ndeny write on '/b'").unwrap();
+        let counter = RuleCounter {
+            count: std::cell::Cell::new(0),
+        };
+        doc.accept_const(&counter).unwrap();
+        assert_eq!(counter.count.get(), 2);
+    }
+
+    struct ShortCircuit;
+
+    impl ASTVisitor for ShortCircuit {
+        fn visit_rule(&mut self, _stmt: &Statement) -> Result<(), SteleError> {
+            Err(SteleError::InvalidInput("stop".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_visitor_can_short_circuit() {
+        let doc = parse("permit read on '/a'\ndeny write on '/b'").unwrap();
+        let mut visitor = ShortCircuit;
+        assert!(doc.accept(&mut visitor).is_err());
+    }
+}
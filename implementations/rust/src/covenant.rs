@@ -4,12 +4,134 @@
 //! a beneficiary, encoding CCL constraints that govern agent behavior.
 //! Covenants can be chained (delegated) to form trust hierarchies where
 //! child covenants can only narrow (restrict) their parent's constraints.
+//!
+//! The issuer signature and each countersignature record the
+//! [`crypto::SignatureAlgorithm`] they were produced with, so an issuer
+//! can sign with Ed25519, ECDSA P-256, or RSA-2048 (see
+//! [`build_covenant_with_key`]) while a
+//! countersigner (e.g. an enterprise auditor with an HSM-backed key)
+//! independently chooses its own algorithm -- see
+//! [`countersign_covenant_ecdsa_p256`]/[`countersign_covenant_rsa2048`].
+//! Documents without an `alg` field (pre-dating this) deserialize as
+//! Ed25519, so existing `"1.0"` covenants still verify.
+//!
+//! [`jws::to_jws`]/[`jws::from_jws`] re-present a signed document as a
+//! JSON Web Signature, so it can travel through existing JOSE libraries
+//! and HTTP middleware instead of requiring a Nobulex-specific client.
+//! [`vc::to_verifiable_credential`]/[`vc::from_verifiable_credential`] and
+//! [`vc::to_jws_compact`]/[`vc::from_jws_compact`] go a step further for
+//! Ed25519-signed covenants, presenting one as a W3C Verifiable Credential
+//! or a standards-compliant compact JWT so it can flow through existing
+//! DID/VC verifier tooling with no Nobulex-specific parser at all.
+//! [`vc::to_jwt_vc`]/[`vc::from_jwt_vc`]/[`vc::verify_jwt_vc`] present the
+//! same credential in the standard JWT-encoded VC shape instead: the
+//! whole credential travels as a `vc` claim and the JWT's own signature
+//! (named by a `kid` header) covers `header.payload`, so it verifies
+//! without a document lookup -- unlike the compact form's detached
+//! signature.
+//!
+//! [`revocation::revoke`] lets an issuer disavow a covenant before its
+//! `expiresAt`, and [`revocation::verify_covenant_with_store`] checks a
+//! document and its whole delegation chain against a store for one --
+//! see [`revocation`] for why `expiresAt` alone isn't enough.
+//!
+//! [`revocation::RevocationRegistry`] aggregates many covenants' revocation
+//! status into one gossipable document; [`revocation::verify_covenant_with_registry`]
+//! checks against it instead of per-covenant certificates.
+//!
+//! [`cbor::serialize_covenant_cbor`]/[`cbor::deserialize_covenant_cbor`]
+//! and [`cbor::to_cose_sign1`]/[`cbor::from_cose_sign1`] offer a compact
+//! binary alternative to the JSON forms above, for constrained/enclave
+//! environments that speak CBOR/COSE rather than JSON.
+//!
+//! A `Party`'s `publicKey` is usually a raw hex verification key, but it
+//! may instead be a `did:key` DID, which [`verify_covenant`] resolves
+//! itself with no network access; [`did::verify_covenant_with_did_resolver`]
+//! additionally handles DID schemes (e.g. `did:web`) that need a
+//! [`did::DidResolver`] to look up the key. This lets issuers and
+//! countersigners rotate keys behind a stable identifier instead of
+//! baking one fixed key into every covenant they sign.
+//!
+//! [`keyring::verify_covenant_with_keyring`] takes this further: it
+//! treats `publicKey` as a key-id into a [`crypto::keyring::Keyring`],
+//! which indexes verification keys by their SPKI encoding and dispatches
+//! to whichever of Ed25519, ECDSA P-256, or RSA-PKCS1 that SPKI's own
+//! `AlgorithmIdentifier` names, instead of trusting the document's
+//! self-reported `alg`. This is how an organization mixes an HSM-backed
+//! RSA issuer with an Ed25519 beneficiary in the same covenant chain.
+//!
+//! Countersignatures are otherwise unbounded: anyone holding the
+//! document can append one, so a widely-gossiped covenant can be flooded
+//! with junk certifications. [`attestation::attest_countersignatures`]
+//! lets a principal party (issuer or beneficiary) sign a statement
+//! naming the digests of the countersignatures it actually accepts, and
+//! [`attestation::verify_covenant_with_attestation`] then counts and
+//! reports only those by default, with a `verify_all` escape hatch for
+//! auditors who need to see everything regardless.
+//!
+//! All of the above still require a verifier to already know an issuer's
+//! public key out of band. [`trust_root::TrustRoot`] is a signed,
+//! versioned alternative: it maps issuer IDs to their currently valid
+//! keys, and [`trust_root::verify_covenant_with_trust_root`] resolves the
+//! issuer's key from it instead of the document, failing if the root is
+//! expired or the key has been rotated out. [`trust_root::TrustRoot::update`]
+//! is how a fleet of verifiers rotates keys safely, requiring a threshold
+//! of the prior root's own keys to sign the replacement.
+//!
+//! A trust root says who signed; [`timestamp::timestamp_covenant`] says
+//! when, non-repudiably: it has a timestamp authority sign the document's
+//! digest together with a Unix time, storing the result as
+//! `timestampToken`. [`verify_covenant`] checks that signature (step 12,
+//! `timestamp`) and, once it verifies, evaluates `not_expired`/`active`
+//! against the attested time rather than the local system clock -- so a
+//! covenant's validity window can still be judged correctly on a machine
+//! whose clock can't be trusted.
 
 use crate::ccl;
 use crate::crypto;
-use crate::NobulexError;
+use crate::crypto::signer::Signer;
+use crate::telemetry;
+use crate::SteleError;
 use serde::{Deserialize, Serialize};
 
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+pub mod attestation;
+pub mod cbor;
+pub mod did;
+pub mod dispute;
+pub mod jws;
+// Depends on `crypto::keyring`, which is itself `std`-only.
+#[cfg(feature = "std")]
+pub mod keyring;
+pub mod revocation;
+pub mod timestamp;
+pub mod trust_root;
+pub mod vc;
+pub mod wasm;
+
+pub use attestation::{attest_countersignatures, verify_covenant_with_attestation, AttestedCountersignatures};
+pub use cbor::{
+    canonical_form_cbor, deserialize_covenant_cbor, from_cose_sign1, serialize_covenant_cbor, to_cose_sign1,
+};
+pub use did::{verify_covenant_with_did_resolver, DidKeyResolver, DidResolver, ResolvedKey};
+pub use jws::{from_jws, to_jws, JwsForm};
+#[cfg(feature = "std")]
+pub use keyring::verify_covenant_with_keyring;
+pub use vc::{
+    from_jws_compact, from_jwt_vc, from_verifiable_credential, to_jws_compact, to_jwt_vc, to_verifiable_credential,
+    verify_jwt_vc, JwtClaims,
+};
+pub use revocation::{revoke, RevocationBitmap, RevocationCertificate, RevocationReason, RevocationRegistry};
+#[cfg(feature = "std")]
+pub use revocation::{verify_covenant_with_registry, verify_covenant_with_store};
+pub use timestamp::{timestamp_covenant, TimestampToken};
+pub use trust_root::{verify_covenant_with_trust_root, RootSignature, TrustRoot};
+
 /// Current Nobulex Covenant protocol version.
 pub const PROTOCOL_VERSION: &str = "1.0";
 
@@ -53,6 +175,11 @@ pub struct Countersignature {
     pub signer_role: String,
     pub signature: String,
     pub timestamp: String,
+    /// Algorithm the countersignature was produced with. Absent on
+    /// documents written before this field existed, which defaults to
+    /// `Ed25519` so they remain verifiable.
+    #[serde(default)]
+    pub alg: crypto::SignatureAlgorithm,
 }
 
 /// A complete, signed Covenant document.
@@ -67,6 +194,13 @@ pub struct CovenantDocument {
     #[serde(rename = "createdAt")]
     pub created_at: String,
     pub signature: String,
+    /// Algorithm the issuer signature was produced with. Included in
+    /// the canonical form, so substituting a weaker algorithm for a
+    /// recorded signature is detected as tampering rather than quietly
+    /// accepted. Absent on documents written before this field
+    /// existed, which defaults to `Ed25519` so they remain verifiable.
+    #[serde(default)]
+    pub alg: crypto::SignatureAlgorithm,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chain: Option<ChainReference>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "expiresAt")]
@@ -79,6 +213,11 @@ pub struct CovenantDocument {
     pub countersignatures: Option<Vec<Countersignature>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
+    /// A signed attestation of the time this document existed at,
+    /// analogous to a Signed Certificate Timestamp. See
+    /// [`timestamp::timestamp_covenant`].
+    #[serde(skip_serializing_if = "Option::is_none", rename = "timestampToken")]
+    pub timestamp_token: Option<timestamp::TimestampToken>,
 }
 
 /// A single verification check and its result.
@@ -108,6 +247,31 @@ pub struct CovenantBuilderOptions {
     pub metadata: Option<serde_json::Value>,
 }
 
+/// The issuer key or signer backend for [`build_covenant_with_key`],
+/// covering every way of producing an issuer signature other than
+/// [`build_covenant`]'s in-memory Ed25519 `signing_key`.
+#[derive(Clone, Copy)]
+pub enum CovenantIssuerKey<'a> {
+    EcdsaP256(&'a p256::ecdsa::SigningKey),
+    Rsa2048(&'a rsa::RsaPrivateKey),
+    Signer(&'a dyn Signer),
+}
+
+/// Options for [`build_covenant_with_key`]. Identical to
+/// [`CovenantBuilderOptions`] except `signing_key` selects among the
+/// non-Ed25519 key types and signer backends via [`CovenantIssuerKey`]
+/// instead of being fixed to one.
+pub struct CovenantKeyedBuilderOptions<'a> {
+    pub issuer: Party,
+    pub beneficiary: Party,
+    pub constraints: String,
+    pub signing_key: CovenantIssuerKey<'a>,
+    pub chain: Option<ChainReference>,
+    pub expires_at: Option<String>,
+    pub activates_at: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
 // ---------------------------------------------------------------------------
 // Canonical form and ID computation
 // ---------------------------------------------------------------------------
@@ -116,27 +280,28 @@ pub struct CovenantBuilderOptions {
 ///
 /// Strips `id`, `signature`, and `countersignatures`, then produces
 /// deterministic JSON via JCS (sorted keys) canonicalization.
-pub fn canonical_form(doc: &CovenantDocument) -> Result<String, NobulexError> {
+pub fn canonical_form(doc: &CovenantDocument) -> Result<String, SteleError> {
     // Build a JSON value, then remove the mutable fields
     let val = serde_json::to_value(doc)
-        .map_err(|e| NobulexError::SerializationError(format!("Failed to convert to JSON value: {}", e)))?;
+        .map_err(|e| SteleError::SerializationError(format!("Failed to convert to JSON value: {}", e)))?;
 
     let mut obj = match val {
         serde_json::Value::Object(m) => m,
-        _ => return Err(NobulexError::SerializationError("Expected object".to_string())),
+        _ => return Err(SteleError::SerializationError("Expected object".to_string())),
     };
 
     // Remove fields that are not part of the canonical form
     obj.remove("id");
     obj.remove("signature");
     obj.remove("countersignatures");
+    obj.remove("timestampToken");
 
     let cleaned = serde_json::Value::Object(obj);
     Ok(crypto::canonicalize_json(&cleaned))
 }
 
 /// Compute the SHA-256 document ID from its canonical form.
-pub fn compute_id(doc: &CovenantDocument) -> Result<String, NobulexError> {
+pub fn compute_id(doc: &CovenantDocument) -> Result<String, SteleError> {
     let canonical = canonical_form(doc)?;
     Ok(crypto::sha256_string(&canonical))
 }
@@ -152,49 +317,120 @@ pub fn compute_id(doc: &CovenantDocument) -> Result<String, NobulexError> {
 /// issuer's private key, and computes the document ID.
 ///
 /// # Errors
-/// Returns `NobulexError::InvalidInput` for missing/invalid fields,
-/// `NobulexError::CCLParseError` for invalid constraints, or
-/// `NobulexError::CryptoError` for signing failures.
-pub fn build_covenant(opts: CovenantBuilderOptions) -> Result<CovenantDocument, NobulexError> {
-    // Validate required inputs
-    if opts.issuer.id.is_empty() {
-        return Err(NobulexError::InvalidInput("issuer.id is required".to_string()));
-    }
-    if opts.issuer.public_key.is_empty() {
-        return Err(NobulexError::InvalidInput(
+/// Returns `SteleError::InvalidInput` for missing/invalid fields,
+/// `SteleError::CCLParseError` for invalid constraints, or
+/// `SteleError::CryptoError` for signing failures.
+pub fn build_covenant(opts: CovenantBuilderOptions) -> Result<CovenantDocument, SteleError> {
+    let _span = telemetry::start_span("covenant.build");
+    let mut doc = draft_covenant(
+        opts.issuer,
+        opts.beneficiary,
+        opts.constraints,
+        opts.chain,
+        opts.expires_at,
+        opts.activates_at,
+        opts.metadata,
+    )?;
+
+    let canonical = canonical_form(&doc)?;
+    let sig_bytes = crypto::sign(canonical.as_bytes(), &opts.signing_key)?;
+    doc.signature = hex::encode(&sig_bytes);
+    doc.id = crypto::sha256_string(&canonical);
+
+    enforce_document_size(&doc)?;
+    Ok(doc)
+}
+
+/// Build a new, signed CovenantDocument using an ECDSA P-256 (ES256) key,
+/// a 2048-bit RSA (RS256) key, or any pluggable [`Signer`] backend
+/// (software, hardware token, etc.), selected via `opts.signing_key`'s
+/// [`CovenantIssuerKey`] variant. Otherwise identical to [`build_covenant`].
+///
+/// # Errors
+/// Same error conditions as [`build_covenant`], plus any error surfaced
+/// by a `Signer` backend.
+pub fn build_covenant_with_key(opts: CovenantKeyedBuilderOptions) -> Result<CovenantDocument, SteleError> {
+    let mut doc = draft_covenant(
+        opts.issuer,
+        opts.beneficiary,
+        opts.constraints,
+        opts.chain,
+        opts.expires_at,
+        opts.activates_at,
+        opts.metadata,
+    )?;
+    doc.alg = match opts.signing_key {
+        CovenantIssuerKey::EcdsaP256(_) => crypto::SignatureAlgorithm::EcdsaP256,
+        CovenantIssuerKey::Rsa2048(_) => crypto::SignatureAlgorithm::Rsa2048,
+        CovenantIssuerKey::Signer(_) => doc.alg,
+    };
+
+    let canonical = canonical_form(&doc)?;
+    let sig_bytes = match opts.signing_key {
+        CovenantIssuerKey::EcdsaP256(key) => crypto::sign_ecdsa_p256(canonical.as_bytes(), key)?,
+        CovenantIssuerKey::Rsa2048(key) => crypto::sign_rsa2048(canonical.as_bytes(), key)?,
+        CovenantIssuerKey::Signer(signer) => {
+            let digest = crypto::signer::signing_digest(&canonical);
+            signer.sign_digest(&digest)?
+        }
+    };
+    doc.signature = hex::encode(&sig_bytes);
+    doc.id = crypto::sha256_string(&canonical);
+
+    enforce_document_size(&doc)?;
+    Ok(doc)
+}
+
+/// Validate inputs and assemble an unsigned `CovenantDocument` shell
+/// shared by every build path. The returned document has empty `id`
+/// and `signature` fields awaiting the caller's signing step.
+fn draft_covenant(
+    issuer: Party,
+    beneficiary: Party,
+    constraints: String,
+    chain: Option<ChainReference>,
+    expires_at: Option<String>,
+    activates_at: Option<String>,
+    metadata: Option<serde_json::Value>,
+) -> Result<CovenantDocument, SteleError> {
+    if issuer.id.is_empty() {
+        return Err(SteleError::InvalidInput("issuer.id is required".to_string()));
+    }
+    if issuer.public_key.is_empty() {
+        return Err(SteleError::InvalidInput(
             "issuer.publicKey is required".to_string(),
         ));
     }
-    if opts.issuer.role != "issuer" {
-        return Err(NobulexError::InvalidInput(
+    if issuer.role != "issuer" {
+        return Err(SteleError::InvalidInput(
             "issuer.role must be \"issuer\"".to_string(),
         ));
     }
-    if opts.beneficiary.id.is_empty() {
-        return Err(NobulexError::InvalidInput(
+    if beneficiary.id.is_empty() {
+        return Err(SteleError::InvalidInput(
             "beneficiary.id is required".to_string(),
         ));
     }
-    if opts.beneficiary.public_key.is_empty() {
-        return Err(NobulexError::InvalidInput(
+    if beneficiary.public_key.is_empty() {
+        return Err(SteleError::InvalidInput(
             "beneficiary.publicKey is required".to_string(),
         ));
     }
-    if opts.beneficiary.role != "beneficiary" {
-        return Err(NobulexError::InvalidInput(
+    if beneficiary.role != "beneficiary" {
+        return Err(SteleError::InvalidInput(
             "beneficiary.role must be \"beneficiary\"".to_string(),
         ));
     }
-    if opts.constraints.trim().is_empty() {
-        return Err(NobulexError::InvalidInput(
+    if constraints.trim().is_empty() {
+        return Err(SteleError::InvalidInput(
             "constraints is required".to_string(),
         ));
     }
 
     // Parse CCL to verify syntax and check constraint count
-    let parsed_ccl = ccl::parse(&opts.constraints)?;
+    let parsed_ccl = ccl::parse(&constraints)?;
     if parsed_ccl.statements.len() > MAX_CONSTRAINTS {
-        return Err(NobulexError::InvalidInput(format!(
+        return Err(SteleError::InvalidInput(format!(
             "Constraints exceed maximum of {} statements (got {})",
             MAX_CONSTRAINTS,
             parsed_ccl.statements.len()
@@ -202,81 +438,78 @@ pub fn build_covenant(opts: CovenantBuilderOptions) -> Result<CovenantDocument,
     }
 
     // Validate chain reference if present
-    if let Some(ref chain) = opts.chain {
+    if let Some(ref chain) = chain {
         if chain.parent_id.is_empty() {
-            return Err(NobulexError::InvalidInput(
+            return Err(SteleError::InvalidInput(
                 "chain.parentId is required".to_string(),
             ));
         }
         if chain.relation.is_empty() {
-            return Err(NobulexError::InvalidInput(
+            return Err(SteleError::InvalidInput(
                 "chain.relation is required".to_string(),
             ));
         }
         if chain.depth < 1 {
-            return Err(NobulexError::InvalidInput(
+            return Err(SteleError::InvalidInput(
                 "chain.depth must be a positive integer".to_string(),
             ));
         }
         if chain.depth > MAX_CHAIN_DEPTH {
-            return Err(NobulexError::InvalidInput(format!(
+            return Err(SteleError::InvalidInput(format!(
                 "chain.depth exceeds maximum of {} (got {})",
                 MAX_CHAIN_DEPTH, chain.depth
             )));
         }
     }
 
-    // Generate nonce and timestamp
     let nonce = hex::encode(crypto::generate_nonce());
     let created_at = crypto::timestamp();
 
-    // Construct the document (id and signature filled after hashing/signing)
-    let mut doc = CovenantDocument {
+    Ok(CovenantDocument {
         id: String::new(),
         version: PROTOCOL_VERSION.to_string(),
-        issuer: opts.issuer,
-        beneficiary: opts.beneficiary,
-        constraints: opts.constraints,
+        issuer,
+        beneficiary,
+        constraints,
         nonce,
         created_at,
         signature: String::new(),
-        chain: opts.chain,
-        expires_at: opts.expires_at,
-        activates_at: opts.activates_at,
+        alg: crypto::SignatureAlgorithm::Ed25519,
+        chain,
+        expires_at,
+        activates_at,
         countersignatures: None,
-        metadata: opts.metadata,
-    };
-
-    // Compute canonical form, sign, and derive ID
-    let canonical = canonical_form(&doc)?;
-    let sig_bytes = crypto::sign(canonical.as_bytes(), &opts.signing_key)?;
-    doc.signature = hex::encode(&sig_bytes);
-    doc.id = crypto::sha256_string(&canonical);
+        metadata,
+        timestamp_token: None,
+    })
+}
 
-    // Validate serialized size
-    let serialized = serde_json::to_string(&doc)
-        .map_err(|e| NobulexError::SerializationError(format!("Failed to serialize: {}", e)))?;
+/// Reject documents whose serialized size exceeds `MAX_DOCUMENT_SIZE`.
+fn enforce_document_size(doc: &CovenantDocument) -> Result<(), SteleError> {
+    let serialized = serde_json::to_string(doc)
+        .map_err(|e| SteleError::SerializationError(format!("Failed to serialize: {}", e)))?;
     if serialized.len() > MAX_DOCUMENT_SIZE {
-        return Err(NobulexError::InvalidInput(format!(
+        return Err(SteleError::InvalidInput(format!(
             "Serialized document exceeds maximum size of {} bytes",
             MAX_DOCUMENT_SIZE
         )));
     }
-
-    Ok(doc)
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
 // Verify
 // ---------------------------------------------------------------------------
 
-/// Verify a covenant document by running all 11 specification checks.
+/// Verify a covenant document by running all 12 specification checks.
 ///
 /// Checks:
 ///  1. `id_match` -- Document ID matches SHA-256 of canonical form
 ///  2. `signature_valid` -- Issuer's Ed25519 signature is valid
-///  3. `not_expired` -- Current time is before expiresAt (if set)
-///  4. `active` -- Current time is after activatesAt (if set)
+///  3. `not_expired` -- Current time is before expiresAt (if set); uses the
+///     TSA-attested time from a valid `timestamp_token` instead of the
+///     system clock when one is present
+///  4. `active` -- Same clock as `not_expired`, checked after activatesAt (if set)
 ///  5. `ccl_parses` -- Constraints parse as valid CCL
 ///  6. `enforcement_valid` -- Enforcement config type is recognized (if set)
 ///  7. `proof_valid` -- Proof config type is recognized (if set)
@@ -284,7 +517,14 @@ pub fn build_covenant(opts: CovenantBuilderOptions) -> Result<CovenantDocument,
 ///  9. `document_size` -- Serialized size does not exceed MAX_DOCUMENT_SIZE
 /// 10. `countersignatures` -- All countersignatures are valid
 /// 11. `nonce_present` -- Nonce is present and non-empty
-pub fn verify_covenant(doc: &CovenantDocument) -> Result<VerificationResult, NobulexError> {
+/// 12. `timestamp` -- `timestamp_token` (if present) is signed by its TSA
+///     and consistent with the document it attests to
+pub fn verify_covenant(doc: &CovenantDocument) -> Result<VerificationResult, SteleError> {
+    let mut span = telemetry::VerificationSpan::start(&doc.id);
+    if let Some(ref chain) = doc.chain {
+        span.set_chain_depth(chain.depth);
+    }
+
     let mut checks: Vec<VerificationCheck> = Vec::new();
 
     // 1. ID match
@@ -303,15 +543,9 @@ pub fn verify_covenant(doc: &CovenantDocument) -> Result<VerificationResult, Nob
     let sig_valid = {
         let canonical = canonical_form(doc)?;
         let sig_bytes = hex::decode(&doc.signature).unwrap_or_default();
-        let pub_key_bytes = hex::decode(&doc.issuer.public_key).unwrap_or_default();
-        let pub_array: [u8; 32] = pub_key_bytes
-            .as_slice()
-            .try_into()
-            .unwrap_or([0u8; 32]);
-        if let Ok(vk) = ed25519_dalek::VerifyingKey::from_bytes(&pub_array) {
-            crypto::verify(canonical.as_bytes(), &sig_bytes, &vk)
-        } else {
-            false
+        match did::resolve_builtin(&doc.issuer.public_key) {
+            Ok(key_hex) => crypto::verify_signature(doc.alg, canonical.as_bytes(), &sig_bytes, &key_hex),
+            Err(_) => false,
         }
     };
     checks.push(VerificationCheck {
@@ -324,10 +558,17 @@ pub fn verify_covenant(doc: &CovenantDocument) -> Result<VerificationResult, Nob
         },
     });
 
+    // A verified `timestamp_token` (step 12) attests a time from a
+    // trusted clock; the `not_expired`/`active` checks below evaluate
+    // against it instead of the local system clock when present, since
+    // `expiresAt`/`activatesAt` are only as trustworthy as the clock
+    // used to check them.
+    let attested_now = timestamp::attested_time(doc);
+    let now = attested_now.unwrap_or_else(chrono::Utc::now);
+
     // 3. Not expired
     if let Some(ref expires_at) = doc.expires_at {
         if let Ok(expires) = chrono::DateTime::parse_from_rfc3339(expires_at) {
-            let now = chrono::Utc::now();
             let not_expired = now < expires;
             checks.push(VerificationCheck {
                 name: "not_expired".to_string(),
@@ -341,7 +582,7 @@ pub fn verify_covenant(doc: &CovenantDocument) -> Result<VerificationResult, Nob
         } else {
             // Try a more lenient parse for ISO 8601 with milliseconds
             let not_expired = parse_timestamp(expires_at)
-                .map(|exp| chrono::Utc::now() < exp)
+                .map(|exp| now < exp)
                 .unwrap_or(true);
             checks.push(VerificationCheck {
                 name: "not_expired".to_string(),
@@ -364,7 +605,6 @@ pub fn verify_covenant(doc: &CovenantDocument) -> Result<VerificationResult, Nob
     // 4. Active
     if let Some(ref activates_at) = doc.activates_at {
         if let Ok(activates) = chrono::DateTime::parse_from_rfc3339(activates_at) {
-            let now = chrono::Utc::now();
             let is_active = now >= activates;
             checks.push(VerificationCheck {
                 name: "active".to_string(),
@@ -377,7 +617,7 @@ pub fn verify_covenant(doc: &CovenantDocument) -> Result<VerificationResult, Nob
             });
         } else {
             let is_active = parse_timestamp(activates_at)
-                .map(|act| chrono::Utc::now() >= act)
+                .map(|act| now >= act)
                 .unwrap_or(true);
             checks.push(VerificationCheck {
                 name: "active".to_string(),
@@ -491,15 +731,9 @@ pub fn verify_covenant(doc: &CovenantDocument) -> Result<VerificationResult, Nob
 
             for cs in countersigs {
                 let cs_sig_bytes = hex::decode(&cs.signature).unwrap_or_default();
-                let cs_pub_bytes = hex::decode(&cs.signer_public_key).unwrap_or_default();
-                let cs_pub_array: [u8; 32] = cs_pub_bytes
-                    .as_slice()
-                    .try_into()
-                    .unwrap_or([0u8; 32]);
-                let cs_valid = if let Ok(vk) = ed25519_dalek::VerifyingKey::from_bytes(&cs_pub_array) {
-                    crypto::verify(canonical.as_bytes(), &cs_sig_bytes, &vk)
-                } else {
-                    false
+                let cs_valid = match did::resolve_builtin(&cs.signer_public_key) {
+                    Ok(key_hex) => crypto::verify_signature(cs.alg, canonical.as_bytes(), &cs_sig_bytes, &key_hex),
+                    Err(_) => false,
                 };
 
                 if !cs_valid {
@@ -562,7 +796,29 @@ pub fn verify_covenant(doc: &CovenantDocument) -> Result<VerificationResult, Nob
         },
     });
 
+    // 12. Timestamp token (if present) is validly signed by its TSA
+    checks.push(VerificationCheck {
+        name: "timestamp".to_string(),
+        passed: match &doc.timestamp_token {
+            None => true,
+            Some(_) => attested_now.is_some(),
+        },
+        message: match (&doc.timestamp_token, &attested_now) {
+            (None, _) => "No timestamp token present; using local system clock for activation/expiry".to_string(),
+            (Some(token), Some(t)) => format!(
+                "Timestamp token from {} is valid; using TSA-attested time {} for activation/expiry",
+                token.tsa_public_key,
+                t.to_rfc3339()
+            ),
+            (Some(_), None) => "Timestamp token present but failed verification; falling back to local system clock".to_string(),
+        },
+    });
+
     let valid = checks.iter().all(|c| c.passed);
+    for check in &checks {
+        span.record_check(&check.name, check.passed);
+    }
+    span.finish(valid);
 
     Ok(VerificationResult { valid, checks })
 }
@@ -580,7 +836,7 @@ pub fn countersign_covenant(
     doc: &CovenantDocument,
     kp: &crypto::KeyPair,
     role: &str,
-) -> Result<CovenantDocument, NobulexError> {
+) -> Result<CovenantDocument, SteleError> {
     let canonical = canonical_form(doc)?;
     let sig_bytes = crypto::sign(canonical.as_bytes(), &kp.signing_key)?;
 
@@ -589,6 +845,61 @@ pub fn countersign_covenant(
         signer_role: role.to_string(),
         signature: hex::encode(&sig_bytes),
         timestamp: crypto::timestamp(),
+        alg: crypto::SignatureAlgorithm::Ed25519,
+    };
+
+    let mut new_doc = doc.clone();
+    let mut existing = new_doc.countersignatures.unwrap_or_default();
+    existing.push(countersig);
+    new_doc.countersignatures = Some(existing);
+
+    Ok(new_doc)
+}
+
+/// Add a countersignature produced with an ECDSA P-256 (ES256) key
+/// instead of Ed25519 -- e.g. an enterprise auditor whose signing key
+/// lives in a P-256-only HSM. Otherwise identical to
+/// [`countersign_covenant`].
+pub fn countersign_covenant_ecdsa_p256(
+    doc: &CovenantDocument,
+    kp: &crypto::EcdsaP256KeyPair,
+    role: &str,
+) -> Result<CovenantDocument, SteleError> {
+    let canonical = canonical_form(doc)?;
+    let sig_bytes = crypto::sign_ecdsa_p256(canonical.as_bytes(), &kp.signing_key)?;
+
+    let countersig = Countersignature {
+        signer_public_key: kp.public_key_hex.clone(),
+        signer_role: role.to_string(),
+        signature: hex::encode(&sig_bytes),
+        timestamp: crypto::timestamp(),
+        alg: crypto::SignatureAlgorithm::EcdsaP256,
+    };
+
+    let mut new_doc = doc.clone();
+    let mut existing = new_doc.countersignatures.unwrap_or_default();
+    existing.push(countersig);
+    new_doc.countersignatures = Some(existing);
+
+    Ok(new_doc)
+}
+
+/// Add a countersignature produced with a 2048-bit RSA (RS256) key
+/// instead of Ed25519. Otherwise identical to [`countersign_covenant`].
+pub fn countersign_covenant_rsa2048(
+    doc: &CovenantDocument,
+    kp: &crypto::RsaKeyPair,
+    role: &str,
+) -> Result<CovenantDocument, SteleError> {
+    let canonical = canonical_form(doc)?;
+    let sig_bytes = crypto::sign_rsa2048(canonical.as_bytes(), &kp.signing_key)?;
+
+    let countersig = Countersignature {
+        signer_public_key: kp.public_key_hex.clone(),
+        signer_role: role.to_string(),
+        signature: hex::encode(&sig_bytes),
+        timestamp: crypto::timestamp(),
+        alg: crypto::SignatureAlgorithm::Rsa2048,
     };
 
     let mut new_doc = doc.clone();
@@ -604,15 +915,15 @@ pub fn countersign_covenant(
 // ---------------------------------------------------------------------------
 
 /// Serialize a CovenantDocument to a JSON string.
-pub fn serialize_covenant(doc: &CovenantDocument) -> Result<String, NobulexError> {
+pub fn serialize_covenant(doc: &CovenantDocument) -> Result<String, SteleError> {
     serde_json::to_string_pretty(doc)
-        .map_err(|e| NobulexError::SerializationError(format!("Failed to serialize covenant: {}", e)))
+        .map_err(|e| SteleError::SerializationError(format!("Failed to serialize covenant: {}", e)))
 }
 
 /// Deserialize a JSON string into a CovenantDocument.
-pub fn deserialize_covenant(json: &str) -> Result<CovenantDocument, NobulexError> {
+pub fn deserialize_covenant(json: &str) -> Result<CovenantDocument, SteleError> {
     serde_json::from_str(json)
-        .map_err(|e| NobulexError::SerializationError(format!("Failed to deserialize covenant: {}", e)))
+        .map_err(|e| SteleError::SerializationError(format!("Failed to deserialize covenant: {}", e)))
 }
 
 // ---------------------------------------------------------------------------
@@ -626,12 +937,151 @@ pub fn deserialize_covenant(json: &str) -> Result<CovenantDocument, NobulexError
 pub fn validate_chain_narrowing(
     child: &CovenantDocument,
     parent: &CovenantDocument,
-) -> Result<ccl::NarrowingResult, NobulexError> {
+) -> Result<ccl::NarrowingResult, SteleError> {
+    let _span = telemetry::start_span("covenant.chain.validate_narrowing");
     let parent_ccl = ccl::parse(&parent.constraints)?;
     let child_ccl = ccl::parse(&child.constraints)?;
     Ok(ccl::validate_narrowing(&parent_ccl, &child_ccl))
 }
 
+// ---------------------------------------------------------------------------
+// Recursive chain resolution
+// ---------------------------------------------------------------------------
+
+/// Resolves a covenant by ID while walking a `chain.parentId` link up to
+/// its root, mirroring a block-by-hash provider in a blockchain client.
+/// Has a blanket impl for any `Fn(&str) -> Result<Option<CovenantDocument>, SteleError>`
+/// closure, so callers can pass a `store::Store`-backed lookup (or, in
+/// tests, a plain closure over an in-memory map) without implementing
+/// the trait by hand.
+pub trait ChainResolver {
+    fn resolve(&self, id: &str) -> Result<Option<CovenantDocument>, SteleError>;
+}
+
+impl<F> ChainResolver for F
+where
+    F: Fn(&str) -> Result<Option<CovenantDocument>, SteleError>,
+{
+    fn resolve(&self, id: &str) -> Result<Option<CovenantDocument>, SteleError> {
+        self(id)
+    }
+}
+
+/// Walk `leaf.chain.parentId` up to the root via `resolver`, verifying
+/// the whole delegation hierarchy rather than just one child/parent
+/// pair like [`validate_chain_narrowing`]. At each link: (1) the
+/// resolved parent's `id` matches `ChainReference.parentId`, (2) the
+/// parent passes full [`verify_covenant`], (3) `child.chain.depth ==
+/// parent.chain.depth + 1` (the root has no `chain`), (4) the resolved
+/// path does not exceed [`MAX_CHAIN_DEPTH`], and (5)
+/// [`ccl::validate_narrowing`] passes between every adjacent pair, so
+/// permissions only shrink walking from root to leaf. A `parentId` that
+/// revisits an already-seen id is rejected as a cycle rather than
+/// looped on forever.
+pub fn verify_chain(
+    leaf: &CovenantDocument,
+    resolver: &dyn ChainResolver,
+) -> Result<VerificationResult, SteleError> {
+    let mut checks: Vec<VerificationCheck> = Vec::new();
+    let mut visited: Vec<String> = vec![leaf.id.clone()];
+    let mut child = leaf.clone();
+    let mut hops = 0usize;
+
+    while let Some(chain_ref) = child.chain.clone() {
+        hops += 1;
+        if hops > MAX_CHAIN_DEPTH {
+            checks.push(VerificationCheck {
+                name: format!("chain_depth[{}]", hops),
+                passed: false,
+                message: format!("Resolved chain exceeds MAX_CHAIN_DEPTH of {}", MAX_CHAIN_DEPTH),
+            });
+            break;
+        }
+
+        if visited.contains(&chain_ref.parent_id) {
+            checks.push(VerificationCheck {
+                name: format!("no_cycles[{}]", hops),
+                passed: false,
+                message: format!("Cycle detected: {} was already visited", chain_ref.parent_id),
+            });
+            break;
+        }
+
+        let parent = match resolver.resolve(&chain_ref.parent_id)? {
+            Some(parent) => parent,
+            None => {
+                checks.push(VerificationCheck {
+                    name: format!("parent_resolved[{}]", hops),
+                    passed: false,
+                    message: format!("Parent {} could not be resolved", chain_ref.parent_id),
+                });
+                break;
+            }
+        };
+
+        let id_matches = parent.id == chain_ref.parent_id;
+        checks.push(VerificationCheck {
+            name: format!("parent_id_match[{}]", hops),
+            passed: id_matches,
+            message: if id_matches {
+                "Resolved parent id matches the chain reference".to_string()
+            } else {
+                format!(
+                    "Resolved parent id {} does not match chain reference {}",
+                    parent.id, chain_ref.parent_id
+                )
+            },
+        });
+
+        let ancestor_result = verify_covenant(&parent)?;
+        checks.push(VerificationCheck {
+            name: format!("ancestor_valid[{}]", hops),
+            passed: ancestor_result.valid,
+            message: if ancestor_result.valid {
+                format!("Ancestor {} passed verification", parent.id)
+            } else {
+                format!("Ancestor {} failed verification", parent.id)
+            },
+        });
+
+        let expected_parent_depth = parent.chain.as_ref().map(|c| c.depth).unwrap_or(0);
+        let depth_ok = chain_ref.depth == expected_parent_depth + 1;
+        checks.push(VerificationCheck {
+            name: format!("depth_sequence[{}]", hops),
+            passed: depth_ok,
+            message: if depth_ok {
+                "Child depth is exactly one more than its parent's".to_string()
+            } else {
+                format!(
+                    "Depth mismatch: child depth {} is not parent depth {} + 1",
+                    chain_ref.depth, expected_parent_depth
+                )
+            },
+        });
+
+        let narrowing = validate_chain_narrowing(&child, &parent)?;
+        checks.push(VerificationCheck {
+            name: format!("narrowing[{}]", hops),
+            passed: narrowing.valid,
+            message: if narrowing.valid {
+                "Child constraints narrow the parent's".to_string()
+            } else {
+                narrowing
+                    .violations
+                    .first()
+                    .map(|v| v.message.clone())
+                    .unwrap_or_else(|| "Child constraints do not narrow the parent's".to_string())
+            },
+        });
+
+        visited.push(chain_ref.parent_id.clone());
+        child = parent;
+    }
+
+    let valid = checks.iter().all(|c| c.passed);
+    Ok(VerificationResult { valid, checks })
+}
+
 // ---------------------------------------------------------------------------
 // Timestamp parsing helper
 // ---------------------------------------------------------------------------
@@ -739,4 +1189,300 @@ mod tests {
         let result = verify_covenant(&signed).unwrap();
         assert!(result.valid, "Verification after countersign failed: {:?}", result.checks);
     }
+
+    #[test]
+    fn test_build_covenant_with_signer() {
+        let (issuer, beneficiary, issuer_kp, _) = make_test_parties();
+        let signer = crate::crypto::signer::SoftwareSigner::new(&issuer_kp);
+        let doc = build_covenant_with_key(CovenantKeyedBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: CovenantIssuerKey::Signer(&signer),
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+
+        let result = verify_covenant(&doc).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_new_covenants_default_to_ed25519_alg() {
+        let (issuer, beneficiary, issuer_kp, _) = make_test_parties();
+        let doc = build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key,
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+        assert_eq!(doc.alg, crypto::SignatureAlgorithm::Ed25519);
+    }
+
+    #[test]
+    fn test_documents_without_alg_field_deserialize_as_ed25519() {
+        let (issuer, beneficiary, issuer_kp, _) = make_test_parties();
+        let doc = build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key,
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+
+        let mut value = serde_json::to_value(&doc).unwrap();
+        value.as_object_mut().unwrap().remove("alg");
+        let restored: CovenantDocument = serde_json::from_value(value).unwrap();
+        assert_eq!(restored.alg, crypto::SignatureAlgorithm::Ed25519);
+    }
+
+    #[test]
+    fn test_countersign_ecdsa_p256() {
+        let (issuer, beneficiary, issuer_kp, _) = make_test_parties();
+        let doc = build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key,
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+
+        let auditor_kp = crypto::generate_ecdsa_p256_key_pair().unwrap();
+        let signed = countersign_covenant_ecdsa_p256(&doc, &auditor_kp, "auditor").unwrap();
+        assert_eq!(
+            signed.countersignatures.as_ref().unwrap()[0].alg,
+            crypto::SignatureAlgorithm::EcdsaP256
+        );
+
+        let result = verify_covenant(&signed).unwrap();
+        assert!(result.valid, "Verification after P-256 countersign failed: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_alg_substitution_is_detected_as_tampering() {
+        let (issuer, beneficiary, issuer_kp, _) = make_test_parties();
+        let mut doc = build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key,
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+
+        // Swap the recorded algorithm without re-signing: both the id
+        // and the signature were computed over the original `alg`.
+        doc.alg = crypto::SignatureAlgorithm::EcdsaP256;
+        let result = verify_covenant(&doc).unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_build_covenant_ecdsa_p256_round_trips_through_verify() {
+        let (_, beneficiary, _, _) = make_test_parties();
+        let kp = crypto::generate_ecdsa_p256_key_pair().unwrap();
+        let issuer = Party {
+            id: "issuer-1".to_string(),
+            public_key: kp.public_key_hex.clone(),
+            role: "issuer".to_string(),
+        };
+        let doc = build_covenant_with_key(CovenantKeyedBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: CovenantIssuerKey::EcdsaP256(&kp.signing_key),
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+        assert_eq!(doc.alg, crypto::SignatureAlgorithm::EcdsaP256);
+
+        let result = verify_covenant(&doc).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_build_covenant_rsa2048_round_trips_through_verify() {
+        let (_, beneficiary, _, _) = make_test_parties();
+        let kp = crypto::generate_rsa2048_key_pair().unwrap();
+        let issuer = Party {
+            id: "issuer-1".to_string(),
+            public_key: kp.public_key_hex.clone(),
+            role: "issuer".to_string(),
+        };
+        let doc = build_covenant_with_key(CovenantKeyedBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: CovenantIssuerKey::Rsa2048(&kp.signing_key),
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+        assert_eq!(doc.alg, crypto::SignatureAlgorithm::Rsa2048);
+
+        let result = verify_covenant(&doc).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_countersign_rsa2048() {
+        let (issuer, beneficiary, issuer_kp, _) = make_test_parties();
+        let doc = build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key,
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+
+        let auditor_kp = crypto::generate_rsa2048_key_pair().unwrap();
+        let signed = countersign_covenant_rsa2048(&doc, &auditor_kp, "auditor").unwrap();
+        assert_eq!(
+            signed.countersignatures.as_ref().unwrap()[0].alg,
+            crypto::SignatureAlgorithm::Rsa2048
+        );
+
+        let result = verify_covenant(&signed).unwrap();
+        assert!(result.valid, "Verification after RSA countersign failed: {:?}", result.checks);
+    }
+
+    fn build_chain(constraints: &[&str]) -> Vec<CovenantDocument> {
+        let mut docs = Vec::new();
+        let mut parent: Option<ChainReference> = None;
+        for (i, constraint) in constraints.iter().enumerate() {
+            let kp = crypto::generate_key_pair().unwrap();
+            let bene_kp = crypto::generate_key_pair().unwrap();
+            let issuer = Party {
+                id: format!("issuer-{}", i),
+                public_key: kp.public_key_hex.clone(),
+                role: "issuer".to_string(),
+            };
+            let beneficiary = Party {
+                id: format!("beneficiary-{}", i),
+                public_key: bene_kp.public_key_hex,
+                role: "beneficiary".to_string(),
+            };
+            let doc = build_covenant(CovenantBuilderOptions {
+                issuer,
+                beneficiary,
+                constraints: constraint.to_string(),
+                signing_key: kp.signing_key,
+                chain: parent.clone(),
+                expires_at: None,
+                activates_at: None,
+                metadata: None,
+            })
+            .unwrap();
+            parent = Some(ChainReference {
+                parent_id: doc.id.clone(),
+                relation: "delegation".to_string(),
+                depth: parent.as_ref().map(|c| c.depth).unwrap_or(0) + 1,
+            });
+            docs.push(doc);
+        }
+        docs
+    }
+
+    fn resolver_for(docs: &[CovenantDocument]) -> impl Fn(&str) -> Result<Option<CovenantDocument>, SteleError> + '_ {
+        move |id: &str| Ok(docs.iter().find(|d| d.id == id).cloned())
+    }
+
+    #[test]
+    fn test_verify_chain_walks_to_root_and_passes() {
+        let docs = build_chain(&[
+            "permit read on '/data/**'\npermit write on '/data/**'",
+            "permit read on '/data/**'",
+            "permit read on '/data/reports/**'",
+        ]);
+        let leaf = docs.last().unwrap().clone();
+        let resolver = resolver_for(&docs);
+
+        let result = verify_chain(&leaf, &resolver).unwrap();
+        assert!(result.valid, "Chain verification failed: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_verify_chain_detects_unresolvable_parent() {
+        let docs = build_chain(&["permit read on '/data/**'", "permit read on '/data/**'"]);
+        let leaf = docs.last().unwrap().clone();
+        let empty: Vec<CovenantDocument> = Vec::new();
+        let resolver = resolver_for(&empty);
+
+        let result = verify_chain(&leaf, &resolver).unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_verify_chain_detects_depth_mismatch() {
+        let docs = build_chain(&["permit read on '/data/**'", "permit read on '/data/**'"]);
+        let mut leaf = docs.last().unwrap().clone();
+        leaf.chain.as_mut().unwrap().depth = 5;
+        let resolver = resolver_for(&docs);
+
+        let result = verify_chain(&leaf, &resolver).unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_verify_chain_detects_broadened_permissions() {
+        let docs = build_chain(&["permit read on '/data/**'", "permit read on '/data/**'\npermit write on '/data/**'"]);
+        let leaf = docs.last().unwrap().clone();
+        let resolver = resolver_for(&docs);
+
+        let result = verify_chain(&leaf, &resolver).unwrap();
+        assert!(!result.valid, "Broadened child should fail narrowing: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_verify_chain_detects_cycle() {
+        let mut docs = build_chain(&["permit read on '/data/**'", "permit read on '/data/**'"]);
+        // Forge the root's chain link to point back at the leaf, forming a
+        // cycle. This also breaks the root's own id/signature (its `chain`
+        // field is part of the hashed canonical form), so the walk should
+        // terminate rather than loop forever regardless of which check
+        // trips first.
+        docs[0].chain = Some(ChainReference {
+            parent_id: docs[1].id.clone(),
+            relation: "delegation".to_string(),
+            depth: 1,
+        });
+        let leaf = docs.last().unwrap().clone();
+        let resolver = resolver_for(&docs);
+
+        let result = verify_chain(&leaf, &resolver).unwrap();
+        assert!(!result.valid);
+        assert!(
+            result.checks.iter().any(|c| c.name.starts_with("no_cycles") && !c.passed),
+            "expected a failing no_cycles check, got: {:?}",
+            result.checks
+        );
+    }
 }
@@ -0,0 +1,317 @@
+//! Attested countersignatures, to stop countersignature spam.
+//!
+//! [`countersign_covenant`] lets any keypair append an entry to
+//! `countersignatures`, so a document that circulates widely can be
+//! flooded with junk countersignatures that every verifier then has to
+//! fetch and check. Borrowing the attested-certification model from
+//! keyservers, an [`AttestedCountersignatures`] block is a small signed
+//! statement from a principal party (the covenant's issuer or
+//! beneficiary) naming the digests of the specific countersignatures it
+//! accepts. [`verify_covenant_with_attestation`] then, by default, only
+//! counts and reports countersignatures whose digest appears in a
+//! validly-signed attestation -- everything else is excluded rather than
+//! reported as invalid -- while `verify_all` lets an auditor fall back to
+//! [`verify_covenant`]'s unfiltered check.
+//!
+//! [`countersign_covenant`]: super::countersign_covenant
+//! [`verify_covenant`]: super::verify_covenant
+
+use super::{canonical_form, verify_covenant, Countersignature, CovenantDocument, VerificationResult};
+use crate::crypto;
+use crate::SteleError;
+
+/// A signed statement from a principal party naming which
+/// countersignatures on a covenant it accepts, by digest.
+///
+/// The signature covers the canonical `(covenantId, acceptedDigests,
+/// attestedAt)` tuple, not the countersignatures themselves, so the
+/// attestation remains verifiable even if some attested countersignature
+/// is never re-transmitted alongside it.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AttestedCountersignatures {
+    #[serde(rename = "covenantId")]
+    pub covenant_id: String,
+    #[serde(rename = "attesterPublicKey")]
+    pub attester_public_key: String,
+    #[serde(rename = "acceptedDigests")]
+    pub accepted_digests: Vec<String>,
+    #[serde(rename = "attestedAt")]
+    pub attested_at: String,
+    pub signature: String,
+    pub alg: crypto::SignatureAlgorithm,
+}
+
+/// The digest an [`AttestedCountersignatures`] block names: the SHA-256
+/// of `cs`'s own canonical (JCS) JSON form.
+pub fn countersignature_digest(cs: &Countersignature) -> Result<String, SteleError> {
+    let val = serde_json::to_value(cs)
+        .map_err(|e| SteleError::SerializationError(format!("Failed to convert countersignature to JSON value: {}", e)))?;
+    let canonical = crypto::canonicalize_json(&val);
+    Ok(crypto::sha256_string(&canonical))
+}
+
+/// Produce the canonical bytes an attestation signs: the JCS
+/// canonicalization of `{covenantId, acceptedDigests, attestedAt}`.
+fn attestation_signing_bytes(covenant_id: &str, accepted_digests: &[String], attested_at: &str) -> String {
+    let payload = serde_json::json!({
+        "covenantId": covenant_id,
+        "acceptedDigests": accepted_digests,
+        "attestedAt": attested_at,
+    });
+    crypto::canonicalize_json(&payload)
+}
+
+/// Attest, on behalf of `party_kp`, that `doc`'s countersignatures
+/// matching `accepted` (by digest) are to be trusted by verifiers.
+/// `accepted` is taken from `doc.countersignatures` (or gathered
+/// out-of-band) -- each entry's digest is computed and signed, so the
+/// attestation names exactly the countersignatures the party reviewed.
+///
+/// # Errors
+/// Returns `SteleError::InvalidInput` if `party_kp` matches neither
+/// `doc.issuer.public_key` nor `doc.beneficiary.public_key`, or
+/// `SteleError::CryptoError` if signing fails.
+pub fn attest_countersignatures(
+    doc: &CovenantDocument,
+    party_kp: &crypto::KeyPair,
+    accepted: &[Countersignature],
+) -> Result<AttestedCountersignatures, SteleError> {
+    if party_kp.public_key_hex != doc.issuer.public_key && party_kp.public_key_hex != doc.beneficiary.public_key {
+        return Err(SteleError::InvalidInput(
+            "attesting key is neither the covenant's issuer nor its beneficiary".to_string(),
+        ));
+    }
+
+    let mut accepted_digests: Vec<String> = accepted.iter().map(countersignature_digest).collect::<Result<_, _>>()?;
+    accepted_digests.sort();
+    accepted_digests.dedup();
+
+    let attested_at = crypto::timestamp();
+    let canonical = attestation_signing_bytes(&doc.id, &accepted_digests, &attested_at);
+    let sig_bytes = crypto::sign(canonical.as_bytes(), &party_kp.signing_key)?;
+
+    Ok(AttestedCountersignatures {
+        covenant_id: doc.id.clone(),
+        attester_public_key: party_kp.public_key_hex.clone(),
+        accepted_digests,
+        attested_at,
+        signature: hex::encode(&sig_bytes),
+        alg: crypto::SignatureAlgorithm::Ed25519,
+    })
+}
+
+/// Verify that `attestation` names `doc`, was signed by one of `doc`'s
+/// principal parties (issuer or beneficiary), and checks out over the
+/// canonical `(covenantId, acceptedDigests, attestedAt)` tuple.
+fn verify_attestation(attestation: &AttestedCountersignatures, doc: &CovenantDocument) -> bool {
+    if attestation.covenant_id != doc.id {
+        return false;
+    }
+    if attestation.attester_public_key != doc.issuer.public_key && attestation.attester_public_key != doc.beneficiary.public_key {
+        return false;
+    }
+    let canonical = attestation_signing_bytes(&attestation.covenant_id, &attestation.accepted_digests, &attestation.attested_at);
+    let sig_bytes = match hex::decode(&attestation.signature) {
+        Ok(b) => b,
+        Err(_) => return false,
+    };
+    crypto::verify_signature(attestation.alg, canonical.as_bytes(), &sig_bytes, &attestation.attester_public_key)
+}
+
+/// Verify `doc` exactly as [`verify_covenant`] does, then replace its
+/// `countersignatures` check: by default, only countersignatures whose
+/// digest appears in `attestation` (once `attestation` itself is
+/// confirmed validly signed by a principal party) are counted and
+/// reported -- everything else is silently excluded, as if it were never
+/// attached. Pass `verify_all: true` (for auditors who need to see
+/// everything regardless of attestation) to skip this filtering
+/// entirely and fall back to `verify_covenant`'s unfiltered check.
+/// When `doc` has countersignatures but `attestation` is `None` or does
+/// not verify, the `countersignatures` check fails.
+///
+/// # Errors
+/// Same error conditions as `verify_covenant`.
+pub fn verify_covenant_with_attestation(
+    doc: &CovenantDocument,
+    attestation: Option<&AttestedCountersignatures>,
+    verify_all: bool,
+) -> Result<VerificationResult, SteleError> {
+    let mut result = verify_covenant(doc)?;
+    if verify_all {
+        return Ok(result);
+    }
+
+    let all_countersigs = doc.countersignatures.clone().unwrap_or_default();
+    if all_countersigs.is_empty() {
+        return Ok(result);
+    }
+
+    let attestation = match attestation.filter(|a| verify_attestation(a, doc)) {
+        Some(a) => a,
+        None => {
+            if let Some(check) = result.checks.iter_mut().find(|c| c.name == "countersignatures") {
+                check.passed = false;
+                check.message = "No valid attestation block present; countersignatures are unverified and excluded".to_string();
+            }
+            result.valid = result.checks.iter().all(|c| c.passed);
+            return Ok(result);
+        }
+    };
+
+    let canonical = canonical_form(doc)?;
+    let mut attested_valid = true;
+    let mut attested_count = 0usize;
+    let mut failures: Vec<String> = Vec::new();
+
+    for cs in &all_countersigs {
+        let digest = countersignature_digest(cs)?;
+        if !attestation.accepted_digests.contains(&digest) {
+            continue;
+        }
+        attested_count += 1;
+
+        let cs_sig_bytes = hex::decode(&cs.signature).unwrap_or_default();
+        let cs_valid = match super::did::resolve_builtin(&cs.signer_public_key) {
+            Ok(key_hex) => crypto::verify_signature(cs.alg, canonical.as_bytes(), &cs_sig_bytes, &key_hex),
+            Err(_) => false,
+        };
+        if !cs_valid {
+            attested_valid = false;
+            failures.push(cs.signer_public_key.clone());
+        }
+    }
+
+    if let Some(check) = result.checks.iter_mut().find(|c| c.name == "countersignatures") {
+        check.passed = attested_valid;
+        check.message = if attested_valid {
+            format!(
+                "{} of {} countersignature(s) are attested and valid",
+                attested_count,
+                all_countersigs.len()
+            )
+        } else {
+            format!("Invalid attested countersignature(s) from: {}", failures.join(", "))
+        };
+    }
+    result.valid = result.checks.iter().all(|c| c.passed);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covenant::{build_covenant, countersign_covenant, CovenantBuilderOptions, Party};
+
+    fn make_covenant() -> (CovenantDocument, crypto::KeyPair, crypto::KeyPair) {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary_kp = crypto::generate_key_pair().unwrap();
+        let doc = build_covenant(CovenantBuilderOptions {
+            issuer: Party {
+                id: "issuer-1".to_string(),
+                public_key: issuer_kp.public_key_hex.clone(),
+                role: "issuer".to_string(),
+            },
+            beneficiary: Party {
+                id: "beneficiary-1".to_string(),
+                public_key: beneficiary_kp.public_key_hex.clone(),
+                role: "beneficiary".to_string(),
+            },
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key.clone(),
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+        (doc, issuer_kp, beneficiary_kp)
+    }
+
+    #[test]
+    fn test_attest_and_verify_accepts_attested_countersignature() {
+        let (doc, issuer_kp, _beneficiary_kp) = make_covenant();
+        let auditor_kp = crypto::generate_key_pair().unwrap();
+        let signed = countersign_covenant(&doc, &auditor_kp, "auditor").unwrap();
+        let accepted = signed.countersignatures.clone().unwrap();
+
+        let attestation = attest_countersignatures(&signed, &issuer_kp, &accepted).unwrap();
+        let result = verify_covenant_with_attestation(&signed, Some(&attestation), false).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+        let check = result.checks.iter().find(|c| c.name == "countersignatures").unwrap();
+        assert!(check.message.contains("1 of 1"), "message was: {}", check.message);
+    }
+
+    #[test]
+    fn test_unattested_countersignature_is_excluded_not_failed() {
+        let (doc, issuer_kp, _beneficiary_kp) = make_covenant();
+        let auditor_kp = crypto::generate_key_pair().unwrap();
+        let signed = countersign_covenant(&doc, &auditor_kp, "auditor").unwrap();
+
+        // Attest an empty set: the countersignature exists but isn't named.
+        let attestation = attest_countersignatures(&signed, &issuer_kp, &[]).unwrap();
+        let result = verify_covenant_with_attestation(&signed, Some(&attestation), false).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+        let check = result.checks.iter().find(|c| c.name == "countersignatures").unwrap();
+        assert!(check.message.contains("0 of 1"), "message was: {}", check.message);
+    }
+
+    #[test]
+    fn test_verify_all_bypasses_attestation_filter() {
+        let (doc, _issuer_kp, _beneficiary_kp) = make_covenant();
+        let auditor_kp = crypto::generate_key_pair().unwrap();
+        let signed = countersign_covenant(&doc, &auditor_kp, "auditor").unwrap();
+
+        let result = verify_covenant_with_attestation(&signed, None, true).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+        let check = result.checks.iter().find(|c| c.name == "countersignatures").unwrap();
+        assert!(check.message.contains("All 1 countersignature"), "message was: {}", check.message);
+    }
+
+    #[test]
+    fn test_missing_attestation_fails_when_countersignatures_present() {
+        let (doc, _issuer_kp, _beneficiary_kp) = make_covenant();
+        let auditor_kp = crypto::generate_key_pair().unwrap();
+        let signed = countersign_covenant(&doc, &auditor_kp, "auditor").unwrap();
+
+        let result = verify_covenant_with_attestation(&signed, None, false).unwrap();
+        assert!(!result.valid);
+        let check = result.checks.iter().find(|c| c.name == "countersignatures").unwrap();
+        assert!(check.message.contains("No valid attestation"), "message was: {}", check.message);
+    }
+
+    #[test]
+    fn test_attest_countersignatures_rejects_non_principal_key() {
+        let (doc, _issuer_kp, _beneficiary_kp) = make_covenant();
+        let impostor_kp = crypto::generate_key_pair().unwrap();
+        let result = attest_countersignatures(&doc, &impostor_kp, &[]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_beneficiary_may_also_attest() {
+        let (doc, _issuer_kp, beneficiary_kp) = make_covenant();
+        let auditor_kp = crypto::generate_key_pair().unwrap();
+        let signed = countersign_covenant(&doc, &auditor_kp, "auditor").unwrap();
+        let accepted = signed.countersignatures.clone().unwrap();
+
+        let attestation = attest_countersignatures(&signed, &beneficiary_kp, &accepted).unwrap();
+        let result = verify_covenant_with_attestation(&signed, Some(&attestation), false).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_tampered_attestation_is_rejected() {
+        let (doc, issuer_kp, _beneficiary_kp) = make_covenant();
+        let auditor_kp = crypto::generate_key_pair().unwrap();
+        let signed = countersign_covenant(&doc, &auditor_kp, "auditor").unwrap();
+        let accepted = signed.countersignatures.clone().unwrap();
+
+        let mut attestation = attest_countersignatures(&signed, &issuer_kp, &accepted).unwrap();
+        attestation.signature = "00".repeat(64);
+
+        let result = verify_covenant_with_attestation(&signed, Some(&attestation), false).unwrap();
+        assert!(!result.valid);
+        let check = result.checks.iter().find(|c| c.name == "countersignatures").unwrap();
+        assert!(check.message.contains("No valid attestation"), "message was: {}", check.message);
+    }
+}
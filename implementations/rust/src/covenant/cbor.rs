@@ -0,0 +1,547 @@
+//! Deterministic CBOR (RFC 8949 section 4.2) and COSE_Sign1 (RFC 9052
+//! section 4.2) serialization for covenants, as a compact binary
+//! alternative to the JCS-JSON form [`serialize_covenant`]/
+//! [`deserialize_covenant`] produce.
+//!
+//! Map keys are sorted by their encoded bytes, integers use the
+//! shortest-form encoding, and no indefinite-length items are emitted --
+//! two structurally equal documents always produce identical bytes,
+//! mirroring what [`crypto::canonicalize_json`] already guarantees for
+//! JSON. [`canonical_form_cbor`] mirrors [`canonical_form`]: it strips
+//! `id`, `signature`, `countersignatures`, and `timestampToken` before
+//! encoding.
+//!
+//! This hand-rolls the small slice of CBOR/COSE it needs rather than
+//! depending on a general-purpose library, the same tradeoff
+//! [`crate::x509`] makes for DER.
+//!
+//! [`to_cose_sign1`] wraps [`canonical_form_cbor`] in a COSE_Sign1
+//! envelope (an untagged `[protected, unprotected, payload, signature]`
+//! array), signing the COSE `Sig_structure` -- `"Signature1"`, the
+//! protected header, an empty `external_aad`, and the payload -- rather
+//! than the payload bytes directly. The protected header carries the
+//! COSE algorithm identifier (`-8` for EdDSA; only Ed25519 is supported,
+//! matching [`Signer`]/[`Verifier`]) and `kid` (the issuer id), so the
+//! signing context can't be swapped out from under the signature. Unlike
+//! [`jws::to_jws`], which only re-presents an already-signed document,
+//! this produces an independent signature alongside `doc.signature` --
+//! the caller must supply the issuer's key via a [`Signer`].
+//! [`from_cose_sign1`] verifies that signature and reconstructs a
+//! `CovenantDocument`, recomputing `id` from the JSON [`canonical_form`]
+//! (the crate-wide definition of a document's id) rather than from the
+//! CBOR bytes.
+//!
+//! [`canonical_form`]: super::canonical_form
+//! [`serialize_covenant`]: super::serialize_covenant
+//! [`deserialize_covenant`]: super::deserialize_covenant
+
+use super::{canonical_form, CovenantDocument};
+use crate::crypto;
+use crate::crypto::signer::{signing_digest_bytes, Signer, Verifier};
+use crate::SteleError;
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// COSE algorithm identifier for EdDSA (RFC 9053 section 2.2).
+const COSE_ALG_EDDSA: i64 = -8;
+
+// ---------------------------------------------------------------------------
+// Generic deterministic CBOR codec for `serde_json::Value`
+// ---------------------------------------------------------------------------
+
+fn encode_head(major: u8, value: u64, out: &mut Vec<u8>) {
+    let top = major << 5;
+    if value < 24 {
+        out.push(top | value as u8);
+    } else if value <= 0xff {
+        out.push(top | 24);
+        out.push(value as u8);
+    } else if value <= 0xffff {
+        out.push(top | 25);
+        out.extend_from_slice(&(value as u16).to_be_bytes());
+    } else if value <= 0xffff_ffff {
+        out.push(top | 26);
+        out.extend_from_slice(&(value as u32).to_be_bytes());
+    } else {
+        out.push(top | 27);
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+fn encode_int(n: i64, out: &mut Vec<u8>) {
+    if n >= 0 {
+        encode_head(0, n as u64, out);
+    } else {
+        encode_head(1, (-1 - n) as u64, out);
+    }
+}
+
+fn encode_tstr(s: &str, out: &mut Vec<u8>) {
+    encode_head(3, s.len() as u64, out);
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn encode_bstr(data: &[u8], out: &mut Vec<u8>) {
+    encode_head(2, data.len() as u64, out);
+    out.extend_from_slice(data);
+}
+
+fn encode_number(n: &serde_json::Number, out: &mut Vec<u8>) {
+    if let Some(u) = n.as_u64() {
+        encode_head(0, u, out);
+    } else if let Some(i) = n.as_i64() {
+        encode_int(i, out);
+    } else if let Some(f) = n.as_f64() {
+        out.push(0xfb); // major type 7, 8-byte IEEE 754 double
+        out.extend_from_slice(&f.to_bits().to_be_bytes());
+    } else {
+        out.push(0xf6); // unrepresentable; encode as null rather than lose the field
+    }
+}
+
+/// Encode `value` as deterministic CBOR, appending to `out`. Object keys
+/// are sorted by their own encoded bytes (not just string order), so
+/// equal documents always produce identical bytes regardless of
+/// serde_json's map iteration order.
+fn encode_value(value: &serde_json::Value, out: &mut Vec<u8>) {
+    match value {
+        serde_json::Value::Null => out.push(0xf6),
+        serde_json::Value::Bool(false) => out.push(0xf4),
+        serde_json::Value::Bool(true) => out.push(0xf5),
+        serde_json::Value::Number(n) => encode_number(n, out),
+        serde_json::Value::String(s) => encode_tstr(s, out),
+        serde_json::Value::Array(arr) => {
+            encode_head(4, arr.len() as u64, out);
+            for v in arr {
+                encode_value(v, out);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<(Vec<u8>, &serde_json::Value)> = Vec::with_capacity(map.len());
+            for (k, v) in map {
+                let mut key_bytes = Vec::new();
+                encode_tstr(k, &mut key_bytes);
+                entries.push((key_bytes, v));
+            }
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            encode_head(5, entries.len() as u64, out);
+            for (key_bytes, v) in entries {
+                out.extend_from_slice(&key_bytes);
+                encode_value(v, out);
+            }
+        }
+    }
+}
+
+fn read_byte(bytes: &[u8], pos: &mut usize) -> Result<u8, SteleError> {
+    let b = bytes
+        .get(*pos)
+        .copied()
+        .ok_or_else(|| SteleError::SerializationError("unexpected end of CBOR input".to_string()))?;
+    *pos += 1;
+    Ok(b)
+}
+
+fn read_n<'a>(bytes: &'a [u8], pos: &mut usize, n: usize) -> Result<&'a [u8], SteleError> {
+    let end = *pos + n;
+    let slice = bytes
+        .get(*pos..end)
+        .ok_or_else(|| SteleError::SerializationError("unexpected end of CBOR input".to_string()))?;
+    *pos = end;
+    Ok(slice)
+}
+
+/// Read one CBOR head (major type + argument value) at `pos`, advancing it.
+fn read_head(bytes: &[u8], pos: &mut usize) -> Result<(u8, u64), SteleError> {
+    let head = read_byte(bytes, pos)?;
+    let major = head >> 5;
+    let info = head & 0x1f;
+    let value = match info {
+        0..=23 => info as u64,
+        24 => read_byte(bytes, pos)? as u64,
+        25 => u16::from_be_bytes(read_n(bytes, pos, 2)?.try_into().unwrap()) as u64,
+        26 => u32::from_be_bytes(read_n(bytes, pos, 4)?.try_into().unwrap()) as u64,
+        27 => u64::from_be_bytes(read_n(bytes, pos, 8)?.try_into().unwrap()),
+        _ => {
+            return Err(SteleError::SerializationError(format!(
+                "unsupported CBOR additional info {}",
+                info
+            )))
+        }
+    };
+    Ok((major, value))
+}
+
+fn read_bstr(bytes: &[u8], pos: &mut usize) -> Result<Vec<u8>, SteleError> {
+    let (major, len) = read_head(bytes, pos)?;
+    if major != 2 {
+        return Err(SteleError::SerializationError("expected a CBOR byte string".to_string()));
+    }
+    Ok(read_n(bytes, pos, len as usize)?.to_vec())
+}
+
+/// Decode one deterministic-CBOR-encoded `serde_json::Value` at `pos`,
+/// advancing it. Only the major types [`encode_value`] emits are
+/// supported (unsigned/negative integers, text strings, arrays, maps,
+/// booleans, null, and 8-byte floats) -- this decodes this crate's own
+/// output, not arbitrary third-party CBOR.
+fn decode_value(bytes: &[u8], pos: &mut usize) -> Result<serde_json::Value, SteleError> {
+    let (major, value) = read_head(bytes, pos)?;
+    match major {
+        0 => Ok(serde_json::Value::Number(value.into())),
+        1 => Ok(serde_json::json!(-1i64 - value as i64)),
+        3 => {
+            let raw = read_n(bytes, pos, value as usize)?;
+            let s = core::str::from_utf8(raw)
+                .map_err(|_| SteleError::SerializationError("CBOR text string is not valid UTF-8".to_string()))?;
+            Ok(serde_json::Value::String(s.to_string()))
+        }
+        4 => {
+            let mut arr = Vec::with_capacity(value as usize);
+            for _ in 0..value {
+                arr.push(decode_value(bytes, pos)?);
+            }
+            Ok(serde_json::Value::Array(arr))
+        }
+        5 => {
+            let mut map = serde_json::Map::new();
+            for _ in 0..value {
+                let key = decode_value(bytes, pos)?;
+                let key = key
+                    .as_str()
+                    .ok_or_else(|| SteleError::SerializationError("CBOR map key is not a text string".to_string()))?
+                    .to_string();
+                let val = decode_value(bytes, pos)?;
+                map.insert(key, val);
+            }
+            Ok(serde_json::Value::Object(map))
+        }
+        7 => match value {
+            20 => Ok(serde_json::Value::Bool(false)),
+            21 => Ok(serde_json::Value::Bool(true)),
+            22 => Ok(serde_json::Value::Null),
+            _ => serde_json::Number::from_f64(f64::from_bits(value))
+                .map(serde_json::Value::Number)
+                .ok_or_else(|| SteleError::SerializationError("CBOR float is not representable as JSON number".to_string())),
+        },
+        other => Err(SteleError::SerializationError(format!("unsupported CBOR major type {}", other))),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Covenant-specific encoding
+// ---------------------------------------------------------------------------
+
+/// Canonical CBOR form of `doc` for signing/hashing, mirroring
+/// [`canonical_form`]: strips `id`, `signature`, `countersignatures`, and
+/// `timestampToken`, then encodes deterministically.
+pub fn canonical_form_cbor(doc: &CovenantDocument) -> Result<Vec<u8>, SteleError> {
+    let val = serde_json::to_value(doc)
+        .map_err(|e| SteleError::SerializationError(format!("failed to convert to JSON value: {}", e)))?;
+    let mut obj = match val {
+        serde_json::Value::Object(m) => m,
+        _ => return Err(SteleError::SerializationError("expected object".to_string())),
+    };
+    obj.remove("id");
+    obj.remove("signature");
+    obj.remove("countersignatures");
+    obj.remove("timestampToken");
+
+    let mut out = Vec::new();
+    encode_value(&serde_json::Value::Object(obj), &mut out);
+    Ok(out)
+}
+
+/// Serialize the full document (including `id`, `signature`, and
+/// `countersignatures`) as deterministic CBOR -- a binary counterpart to
+/// [`serialize_covenant`].
+pub fn serialize_covenant_cbor(doc: &CovenantDocument) -> Result<Vec<u8>, SteleError> {
+    let val = serde_json::to_value(doc)
+        .map_err(|e| SteleError::SerializationError(format!("failed to convert to JSON value: {}", e)))?;
+    let mut out = Vec::new();
+    encode_value(&val, &mut out);
+    Ok(out)
+}
+
+/// Deserialize a document previously produced by [`serialize_covenant_cbor`].
+pub fn deserialize_covenant_cbor(bytes: &[u8]) -> Result<CovenantDocument, SteleError> {
+    let mut pos = 0;
+    let value = decode_value(bytes, &mut pos)?;
+    if pos != bytes.len() {
+        return Err(SteleError::SerializationError("trailing bytes after CBOR document".to_string()));
+    }
+    serde_json::from_value(value)
+        .map_err(|e| SteleError::SerializationError(format!("failed to reconstruct covenant document: {}", e)))
+}
+
+// ---------------------------------------------------------------------------
+// COSE_Sign1
+// ---------------------------------------------------------------------------
+
+/// Build the fixed-shape COSE protected header: a 2-entry map of
+/// `{1: alg, 4: kid}` (RFC 9052 section 3.1), where `alg` is always
+/// [`COSE_ALG_EDDSA`] and `kid` is the issuer id.
+fn protected_header_bytes(kid: &str) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_head(5, 2, &mut out);
+    encode_int(1, &mut out);
+    encode_int(COSE_ALG_EDDSA, &mut out);
+    encode_int(4, &mut out);
+    encode_bstr(kid.as_bytes(), &mut out);
+    out
+}
+
+/// Parse the fixed-shape protected header [`protected_header_bytes`]
+/// produces, returning `(alg, kid)`.
+fn decode_protected_header(bytes: &[u8]) -> Result<(i64, String), SteleError> {
+    let mut pos = 0;
+    let (major, len) = read_head(bytes, &mut pos)?;
+    if major != 5 || len != 2 {
+        return Err(SteleError::SerializationError(
+            "COSE protected header must be a 2-entry CBOR map (alg, kid)".to_string(),
+        ));
+    }
+    let (label1_major, label1) = read_head(bytes, &mut pos)?;
+    let (alg_major, alg_value) = read_head(bytes, &mut pos)?;
+    let (label2_major, label2) = read_head(bytes, &mut pos)?;
+    let kid_bytes = read_bstr(bytes, &mut pos)?;
+
+    if label1_major != 0 || label1 != 1 || label2_major != 0 || label2 != 4 {
+        return Err(SteleError::SerializationError(
+            "COSE protected header labels must be alg (1) then kid (4)".to_string(),
+        ));
+    }
+    let alg = match alg_major {
+        0 => alg_value as i64,
+        1 => -1 - alg_value as i64,
+        _ => return Err(SteleError::SerializationError("COSE alg must be an integer".to_string())),
+    };
+    let kid = String::from_utf8(kid_bytes)
+        .map_err(|_| SteleError::SerializationError("COSE kid is not valid UTF-8".to_string()))?;
+    Ok((alg, kid))
+}
+
+/// Build the COSE `Sig_structure` (RFC 9052 section 4.4) that gets
+/// signed/verified: `["Signature1", protected, external_aad, payload]`,
+/// with an empty `external_aad`.
+fn sig_structure_bytes(protected: &[u8], payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_head(4, 4, &mut out);
+    encode_tstr("Signature1", &mut out);
+    encode_bstr(protected, &mut out);
+    encode_bstr(&[], &mut out);
+    encode_bstr(payload, &mut out);
+    out
+}
+
+/// Wrap `doc` in a COSE_Sign1 envelope (an untagged
+/// `[protected, unprotected, payload, signature]` array), signing the
+/// COSE `Sig_structure` rather than the payload bytes directly. The
+/// payload is [`canonical_form_cbor`], so the signature covers
+/// deterministic CBOR bytes end to end instead of JSON.
+///
+/// Unlike [`jws::to_jws`], which only re-presents an already-signed
+/// document, this computes a fresh signature -- `signer` must hold the
+/// issuer's Ed25519 key.
+///
+/// [`jws::to_jws`]: super::jws::to_jws
+///
+/// # Errors
+/// Returns `SteleError::SerializationError` if `doc` fails to
+/// canonicalize, or whatever `signer` returns if signing fails.
+pub fn to_cose_sign1(doc: &CovenantDocument, signer: &dyn Signer) -> Result<Vec<u8>, SteleError> {
+    let payload = canonical_form_cbor(doc)?;
+    let protected = protected_header_bytes(&doc.issuer.id);
+    let sig_structure = sig_structure_bytes(&protected, &payload);
+    let digest = signing_digest_bytes(&sig_structure);
+    let signature = signer.sign_digest(&digest)?;
+
+    let mut out = Vec::new();
+    encode_head(4, 4, &mut out);
+    encode_bstr(&protected, &mut out);
+    out.push(0xa0); // empty unprotected header map
+    encode_bstr(&payload, &mut out);
+    encode_bstr(&signature, &mut out);
+    Ok(out)
+}
+
+/// Parse and verify a COSE_Sign1 envelope produced by [`to_cose_sign1`],
+/// returning the reconstructed `CovenantDocument`. `id` is recomputed
+/// from the JSON [`canonical_form`] -- the crate-wide definition of a
+/// document's id -- not from the CBOR payload bytes.
+///
+/// # Errors
+/// Returns `SteleError::SerializationError` if `bytes` is not a
+/// well-formed COSE_Sign1 envelope over a covenant, or
+/// `SteleError::VerificationFailed` if the protected header's `alg` is
+/// not EdDSA, its `kid` disagrees with the payload's issuer, or the
+/// signature does not verify.
+pub fn from_cose_sign1(bytes: &[u8], verifier: &dyn Verifier) -> Result<CovenantDocument, SteleError> {
+    let mut pos = 0;
+    let (major, len) = read_head(bytes, &mut pos)?;
+    if major != 4 || len != 4 {
+        return Err(SteleError::SerializationError(
+            "COSE_Sign1 must be a 4-element CBOR array".to_string(),
+        ));
+    }
+    let protected = read_bstr(bytes, &mut pos)?;
+    let (unprotected_major, unprotected_len) = read_head(bytes, &mut pos)?;
+    if unprotected_major != 5 {
+        return Err(SteleError::SerializationError(
+            "COSE_Sign1 unprotected header must be a CBOR map".to_string(),
+        ));
+    }
+    for _ in 0..unprotected_len {
+        decode_value(bytes, &mut pos)?; // key
+        decode_value(bytes, &mut pos)?; // value
+    }
+    let payload = read_bstr(bytes, &mut pos)?;
+    let signature = read_bstr(bytes, &mut pos)?;
+    if pos != bytes.len() {
+        return Err(SteleError::SerializationError(
+            "trailing bytes after COSE_Sign1 envelope".to_string(),
+        ));
+    }
+
+    let (alg, kid) = decode_protected_header(&protected)?;
+    if alg != COSE_ALG_EDDSA {
+        return Err(SteleError::VerificationFailed(format!(
+            "unsupported COSE alg `{}`, only EdDSA (-8) is supported",
+            alg
+        )));
+    }
+
+    let sig_structure = sig_structure_bytes(&protected, &payload);
+    let digest = signing_digest_bytes(&sig_structure);
+    if !verifier.verify_digest(&digest, &signature) {
+        return Err(SteleError::VerificationFailed(
+            "COSE_Sign1 signature verification failed".to_string(),
+        ));
+    }
+
+    let mut payload_pos = 0;
+    let mut payload_value = decode_value(&payload, &mut payload_pos)?;
+    let obj = payload_value
+        .as_object_mut()
+        .ok_or_else(|| SteleError::SerializationError("COSE_Sign1 payload is not a covenant object".to_string()))?;
+
+    let issuer_id = obj
+        .get("issuer")
+        .and_then(|v| v.get("id"))
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    if kid != issuer_id {
+        return Err(SteleError::VerificationFailed(format!(
+            "COSE protected header kid `{}` does not match payload issuer id `{}`",
+            kid, issuer_id
+        )));
+    }
+
+    obj.insert("signature".to_string(), serde_json::Value::String(hex::encode(&signature)));
+    obj.insert("id".to_string(), serde_json::Value::String(String::new()));
+
+    let partial: CovenantDocument = serde_json::from_value(payload_value)
+        .map_err(|e| SteleError::SerializationError(format!("failed to reconstruct covenant document: {}", e)))?;
+
+    let canonical = canonical_form(&partial)
+        .map_err(|e| SteleError::SerializationError(format!("failed to compute canonical form: {}", e)))?;
+    let id = crypto::sha256_string(&canonical);
+
+    Ok(CovenantDocument { id, ..partial })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covenant::{build_covenant, CovenantBuilderOptions, Party};
+    use crate::crypto::signer::{SoftwareSigner, SoftwareVerifier};
+
+    fn make_test_covenant() -> (CovenantDocument, crypto::KeyPair) {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary_kp = crypto::generate_key_pair().unwrap();
+        let issuer = Party {
+            id: "issuer-1".to_string(),
+            public_key: issuer_kp.public_key_hex.clone(),
+            role: "issuer".to_string(),
+        };
+        let beneficiary = Party {
+            id: "beneficiary-1".to_string(),
+            public_key: beneficiary_kp.public_key_hex,
+            role: "beneficiary".to_string(),
+        };
+        let doc = build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key.clone(),
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+        (doc, issuer_kp)
+    }
+
+    #[test]
+    fn test_serialize_covenant_cbor_roundtrip() {
+        let (doc, _kp) = make_test_covenant();
+        let bytes = serialize_covenant_cbor(&doc).unwrap();
+        let restored = deserialize_covenant_cbor(&bytes).unwrap();
+        assert_eq!(restored.id, doc.id);
+        assert_eq!(restored.signature, doc.signature);
+        assert_eq!(restored.constraints, doc.constraints);
+    }
+
+    #[test]
+    fn test_canonical_form_cbor_is_deterministic() {
+        let (doc, _kp) = make_test_covenant();
+        let a = canonical_form_cbor(&doc).unwrap();
+        let b = canonical_form_cbor(&doc).unwrap();
+        assert_eq!(a, b);
+        // Re-serializing and re-encoding must reproduce the exact same bytes.
+        let bytes = serialize_covenant_cbor(&doc).unwrap();
+        let restored = deserialize_covenant_cbor(&bytes).unwrap();
+        assert_eq!(canonical_form_cbor(&restored).unwrap(), a);
+    }
+
+    #[test]
+    fn test_cose_sign1_roundtrip() {
+        let (doc, kp) = make_test_covenant();
+        let signer = SoftwareSigner::new(&kp);
+        let envelope = to_cose_sign1(&doc, &signer).unwrap();
+
+        let verifier = SoftwareVerifier::from_public_key_hex(&kp.public_key_hex).unwrap();
+        let restored = from_cose_sign1(&envelope, &verifier).unwrap();
+        assert_eq!(restored.id, doc.id);
+        assert_eq!(restored.constraints, doc.constraints);
+    }
+
+    #[test]
+    fn test_from_cose_sign1_rejects_tampered_signature() {
+        let (doc, kp) = make_test_covenant();
+        let signer = SoftwareSigner::new(&kp);
+        let mut envelope = to_cose_sign1(&doc, &signer).unwrap();
+        *envelope.last_mut().unwrap() ^= 0xff;
+
+        let verifier = SoftwareVerifier::from_public_key_hex(&kp.public_key_hex).unwrap();
+        assert!(from_cose_sign1(&envelope, &verifier).is_err());
+    }
+
+    #[test]
+    fn test_from_cose_sign1_rejects_wrong_key() {
+        let (doc, kp) = make_test_covenant();
+        let signer = SoftwareSigner::new(&kp);
+        let envelope = to_cose_sign1(&doc, &signer).unwrap();
+
+        let other_kp = crypto::generate_key_pair().unwrap();
+        let verifier = SoftwareVerifier::from_public_key_hex(&other_kp.public_key_hex).unwrap();
+        assert!(from_cose_sign1(&envelope, &verifier).is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! Interactive bisection dispute game over covenant chains.
+//!
+//! When two parties disagree about where in a long covenant chain a CCL
+//! constraint was first violated, re-evaluating the entire chain is
+//! wasteful and puts all the trust in whoever runs that evaluation.
+//! `DisputeGame` instead narrows the disagreement to a single step
+//! transition via bisection, then resolves that one step by running the
+//! `ccl` evaluator over its inputs -- a cheap, verifiable accountability
+//! escalation path modeled on instruction-trace bisection games.
+
+use crate::ccl;
+use crate::SteleError;
+use std::collections::HashMap;
+
+/// Which party is currently expected to make the next move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Party {
+    Challenger,
+    Defender,
+}
+
+impl Party {
+    fn other(self) -> Party {
+        match self {
+            Party::Challenger => Party::Defender,
+            Party::Defender => Party::Challenger,
+        }
+    }
+}
+
+/// A single step in the disputed covenant chain trace: the constraints
+/// in force and the action/resource pair evaluated at that step.
+#[derive(Debug, Clone)]
+pub struct ChainStep {
+    pub constraints: String,
+    pub action: String,
+    pub resource: String,
+    pub context: HashMap<String, String>,
+}
+
+/// The outcome of resolving a collapsed one-step dispute.
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    pub winner: Party,
+    pub step: usize,
+    pub rule: String,
+}
+
+/// An in-progress or resolved bisection dispute over a covenant chain.
+pub struct DisputeGame {
+    /// Agreed-upon root hash of the full chain under dispute.
+    pub chain_root: String,
+    /// Current disputed step range, inclusive of `lo`, exclusive of `hi`.
+    lo: usize,
+    hi: usize,
+    /// Commitments to state hashes at bisection midpoints, by step index.
+    commitments: HashMap<usize, (String, String)>,
+    /// Whose turn it is to commit the next midpoint.
+    turn: Party,
+    /// Per-move timeout in rounds; a party that doesn't respond within
+    /// this many rounds forfeits the game to their opponent.
+    timeout_rounds: u32,
+    rounds_waited: u32,
+    resolution: Option<Resolution>,
+}
+
+impl DisputeGame {
+    /// Start a new dispute over the step range `[0, chain_len)`.
+    pub fn new(chain_root: String, chain_len: usize, timeout_rounds: u32) -> Self {
+        DisputeGame {
+            chain_root,
+            lo: 0,
+            hi: chain_len,
+            commitments: HashMap::new(),
+            turn: Party::Challenger,
+            timeout_rounds,
+            rounds_waited: 0,
+            resolution: None,
+        }
+    }
+
+    /// Current disputed range `(lo, hi)`.
+    pub fn range(&self) -> (usize, usize) {
+        (self.lo, self.hi)
+    }
+
+    /// Whether the range has collapsed to a single transition `i -> i+1`.
+    pub fn is_collapsed(&self) -> bool {
+        self.hi - self.lo <= 1
+    }
+
+    /// Midpoint of the current range.
+    fn midpoint(&self) -> usize {
+        self.lo + (self.hi - self.lo) / 2
+    }
+
+    /// The party whose `turn` it is commits their computed state hash at the
+    /// midpoint of the range. Both parties must commit before the round
+    /// resolves; calling this alternates `turn` to the other party.
+    ///
+    /// # Errors
+    /// Returns `SteleError::InvalidInput` if the game has already
+    /// collapsed and is awaiting `resolve()`.
+    pub fn commit_midpoint(&mut self, state_hash: &str) -> Result<usize, SteleError> {
+        if self.is_collapsed() {
+            return Err(SteleError::InvalidInput(
+                "dispute range has collapsed; call resolve() instead".to_string(),
+            ));
+        }
+        let mid = self.midpoint();
+        let entry = self.commitments.entry(mid).or_insert_with(|| (String::new(), String::new()));
+        match self.turn {
+            Party::Challenger => entry.0 = state_hash.to_string(),
+            Party::Defender => entry.1 = state_hash.to_string(),
+        }
+        self.rounds_waited = 0;
+        self.turn = self.turn.other();
+
+        // Once both sides have committed at this midpoint, narrow the range.
+        if !entry.0.is_empty() && !entry.1.is_empty() {
+            if entry.0 == entry.1 {
+                // Agreement at mid: divergence lies in [mid, hi].
+                self.lo = mid;
+            } else {
+                // Disagreement at mid: divergence lies in [lo, mid].
+                self.hi = mid;
+            }
+        }
+
+        Ok(mid)
+    }
+
+    /// Advance the timeout clock by one round without a commitment.
+    /// Returns the defaulting party if the timeout has now elapsed.
+    pub fn tick_timeout(&mut self) -> Option<Party> {
+        self.rounds_waited += 1;
+        if self.rounds_waited >= self.timeout_rounds {
+            // The party whose turn it was failed to respond in time.
+            Some(self.turn)
+        } else {
+            None
+        }
+    }
+
+    /// Resolve a collapsed dispute by evaluating the single disputed
+    /// transition `lo -> lo + 1` against the CCL evaluator.
+    ///
+    /// # Errors
+    /// Returns `SteleError::InvalidInput` if the range has not yet
+    /// collapsed to a single step, or `SteleError::CCLParseError` if the
+    /// step's constraints fail to parse.
+    pub fn resolve(&mut self, step: &ChainStep, challenger_claims_violation: bool) -> Result<Resolution, SteleError> {
+        if !self.is_collapsed() {
+            return Err(SteleError::InvalidInput(format!(
+                "dispute range [{}, {}) has not collapsed to a single step",
+                self.lo, self.hi
+            )));
+        }
+
+        let doc = ccl::parse(&step.constraints)?;
+        let result = ccl::evaluate(&doc, &step.action, &step.resource, &step.context);
+        let actually_violated = !result.permitted;
+
+        let winner = if actually_violated == challenger_claims_violation {
+            Party::Challenger
+        } else {
+            Party::Defender
+        };
+
+        let resolution = Resolution {
+            winner,
+            step: self.lo,
+            rule: result.reason,
+        };
+        self.resolution = Some(resolution.clone());
+        Ok(resolution)
+    }
+
+    /// The final resolution, if `resolve()` has been called.
+    pub fn resolution(&self) -> Option<&Resolution> {
+        self.resolution.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step() -> ChainStep {
+        ChainStep {
+            constraints: "permit read on '/data/**'\ndeny read on '/data/secret'".to_string(),
+            action: "read".to_string(),
+            resource: "/data/secret".to_string(),
+            context: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_bisection_collapses_to_single_step() {
+        let mut game = DisputeGame::new("root-hash".to_string(), 8, 5);
+        // Both parties agree at every midpoint, so the range narrows to the last step.
+        while !game.is_collapsed() {
+            game.commit_midpoint("agreed-hash").unwrap();
+            if !game.is_collapsed() {
+                game.commit_midpoint("agreed-hash").unwrap();
+            }
+        }
+        let (lo, hi) = game.range();
+        assert_eq!(hi - lo, 1);
+    }
+
+    #[test]
+    fn test_resolve_after_collapse() {
+        let mut game = DisputeGame::new("root-hash".to_string(), 1, 5);
+        assert!(game.is_collapsed());
+        let resolution = game.resolve(&step(), true).unwrap();
+        assert_eq!(resolution.winner, Party::Challenger);
+        assert_eq!(resolution.step, 0);
+    }
+
+    #[test]
+    fn test_resolve_rejects_uncollapsed_range() {
+        let mut game = DisputeGame::new("root-hash".to_string(), 4, 5);
+        let err = game.resolve(&step(), true);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_disagreement_narrows_to_lower_half() {
+        let mut game = DisputeGame::new("root-hash".to_string(), 4, 5);
+        game.commit_midpoint("hash-a").unwrap();
+        game.commit_midpoint("hash-b").unwrap();
+        let (lo, hi) = game.range();
+        assert_eq!((lo, hi), (0, 2));
+    }
+
+    #[test]
+    fn test_timeout_defaults_unresponsive_party() {
+        let mut game = DisputeGame::new("root-hash".to_string(), 4, 2);
+        assert!(game.tick_timeout().is_none());
+        let defaulter = game.tick_timeout();
+        assert_eq!(defaulter, Some(Party::Challenger));
+    }
+}
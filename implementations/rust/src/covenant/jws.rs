@@ -0,0 +1,382 @@
+//! JSON Web Signature (RFC 7515) export/import for covenants.
+//!
+//! [`to_jws`] re-presents an already-signed [`CovenantDocument`] as a JWS,
+//! in either the compact `header.payload.signature` form or the flattened
+//! JSON form ACME (RFC 8555) uses for its requests, so covenants can pass
+//! through existing JOSE libraries and HTTP middleware without a
+//! Nobulex-specific client. The protected header is
+//! `{"alg":<EdDSA|ES256|RS256>,"kid":<issuer.id>,"typ":"nobulex-covenant"}`
+//! and the payload is the base64url-encoded [`canonical_form`]. There is
+//! no registered JOSE `alg` name for `Secp256k1Schnorr` (BIP-340 isn't a
+//! JWS algorithm), so documents signed with it can't be exported as a
+//! JWS -- [`to_jws`] rejects them with `SteleError::InvalidInput` rather
+//! than inventing a non-standard header value.
+//!
+//! Unlike a general-purpose JWS, the embedded signature covers the
+//! canonical payload bytes directly -- the same bytes [`verify_covenant`]
+//! already verifies against -- rather than the ASCII
+//! `base64url(header) + "." + base64url(payload)` signing input most JOSE
+//! libraries produce. `to_jws` only has the document, not the issuer's
+//! private key, so it cannot re-sign; the protected header exists to name
+//! the algorithm and key, not to extend the signed surface. [`from_jws`]
+//! decodes the payload back into a full `CovenantDocument`, recomputing
+//! `id` from the canonical bytes and `signature` from the JWS signature
+//! segment; countersignatures are not part of the canonical form and so
+//! do not round-trip through a JWS.
+//!
+//! [`verify_covenant`]: super::verify_covenant
+
+use super::{canonical_form, CovenantDocument};
+use crate::crypto;
+use crate::SteleError;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+const BASE64URL_ALPHABET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Which JWS serialization [`to_jws`] should produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwsForm {
+    /// `header.payload.signature`, each segment base64url-encoded.
+    Compact,
+    /// The flattened JSON serialization (RFC 7515 section 7.2.2):
+    /// `{"protected": ..., "payload": ..., "signature": ...}`.
+    Flattened,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JwsHeader {
+    alg: String,
+    kid: String,
+    typ: String,
+}
+
+/// Export `doc` as a JSON Web Signature. See the module docs for how the
+/// signed surface differs from a general-purpose JWS.
+///
+/// # Errors
+/// Returns `SteleError::SerializationError` if `doc.signature` is not
+/// valid hex or the document fails to canonicalize/serialize, or
+/// `SteleError::InvalidInput` if `doc.alg` has no registered JWS `alg`
+/// name (see [`alg_to_jws_name`]).
+pub fn to_jws(doc: &CovenantDocument, form: JwsForm) -> Result<String, SteleError> {
+    let header = JwsHeader {
+        alg: alg_to_jws_name(doc.alg)?.to_string(),
+        kid: doc.issuer.id.clone(),
+        typ: "nobulex-covenant".to_string(),
+    };
+    let header_json = serde_json::to_string(&header)
+        .map_err(|e| SteleError::SerializationError(format!("failed to serialize JWS header: {}", e)))?;
+    let header_b64 = base64url_encode(header_json.as_bytes());
+
+    let canonical = canonical_form(doc)
+        .map_err(|e| SteleError::SerializationError(format!("failed to compute canonical form: {}", e)))?;
+    let payload_b64 = base64url_encode(canonical.as_bytes());
+
+    let sig_bytes = hex::decode(&doc.signature)
+        .map_err(|e| SteleError::SerializationError(format!("signature is not valid hex: {}", e)))?;
+    let sig_b64 = base64url_encode(&sig_bytes);
+
+    match form {
+        JwsForm::Compact => Ok(format!("{}.{}.{}", header_b64, payload_b64, sig_b64)),
+        JwsForm::Flattened => {
+            let flattened = serde_json::json!({
+                "protected": header_b64,
+                "payload": payload_b64,
+                "signature": sig_b64,
+            });
+            serde_json::to_string(&flattened)
+                .map_err(|e| SteleError::SerializationError(format!("failed to serialize flattened JWS: {}", e)))
+        }
+    }
+}
+
+/// Parse and verify a JWS produced by [`to_jws`] (either form), returning
+/// the reconstructed `CovenantDocument`.
+///
+/// # Errors
+/// Returns `SteleError::SerializationError` if `input` is not a
+/// well-formed compact or flattened JWS, or
+/// `SteleError::VerificationFailed` if the protected header's `kid`/`alg`
+/// disagree with the decoded payload, or the signature does not verify
+/// against the issuer's embedded public key.
+pub fn from_jws(input: &str) -> Result<CovenantDocument, SteleError> {
+    let (header_b64, payload_b64, sig_b64) = split_jws(input)?;
+
+    let header_bytes = base64url_decode(&header_b64)?;
+    let header: JwsHeader = serde_json::from_slice(&header_bytes)
+        .map_err(|e| SteleError::SerializationError(format!("invalid JWS protected header: {}", e)))?;
+    let alg = jws_name_to_alg(&header.alg)?;
+
+    let payload_bytes = base64url_decode(&payload_b64)?;
+    let canonical = String::from_utf8(payload_bytes)
+        .map_err(|_| SteleError::SerializationError("JWS payload is not valid UTF-8".to_string()))?;
+    let mut payload_value: serde_json::Value = serde_json::from_str(&canonical)
+        .map_err(|e| SteleError::SerializationError(format!("JWS payload is not a valid covenant canonical form: {}", e)))?;
+    let obj = payload_value
+        .as_object_mut()
+        .ok_or_else(|| SteleError::SerializationError("JWS payload is not a JSON object".to_string()))?;
+
+    let issuer_id = obj
+        .get("issuer")
+        .and_then(|v| v.get("id"))
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let issuer_public_key = obj
+        .get("issuer")
+        .and_then(|v| v.get("publicKey"))
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    if header.kid != issuer_id {
+        return Err(SteleError::VerificationFailed(format!(
+            "JWS header kid `{}` does not match payload issuer id `{}`",
+            header.kid, issuer_id
+        )));
+    }
+
+    let payload_alg: crypto::SignatureAlgorithm = obj
+        .get("alg")
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|e: serde_json::Error| SteleError::SerializationError(format!("invalid `alg` in payload: {}", e)))?
+        .unwrap_or_default();
+    if payload_alg != alg {
+        return Err(SteleError::VerificationFailed(format!(
+            "JWS header alg `{}` does not match the payload's recorded algorithm",
+            header.alg
+        )));
+    }
+
+    let sig_bytes = base64url_decode(&sig_b64)?;
+    if !crypto::verify_signature(alg, canonical.as_bytes(), &sig_bytes, &issuer_public_key) {
+        return Err(SteleError::VerificationFailed(
+            "JWS signature verification failed".to_string(),
+        ));
+    }
+
+    obj.insert(
+        "id".to_string(),
+        serde_json::Value::String(crypto::sha256_string(&canonical)),
+    );
+    obj.insert(
+        "signature".to_string(),
+        serde_json::Value::String(hex::encode(&sig_bytes)),
+    );
+
+    serde_json::from_value(payload_value)
+        .map_err(|e| SteleError::SerializationError(format!("failed to reconstruct covenant document: {}", e)))
+}
+
+/// Map a signature algorithm to its registered JWS `alg` name (RFC 7518).
+///
+/// # Errors
+/// Returns `SteleError::InvalidInput` for `Secp256k1Schnorr`: BIP-340
+/// Schnorr signatures have no registered JWS `alg`, and inventing one
+/// would produce a header value no other JOSE implementation recognizes.
+fn alg_to_jws_name(alg: crypto::SignatureAlgorithm) -> Result<&'static str, SteleError> {
+    match alg {
+        crypto::SignatureAlgorithm::Ed25519 => Ok("EdDSA"),
+        crypto::SignatureAlgorithm::EcdsaP256 => Ok("ES256"),
+        crypto::SignatureAlgorithm::Rsa2048 => Ok("RS256"),
+        crypto::SignatureAlgorithm::Secp256k1Schnorr => Err(SteleError::InvalidInput(
+            "secp256k1 Schnorr signatures have no registered JWS alg and cannot be exported as a JWS".to_string(),
+        )),
+    }
+}
+
+fn jws_name_to_alg(name: &str) -> Result<crypto::SignatureAlgorithm, SteleError> {
+    match name {
+        "EdDSA" => Ok(crypto::SignatureAlgorithm::Ed25519),
+        "ES256" => Ok(crypto::SignatureAlgorithm::EcdsaP256),
+        "RS256" => Ok(crypto::SignatureAlgorithm::Rsa2048),
+        other => Err(SteleError::InvalidInput(format!("unsupported JWS alg: {}", other))),
+    }
+}
+
+/// Split a compact or flattened-JSON JWS into its three base64url
+/// segments (protected header, payload, signature).
+fn split_jws(input: &str) -> Result<(String, String, String), SteleError> {
+    let trimmed = input.trim();
+    if trimmed.starts_with('{') {
+        let val: serde_json::Value = serde_json::from_str(trimmed)
+            .map_err(|e| SteleError::SerializationError(format!("invalid flattened JWS JSON: {}", e)))?;
+        let protected = val
+            .get("protected")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| SteleError::SerializationError("flattened JWS missing `protected`".to_string()))?
+            .to_string();
+        let payload = val
+            .get("payload")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| SteleError::SerializationError("flattened JWS missing `payload`".to_string()))?
+            .to_string();
+        let signature = val
+            .get("signature")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| SteleError::SerializationError("flattened JWS missing `signature`".to_string()))?
+            .to_string();
+        Ok((protected, payload, signature))
+    } else {
+        let parts: Vec<&str> = trimmed.split('.').collect();
+        if parts.len() != 3 {
+            return Err(SteleError::SerializationError(
+                "compact JWS must have exactly 3 dot-separated segments".to_string(),
+            ));
+        }
+        Ok((parts[0].to_string(), parts[1].to_string(), parts[2].to_string()))
+    }
+}
+
+pub(crate) fn base64url_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = *chunk.get(1).unwrap_or(&0) as u32;
+        let b2 = *chunk.get(2).unwrap_or(&0) as u32;
+        let n = (b0 << 16) | (b1 << 8) | b2;
+        out.push(BASE64URL_ALPHABET[((n >> 18) & 0x3f) as usize] as char);
+        out.push(BASE64URL_ALPHABET[((n >> 12) & 0x3f) as usize] as char);
+        if chunk.len() > 1 {
+            out.push(BASE64URL_ALPHABET[((n >> 6) & 0x3f) as usize] as char);
+        }
+        if chunk.len() > 2 {
+            out.push(BASE64URL_ALPHABET[(n & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+pub(crate) fn base64url_decode(s: &str) -> Result<Vec<u8>, SteleError> {
+    fn digit(c: u8) -> Option<u32> {
+        match c {
+            b'A'..=b'Z' => Some((c - b'A') as u32),
+            b'a'..=b'z' => Some((c - b'a' + 26) as u32),
+            b'0'..=b'9' => Some((c - b'0' + 52) as u32),
+            b'-' => Some(62),
+            b'_' => Some(63),
+            _ => None,
+        }
+    }
+
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len() * 3 / 4 + 3);
+    for chunk in bytes.chunks(4) {
+        if chunk.len() < 2 {
+            return Err(SteleError::SerializationError("invalid base64url length".to_string()));
+        }
+        let mut vals = [0u32; 4];
+        for (i, &c) in chunk.iter().enumerate() {
+            vals[i] = digit(c)
+                .ok_or_else(|| SteleError::SerializationError("invalid base64url character".to_string()))?;
+        }
+        let n = (vals[0] << 18) | (vals[1] << 12) | (vals[2] << 6) | vals[3];
+        out.push(((n >> 16) & 0xff) as u8);
+        if chunk.len() > 2 {
+            out.push(((n >> 8) & 0xff) as u8);
+        }
+        if chunk.len() > 3 {
+            out.push((n & 0xff) as u8);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covenant::{build_covenant, CovenantBuilderOptions, Party};
+
+    fn make_test_covenant() -> CovenantDocument {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary_kp = crypto::generate_key_pair().unwrap();
+        let issuer = Party {
+            id: "issuer-1".to_string(),
+            public_key: issuer_kp.public_key_hex.clone(),
+            role: "issuer".to_string(),
+        };
+        let beneficiary = Party {
+            id: "beneficiary-1".to_string(),
+            public_key: beneficiary_kp.public_key_hex,
+            role: "beneficiary".to_string(),
+        };
+        build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key,
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_base64url_roundtrip() {
+        for data in [b"".as_slice(), b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"] {
+            let encoded = base64url_encode(data);
+            assert!(!encoded.contains('='));
+            assert_eq!(base64url_decode(&encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_compact_jws_roundtrip() {
+        let doc = make_test_covenant();
+        let jws = to_jws(&doc, JwsForm::Compact).unwrap();
+        assert_eq!(jws.matches('.').count(), 2);
+
+        let restored = from_jws(&jws).unwrap();
+        assert_eq!(restored.id, doc.id);
+        assert_eq!(restored.signature, doc.signature);
+        assert_eq!(restored.alg, doc.alg);
+    }
+
+    #[test]
+    fn test_flattened_jws_roundtrip() {
+        let doc = make_test_covenant();
+        let jws = to_jws(&doc, JwsForm::Flattened).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&jws).unwrap();
+        assert!(value.get("protected").is_some());
+        assert!(value.get("payload").is_some());
+        assert!(value.get("signature").is_some());
+
+        let restored = from_jws(&jws).unwrap();
+        assert_eq!(restored.id, doc.id);
+    }
+
+    #[test]
+    fn test_from_jws_rejects_kid_mismatch() {
+        let doc = make_test_covenant();
+        let jws = to_jws(&doc, JwsForm::Compact).unwrap();
+        let mut parts: Vec<&str> = jws.split('.').collect();
+        let bad_header = base64url_encode(
+            serde_json::json!({"alg": "EdDSA", "kid": "someone-else", "typ": "nobulex-covenant"})
+                .to_string()
+                .as_bytes(),
+        );
+        parts[0] = &bad_header;
+        let tampered = parts.join(".");
+        assert!(from_jws(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_from_jws_rejects_bad_signature() {
+        let doc = make_test_covenant();
+        let mut jws = to_jws(&doc, JwsForm::Compact).unwrap();
+        jws.push('x');
+        assert!(from_jws(&jws).is_err());
+    }
+}
@@ -0,0 +1,477 @@
+//! Covenant revocation certificates.
+//!
+//! `expires_at` only covers scheduled, known-in-advance expiry. An issuer
+//! that needs to disavow a covenant early -- because it was superseded, a
+//! signing key leaked, or the counterparty violated policy -- has no way
+//! to do so. Borrowing the revocation-certificate model from OpenPGP, a
+//! [`RevocationCertificate`] is a small signed statement binding a
+//! covenant ID to a [`RevocationReason`] and a timestamp; [`revoke`]
+//! produces one, and [`verify_covenant_with_store`] checks a document
+//! (and every ancestor in its delegation chain) against a [`Store`] for
+//! one before declaring it valid, since a revoked parent must also
+//! invalidate its children.
+//!
+//! [`Store`]: crate::store::Store
+//!
+//! A single [`RevocationCertificate`] is cheap to check against one
+//! store lookup, but gossiping thousands of them between nodes one at a
+//! time doesn't scale. [`RevocationRegistry`] aggregates many covenant
+//! IDs' revocation status into one document: an `explicit` set for
+//! small or sparse registries, and an optional [`RevocationBitmap`] for
+//! dense encoding of a known, ordered batch. [`verify_covenant_with_registry`]
+//! checks a document and its delegation chain against a registry the
+//! same way [`verify_covenant_with_store`] checks against per-covenant
+//! certificates.
+
+use super::{verify_covenant, CovenantDocument, VerificationCheck, VerificationResult};
+use crate::crypto;
+use crate::SteleError;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeSet;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Why a covenant was revoked, mirroring OpenPGP's revocation reason codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RevocationReason {
+    /// Replaced by a newer covenant between the same parties.
+    Superseded,
+    /// The issuer's (or a chain ancestor's) signing key is compromised.
+    KeyCompromise,
+    /// The beneficiary violated the covenant's constraints.
+    PolicyViolation,
+    /// No reason given.
+    Unspecified,
+}
+
+/// A signed statement that a covenant, identified by ID, is revoked.
+///
+/// The signature covers the canonical `(covenantId, reasonCode,
+/// revokedAt)` tuple -- not the covenant document itself, since a
+/// revocation must remain verifiable even if the document is never
+/// re-transmitted alongside it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevocationCertificate {
+    #[serde(rename = "covenantId")]
+    pub covenant_id: String,
+    #[serde(rename = "reasonCode")]
+    pub reason_code: RevocationReason,
+    #[serde(rename = "reasonText")]
+    pub reason_text: String,
+    #[serde(rename = "revokedAt")]
+    pub revoked_at: String,
+    #[serde(rename = "signerPublicKey")]
+    pub signer_public_key: String,
+    pub signature: String,
+    pub alg: crypto::SignatureAlgorithm,
+}
+
+/// Produce the canonical bytes a revocation certificate signs: the JCS
+/// canonicalization of `{covenantId, reasonCode, revokedAt}`.
+fn revocation_signing_bytes(covenant_id: &str, reason_code: RevocationReason, revoked_at: &str) -> String {
+    let payload = serde_json::json!({
+        "covenantId": covenant_id,
+        "reasonCode": reason_code,
+        "revokedAt": revoked_at,
+    });
+    crypto::canonicalize_json(&payload)
+}
+
+/// Revoke `doc`, signed by its issuer.
+///
+/// # Errors
+/// Returns `SteleError::InvalidInput` if `issuer_kp` does not match
+/// `doc.issuer.public_key`, or `SteleError::CryptoError` if signing fails.
+pub fn revoke(
+    doc: &CovenantDocument,
+    issuer_kp: &crypto::KeyPair,
+    reason_code: RevocationReason,
+    reason_text: &str,
+) -> Result<RevocationCertificate, SteleError> {
+    if issuer_kp.public_key_hex != doc.issuer.public_key {
+        return Err(SteleError::InvalidInput(
+            "revoking key does not match the covenant's issuer public key".to_string(),
+        ));
+    }
+
+    let revoked_at = crypto::timestamp();
+    let canonical = revocation_signing_bytes(&doc.id, reason_code, &revoked_at);
+    let sig_bytes = crypto::sign(canonical.as_bytes(), &issuer_kp.signing_key)?;
+
+    Ok(RevocationCertificate {
+        covenant_id: doc.id.clone(),
+        reason_code,
+        reason_text: reason_text.to_string(),
+        revoked_at,
+        signer_public_key: issuer_kp.public_key_hex.clone(),
+        signature: hex::encode(&sig_bytes),
+        alg: crypto::SignatureAlgorithm::Ed25519,
+    })
+}
+
+/// Verify that `revocation` is a valid revocation of `doc`: it names
+/// `doc`'s ID, is signed by `doc`'s own issuer, and the signature checks
+/// out over the canonical `(covenantId, reasonCode, revokedAt)` tuple.
+fn verify_revocation(revocation: &RevocationCertificate, doc: &CovenantDocument) -> bool {
+    if revocation.covenant_id != doc.id || revocation.signer_public_key != doc.issuer.public_key {
+        return false;
+    }
+    let canonical = revocation_signing_bytes(&revocation.covenant_id, revocation.reason_code, &revocation.revoked_at);
+    let sig_bytes = match hex::decode(&revocation.signature) {
+        Ok(b) => b,
+        Err(_) => return false,
+    };
+    crypto::verify_signature(revocation.alg, canonical.as_bytes(), &sig_bytes, &revocation.signer_public_key)
+}
+
+/// Walk `doc`'s delegation chain up to its root, via `store`, returning
+/// `doc` itself followed by each ancestor in ascending-depth order.
+/// Stops (without error) at the first `parentId` not found in `store`.
+#[cfg(feature = "std")]
+fn ancestor_chain<'a, S: crate::store::Store>(doc: &'a CovenantDocument, store: &'a S) -> Vec<&'a CovenantDocument> {
+    let mut chain: Vec<&CovenantDocument> = Vec::new();
+    chain.push(doc);
+    let mut current = doc;
+    while let Some(chain_ref) = &current.chain {
+        match store.get(&chain_ref.parent_id) {
+            Ok(Some(parent)) => {
+                chain.push(parent);
+                current = parent;
+            }
+            _ => break,
+        }
+    }
+    chain
+}
+
+/// Verify `doc` exactly as [`verify_covenant`] does, plus a
+/// `revocation_check`: look up a revocation for `doc` and every ancestor
+/// in its delegation chain (via `store`), failing if any is found and
+/// valid -- so revoking a parent also invalidates its children.
+///
+/// # Errors
+/// Same error conditions as `verify_covenant`.
+#[cfg(feature = "std")]
+pub fn verify_covenant_with_store<S: crate::store::Store>(
+    doc: &CovenantDocument,
+    store: &S,
+) -> Result<VerificationResult, SteleError> {
+    let mut result = verify_covenant(doc)?;
+
+    let chain_docs = ancestor_chain(doc, store);
+    let mut revoked_by: Option<String> = None;
+    for ancestor in &chain_docs {
+        if let Ok(Some(revocation)) = store.get_revocation(&ancestor.id) {
+            if verify_revocation(revocation, ancestor) {
+                revoked_by = Some(ancestor.id.clone());
+                break;
+            }
+        }
+    }
+
+    let not_revoked = revoked_by.is_none();
+    result.checks.push(VerificationCheck {
+        name: "revocation_check".to_string(),
+        passed: not_revoked,
+        message: match &revoked_by {
+            None => "No valid revocation found for this covenant or its delegation chain".to_string(),
+            Some(id) => format!("Covenant is revoked (valid revocation certificate found for {})", id),
+        },
+    });
+    result.valid = result.valid && not_revoked;
+
+    Ok(result)
+}
+
+/// Dense, exact encoding of revocation status over an ordered,
+/// node-agreed universe of covenant IDs. Bit `i` is `1` if
+/// `universe[i]` is revoked. Unlike a Bloom filter this has no false
+/// positives, at the cost of requiring `universe`'s ordering to be
+/// shared out of band -- appropriate when gossiping revocation status
+/// for a known batch of covenants (e.g. everything issued in an epoch),
+/// not for an open-ended, growing set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RevocationBitmap {
+    pub universe: Vec<String>,
+    pub bits: Vec<u8>,
+}
+
+impl RevocationBitmap {
+    /// Create an all-unrevoked bitmap over `universe`.
+    pub fn new(universe: Vec<String>) -> Self {
+        let byte_len = universe.len().saturating_add(7) / 8;
+        RevocationBitmap {
+            universe,
+            bits: vec![0u8; byte_len],
+        }
+    }
+
+    /// Mark `covenant_id` revoked. Returns `false` (no-op) if
+    /// `covenant_id` is not in `self.universe`.
+    pub fn set_revoked(&mut self, covenant_id: &str) -> bool {
+        match self.universe.iter().position(|id| id == covenant_id) {
+            Some(idx) => {
+                self.bits[idx / 8] |= 1 << (idx % 8);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_revoked(&self, covenant_id: &str) -> bool {
+        match self.universe.iter().position(|id| id == covenant_id) {
+            Some(idx) => (self.bits[idx / 8] >> (idx % 8)) & 1 == 1,
+            None => false,
+        }
+    }
+
+    /// Merge `other`'s revoked IDs into `self`, extending `self.universe`
+    /// (and resizing `self.bits`) for any ID `other` covers that `self`
+    /// does not yet.
+    pub fn merge(&mut self, other: &RevocationBitmap) {
+        for id in &other.universe {
+            if other.is_revoked(id) && !self.set_revoked(id) {
+                self.universe.push(id.clone());
+                let byte_len = self.universe.len().saturating_add(7) / 8;
+                if byte_len > self.bits.len() {
+                    self.bits.resize(byte_len, 0);
+                }
+                self.set_revoked(id);
+            }
+        }
+    }
+}
+
+/// An aggregated revocation registry, combining an explicit ID set with
+/// an optional [`RevocationBitmap`] for compact bulk encoding. See the
+/// module docs for why this exists alongside [`RevocationCertificate`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RevocationRegistry {
+    pub explicit: BTreeSet<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitmap: Option<RevocationBitmap>,
+}
+
+impl RevocationRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `covenant_id` as revoked in the explicit set.
+    ///
+    /// For bulk encoding of a known batch, build a [`RevocationBitmap`]
+    /// instead and assign it to `self.bitmap`.
+    pub fn revoke_covenant(&mut self, covenant_id: &str) {
+        self.explicit.insert(covenant_id.to_string());
+    }
+
+    /// Check whether `covenant_id` is revoked per the explicit set or
+    /// the bitmap, if present.
+    pub fn is_revoked(&self, covenant_id: &str) -> bool {
+        self.explicit.contains(covenant_id)
+            || self
+                .bitmap
+                .as_ref()
+                .map(|b| b.is_revoked(covenant_id))
+                .unwrap_or(false)
+    }
+
+    /// Merge `other` into `self`: union the explicit sets, and merge
+    /// `other`'s bitmap into ours (creating one if we don't have one
+    /// yet), so registries gossiped from different nodes converge.
+    pub fn merge_registry(&mut self, other: &RevocationRegistry) {
+        self.explicit.extend(other.explicit.iter().cloned());
+        if let Some(other_bitmap) = &other.bitmap {
+            match &mut self.bitmap {
+                Some(bitmap) => bitmap.merge(other_bitmap),
+                None => self.bitmap = Some(other_bitmap.clone()),
+            }
+        }
+    }
+}
+
+/// Verify `doc` exactly as [`verify_covenant`] does, plus a
+/// `not_revoked` check: consult `registry` for `doc` and every ancestor
+/// in its delegation chain (via `store`), failing if any is found
+/// revoked -- so revoking a parent also invalidates its children.
+///
+/// # Errors
+/// Same error conditions as `verify_covenant`.
+#[cfg(feature = "std")]
+pub fn verify_covenant_with_registry<S: crate::store::Store>(
+    doc: &CovenantDocument,
+    store: &S,
+    registry: &RevocationRegistry,
+) -> Result<VerificationResult, SteleError> {
+    let mut result = verify_covenant(doc)?;
+
+    let chain_docs = ancestor_chain(doc, store);
+    let revoked_by = chain_docs
+        .iter()
+        .find(|ancestor| registry.is_revoked(&ancestor.id))
+        .map(|ancestor| ancestor.id.clone());
+
+    let not_revoked = revoked_by.is_none();
+    result.checks.push(VerificationCheck {
+        name: "not_revoked".to_string(),
+        passed: not_revoked,
+        message: match &revoked_by {
+            None => "No revocation found in the registry for this covenant or its delegation chain".to_string(),
+            Some(id) => format!("Covenant is revoked per the registry (entry found for {})", id),
+        },
+    });
+    result.valid = result.valid && not_revoked;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covenant::{build_covenant, CovenantBuilderOptions, ChainReference, Party};
+    use crate::store::{MemoryStore, Store};
+
+    fn make_covenant(chain: Option<ChainReference>) -> (CovenantDocument, crypto::KeyPair) {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary_kp = crypto::generate_key_pair().unwrap();
+        let issuer = Party {
+            id: "issuer-1".to_string(),
+            public_key: issuer_kp.public_key_hex.clone(),
+            role: "issuer".to_string(),
+        };
+        let beneficiary = Party {
+            id: "beneficiary-1".to_string(),
+            public_key: beneficiary_kp.public_key_hex,
+            role: "beneficiary".to_string(),
+        };
+        let doc = build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key,
+            chain,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+        (doc, issuer_kp)
+    }
+
+    #[test]
+    fn test_revoke_and_verify_with_store() {
+        let (doc, issuer_kp) = make_covenant(None);
+        let mut store = MemoryStore::new();
+        store.put(&doc.id.clone(), doc.clone()).unwrap();
+
+        let revocation = revoke(&doc, &issuer_kp, RevocationReason::PolicyViolation, "beneficiary violated read scope").unwrap();
+        store.put_revocation(revocation).unwrap();
+
+        let result = verify_covenant_with_store(&doc, &store).unwrap();
+        assert!(!result.valid);
+        assert!(result.checks.iter().any(|c| c.name == "revocation_check" && !c.passed));
+    }
+
+    #[test]
+    fn test_unrevoked_covenant_passes() {
+        let (doc, _issuer_kp) = make_covenant(None);
+        let store = MemoryStore::new();
+
+        let result = verify_covenant_with_store(&doc, &store).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_revoking_parent_invalidates_child() {
+        let (parent, parent_kp) = make_covenant(None);
+        let mut store = MemoryStore::new();
+        store.put(&parent.id.clone(), parent.clone()).unwrap();
+
+        let (child, _child_kp) = make_covenant(Some(ChainReference {
+            parent_id: parent.id.clone(),
+            relation: "delegation".to_string(),
+            depth: 1,
+        }));
+        store.put(&child.id.clone(), child.clone()).unwrap();
+
+        let revocation = revoke(&parent, &parent_kp, RevocationReason::KeyCompromise, "issuer key leaked").unwrap();
+        store.put_revocation(revocation).unwrap();
+
+        let result = verify_covenant_with_store(&child, &store).unwrap();
+        assert!(!result.valid, "Child should be invalidated by parent revocation");
+    }
+
+    #[test]
+    fn test_revoke_rejects_wrong_key() {
+        let (doc, _issuer_kp) = make_covenant(None);
+        let impostor_kp = crypto::generate_key_pair().unwrap();
+        let result = revoke(&doc, &impostor_kp, RevocationReason::Unspecified, "");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_registry_explicit_revocation() {
+        let (doc, _issuer_kp) = make_covenant(None);
+        let mut store = MemoryStore::new();
+        store.put(&doc.id.clone(), doc.clone()).unwrap();
+
+        let mut registry = RevocationRegistry::new();
+        assert!(!registry.is_revoked(&doc.id));
+        registry.revoke_covenant(&doc.id);
+        assert!(registry.is_revoked(&doc.id));
+
+        let result = verify_covenant_with_registry(&doc, &store, &registry).unwrap();
+        assert!(!result.valid);
+        assert!(result.checks.iter().any(|c| c.name == "not_revoked" && !c.passed));
+    }
+
+    #[test]
+    fn test_registry_bitmap_revocation() {
+        let (doc, _issuer_kp) = make_covenant(None);
+        let mut store = MemoryStore::new();
+        store.put(&doc.id.clone(), doc.clone()).unwrap();
+
+        let mut bitmap = RevocationBitmap::new(vec![doc.id.clone(), "other-covenant".to_string()]);
+        bitmap.set_revoked(&doc.id);
+        let mut registry = RevocationRegistry::new();
+        registry.bitmap = Some(bitmap);
+
+        let result = verify_covenant_with_registry(&doc, &store, &registry).unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_registry_merge_unions_explicit_and_bitmap() {
+        let mut a = RevocationRegistry::new();
+        a.revoke_covenant("covenant-a");
+
+        let mut b = RevocationRegistry::new();
+        let mut bitmap = RevocationBitmap::new(vec!["covenant-b".to_string()]);
+        bitmap.set_revoked("covenant-b");
+        b.bitmap = Some(bitmap);
+
+        a.merge_registry(&b);
+
+        assert!(a.is_revoked("covenant-a"));
+        assert!(a.is_revoked("covenant-b"));
+    }
+
+    #[test]
+    fn test_unrevoked_covenant_passes_registry_check() {
+        let (doc, _issuer_kp) = make_covenant(None);
+        let store = MemoryStore::new();
+        let registry = RevocationRegistry::new();
+
+        let result = verify_covenant_with_registry(&doc, &store, &registry).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+    }
+}
@@ -0,0 +1,178 @@
+//! Signed timestamp tokens binding a covenant to a trusted clock.
+//!
+//! `expiresAt`/`activatesAt` are only meaningful relative to a "now" the
+//! verifier can trust, but a verifier's local system clock is exactly the
+//! kind of thing an attacker controls. Analogous to a Signed Certificate
+//! Timestamp, [`timestamp_covenant`] has a timestamp authority (TSA) sign
+//! the covenant's own digest together with a Unix time, producing a
+//! [`TimestampToken`] stored on the document as `timestampToken`.
+//! [`verify_covenant`](super::verify_covenant) checks the TSA's signature
+//! (step 12, `timestamp`) and, when it verifies, evaluates the
+//! `not_expired`/`active` checks against the attested time instead of the
+//! local clock -- so a covenant's validity window can be checked
+//! correctly even on a machine whose clock is wrong or untrusted.
+
+use super::{canonical_form, CovenantDocument};
+use crate::crypto;
+use crate::SteleError;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+
+/// A TSA's signed statement that a covenant existed, in the form given by
+/// its digest, at a particular Unix time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimestampToken {
+    #[serde(rename = "tsaPublicKey")]
+    pub tsa_public_key: String,
+    #[serde(rename = "unixTime")]
+    pub unix_time: i64,
+    /// SHA-256 of the covenant's own canonical form at the time of
+    /// timestamping, so the token stays bound to this exact document.
+    pub digest: String,
+    pub signature: String,
+    pub alg: crypto::SignatureAlgorithm,
+}
+
+/// Produce the canonical bytes a timestamp token signs: the JCS
+/// canonicalization of `{digest, unixTime}`.
+fn timestamp_signing_bytes(digest: &str, unix_time: i64) -> String {
+    let payload = serde_json::json!({
+        "digest": digest,
+        "unixTime": unix_time,
+    });
+    crypto::canonicalize_json(&payload)
+}
+
+/// Have `tsa_kp` timestamp `doc`: sign its canonical form's digest
+/// together with the current Unix time, and return a copy of `doc` with
+/// the resulting [`TimestampToken`] attached.
+///
+/// # Errors
+/// Returns `SteleError::SerializationError` if `doc`'s canonical form
+/// can't be computed, or `SteleError::CryptoError` if signing fails.
+pub fn timestamp_covenant(doc: &CovenantDocument, tsa_kp: &crypto::KeyPair) -> Result<CovenantDocument, SteleError> {
+    let canonical = canonical_form(doc)?;
+    let digest = crypto::sha256_hex(canonical.as_bytes());
+    let unix_time = chrono::Utc::now().timestamp();
+
+    let signing_bytes = timestamp_signing_bytes(&digest, unix_time);
+    let sig_bytes = crypto::sign(signing_bytes.as_bytes(), &tsa_kp.signing_key)?;
+
+    let mut new_doc = doc.clone();
+    new_doc.timestamp_token = Some(TimestampToken {
+        tsa_public_key: tsa_kp.public_key_hex.clone(),
+        unix_time,
+        digest,
+        signature: hex::encode(&sig_bytes),
+        alg: crypto::SignatureAlgorithm::Ed25519,
+    });
+    Ok(new_doc)
+}
+
+/// Verify that `token` names `doc`'s own digest and checks out over the
+/// canonical `(digest, unixTime)` tuple.
+fn verify_timestamp_token(token: &TimestampToken, doc: &CovenantDocument) -> bool {
+    let canonical = match canonical_form(doc) {
+        Ok(c) => c,
+        Err(_) => return false,
+    };
+    if token.digest != crypto::sha256_hex(canonical.as_bytes()) {
+        return false;
+    }
+    let signing_bytes = timestamp_signing_bytes(&token.digest, token.unix_time);
+    let sig_bytes = match hex::decode(&token.signature) {
+        Ok(b) => b,
+        Err(_) => return false,
+    };
+    crypto::verify_signature(token.alg, signing_bytes.as_bytes(), &sig_bytes, &token.tsa_public_key)
+}
+
+/// The TSA-attested time for `doc`, if it carries a `timestamp_token`
+/// that verifies against it. `None` if there is no token, or it fails
+/// verification -- callers (namely [`verify_covenant`](super::verify_covenant))
+/// should fall back to the system clock in either case.
+pub(crate) fn attested_time(doc: &CovenantDocument) -> Option<chrono::DateTime<chrono::Utc>> {
+    let token = doc.timestamp_token.as_ref()?;
+    if !verify_timestamp_token(token, doc) {
+        return None;
+    }
+    chrono::DateTime::from_timestamp(token.unix_time, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covenant::{build_covenant, verify_covenant, CovenantBuilderOptions, Party};
+
+    fn make_covenant(expires_at: Option<String>, activates_at: Option<String>) -> CovenantDocument {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary_kp = crypto::generate_key_pair().unwrap();
+        build_covenant(CovenantBuilderOptions {
+            issuer: Party { id: "issuer-1".to_string(), public_key: issuer_kp.public_key_hex.clone(), role: "issuer".to_string() },
+            beneficiary: Party { id: "beneficiary-1".to_string(), public_key: beneficiary_kp.public_key_hex, role: "beneficiary".to_string() },
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key,
+            chain: None,
+            expires_at,
+            activates_at,
+            metadata: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_timestamp_covenant_roundtrip() {
+        let doc = make_covenant(None, None);
+        let tsa_kp = crypto::generate_key_pair().unwrap();
+        let timestamped = timestamp_covenant(&doc, &tsa_kp).unwrap();
+
+        assert!(timestamped.timestamp_token.is_some());
+        assert!(verify_timestamp_token(timestamped.timestamp_token.as_ref().unwrap(), &timestamped));
+
+        let result = verify_covenant(&timestamped).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+        let check = result.checks.iter().find(|c| c.name == "timestamp").unwrap();
+        assert!(check.message.contains("TSA-attested time"), "message was: {}", check.message);
+    }
+
+    #[test]
+    fn test_no_timestamp_token_passes_using_system_clock() {
+        let doc = make_covenant(None, None);
+        let result = verify_covenant(&doc).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+        let check = result.checks.iter().find(|c| c.name == "timestamp").unwrap();
+        assert!(check.message.contains("local system clock"), "message was: {}", check.message);
+    }
+
+    #[test]
+    fn test_tampered_timestamp_token_fails() {
+        let doc = make_covenant(None, None);
+        let tsa_kp = crypto::generate_key_pair().unwrap();
+        let mut timestamped = timestamp_covenant(&doc, &tsa_kp).unwrap();
+        timestamped.timestamp_token.as_mut().unwrap().signature = "00".repeat(64);
+
+        let result = verify_covenant(&timestamped).unwrap();
+        assert!(!result.valid);
+        let check = result.checks.iter().find(|c| c.name == "timestamp").unwrap();
+        assert!(!check.passed);
+    }
+
+    #[test]
+    fn test_attested_time_overrides_expiry_evaluation() {
+        // Expired by the system clock (expiresAt 2020), but the attested
+        // time (2019) is still within the validity window.
+        let doc = make_covenant(Some("2020-01-01T00:00:00Z".to_string()), Some("2010-01-01T00:00:00Z".to_string()));
+        let tsa_kp = crypto::generate_key_pair().unwrap();
+        let mut timestamped = timestamp_covenant(&doc, &tsa_kp).unwrap();
+        let token = timestamped.timestamp_token.as_mut().unwrap();
+        token.unix_time = 1_546_300_800; // 2019-01-01T00:00:00Z
+        let signing_bytes = timestamp_signing_bytes(&token.digest, token.unix_time);
+        token.signature = hex::encode(crypto::sign(signing_bytes.as_bytes(), &tsa_kp.signing_key).unwrap());
+
+        let result = verify_covenant(&timestamped).unwrap();
+        assert!(result.checks.iter().find(|c| c.name == "not_expired").unwrap().passed);
+        assert!(result.checks.iter().find(|c| c.name == "active").unwrap().passed);
+    }
+}
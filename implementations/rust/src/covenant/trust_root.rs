@@ -0,0 +1,340 @@
+//! TUF-inspired trust root for issuer key distribution and rotation.
+//!
+//! [`verify_covenant`] trusts whatever key is embedded in `issuer.publicKey`
+//! -- a verifier has to already know, out of band, that this is really the
+//! issuer's key. Borrowing the root-of-trust model from The Update
+//! Framework, a [`TrustRoot`] is a signed, versioned document mapping
+//! issuer IDs to their currently valid public keys, carrying its own
+//! expiration and a `threshold` of `rootKeys` that must sign any update.
+//! [`verify_covenant_with_trust_root`] resolves the issuer's key from a
+//! `TrustRoot` instead of the document, and fails if the root is expired
+//! or the issuer's key isn't (or is no longer) listed in it.
+//!
+//! [`TrustRoot::update`] is how a fleet of verifiers rotates issuer keys
+//! safely: a new, higher-versioned `TrustRoot` is only accepted if it
+//! carries a `threshold` of valid signatures from the *prior* root's own
+//! `rootKeys`, so a single compromised issuer key (or even a single root
+//! key) can't rewrite the trust root on its own.
+//!
+//! [`verify_covenant`]: super::verify_covenant
+
+use super::{canonical_form, parse_timestamp, verify_covenant, CovenantDocument, VerificationResult};
+use crate::crypto;
+use crate::SteleError;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(not(feature = "std"))]
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// A root key's signature over a [`TrustRoot`]'s canonical form.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootSignature {
+    #[serde(rename = "signerPublicKey")]
+    pub signer_public_key: String,
+    pub signature: String,
+}
+
+/// A signed, versioned mapping of issuer IDs to their currently valid
+/// public keys. See the module docs for how [`TrustRoot::update`] governs
+/// rotation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrustRoot {
+    pub version: u64,
+    pub issuers: BTreeMap<String, String>,
+    #[serde(rename = "rootKeys")]
+    pub root_keys: Vec<String>,
+    pub threshold: usize,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: String,
+    #[serde(default)]
+    pub signatures: Vec<RootSignature>,
+}
+
+impl TrustRoot {
+    /// Create a new, unsigned trust root. Callers sign it with
+    /// [`Self::sign`] (once per root key) before distributing it.
+    pub fn new(version: u64, issuers: BTreeMap<String, String>, root_keys: Vec<String>, threshold: usize, expires_at: String) -> Self {
+        TrustRoot { version, issuers, root_keys, threshold, expires_at, signatures: Vec::new() }
+    }
+
+    /// Canonical bytes a root key signs: the JCS canonicalization of
+    /// every field except `signatures` itself.
+    fn signing_bytes(&self) -> String {
+        let payload = serde_json::json!({
+            "version": self.version,
+            "issuers": self.issuers,
+            "rootKeys": self.root_keys,
+            "threshold": self.threshold,
+            "expiresAt": self.expires_at,
+        });
+        crypto::canonicalize_json(&payload)
+    }
+
+    /// Add `kp`'s signature over this root's canonical form.
+    ///
+    /// # Errors
+    /// Returns `SteleError::InvalidInput` if `kp` is not one of
+    /// `self.root_keys`, or `SteleError::CryptoError` if signing fails.
+    pub fn sign(&mut self, kp: &crypto::KeyPair) -> Result<(), SteleError> {
+        if !self.root_keys.iter().any(|k| k == &kp.public_key_hex) {
+            return Err(SteleError::InvalidInput(
+                "signing key is not one of this trust root's root keys".to_string(),
+            ));
+        }
+        let canonical = self.signing_bytes();
+        let sig_bytes = crypto::sign(canonical.as_bytes(), &kp.signing_key)?;
+        self.signatures.push(RootSignature {
+            signer_public_key: kp.public_key_hex.clone(),
+            signature: hex::encode(&sig_bytes),
+        });
+        Ok(())
+    }
+
+    /// Count how many distinct signers in `candidate_keys` have a valid
+    /// Ed25519 signature in `self.signatures` over this root's canonical
+    /// form; a key that signed more than once counts once.
+    fn valid_signature_count_against(&self, candidate_keys: &[String]) -> usize {
+        let canonical = self.signing_bytes();
+        self.signatures
+            .iter()
+            .filter(|sig| {
+                candidate_keys.iter().any(|k| k == &sig.signer_public_key)
+                    && hex::decode(&sig.signature)
+                        .map(|b| {
+                            crypto::verify_signature(
+                                crypto::SignatureAlgorithm::Ed25519,
+                                canonical.as_bytes(),
+                                &b,
+                                &sig.signer_public_key,
+                            )
+                        })
+                        .unwrap_or(false)
+            })
+            .map(|sig| sig.signer_public_key.as_str())
+            .collect::<BTreeSet<&str>>()
+            .len()
+    }
+
+    /// Whether `self.threshold` of `self.root_keys` have validly signed
+    /// this root's own canonical form.
+    pub fn is_self_consistent(&self) -> bool {
+        self.valid_signature_count_against(&self.root_keys) >= self.threshold
+    }
+
+    /// Whether `self.expires_at` has passed.
+    pub fn is_expired(&self) -> bool {
+        parse_timestamp(&self.expires_at).map(|exp| chrono::Utc::now() >= exp).unwrap_or(false)
+    }
+
+    /// Resolve `issuer_id`'s currently valid public key, if listed.
+    pub fn resolve_issuer_key(&self, issuer_id: &str) -> Option<&str> {
+        self.issuers.get(issuer_id).map(|s| s.as_str())
+    }
+
+    /// Accept `new_root` as this root's replacement.
+    ///
+    /// # Errors
+    /// Returns `SteleError::InvalidInput` if `new_root.version` is not
+    /// strictly greater than `self.version`, or if fewer than
+    /// `self.threshold` of `self.root_keys` have validly signed
+    /// `new_root`'s canonical form.
+    pub fn update(&self, new_root: &TrustRoot) -> Result<TrustRoot, SteleError> {
+        if new_root.version <= self.version {
+            return Err(SteleError::InvalidInput(format!(
+                "new trust root version {} must be greater than the current version {}",
+                new_root.version, self.version
+            )));
+        }
+
+        let valid_count = new_root.valid_signature_count_against(&self.root_keys);
+        if valid_count < self.threshold {
+            return Err(SteleError::InvalidInput(format!(
+                "new trust root has only {} valid signature(s) from prior root keys, but {} are required",
+                valid_count, self.threshold
+            )));
+        }
+
+        Ok(new_root.clone())
+    }
+}
+
+/// Verify `doc` exactly as [`verify_covenant`] does, then re-check the
+/// issuer signature by resolving `issuer.id`'s key from `trust_root`
+/// instead of trusting `doc.issuer.publicKey`. Overwrites the
+/// `signature_valid` check in place: it fails if `trust_root` is
+/// expired, if `issuer.id` isn't listed in it, or if the document's
+/// signature doesn't verify against the resolved key (e.g. because the
+/// key it was actually signed with has since been rotated out).
+///
+/// # Errors
+/// Same error conditions as `verify_covenant`.
+pub fn verify_covenant_with_trust_root(doc: &CovenantDocument, trust_root: &TrustRoot) -> Result<VerificationResult, SteleError> {
+    let mut result = verify_covenant(doc)?;
+
+    if trust_root.is_expired() {
+        if let Some(check) = result.checks.iter_mut().find(|c| c.name == "signature_valid") {
+            check.passed = false;
+            check.message = format!("Trust root is expired (expiresAt: {})", trust_root.expires_at);
+        }
+        result.valid = result.checks.iter().all(|c| c.passed);
+        return Ok(result);
+    }
+
+    let canonical = canonical_form(doc)?;
+    let resolved_key = trust_root.resolve_issuer_key(&doc.issuer.id);
+    let sig_valid = match resolved_key {
+        Some(key_hex) => {
+            let sig_bytes = hex::decode(&doc.signature).unwrap_or_default();
+            crypto::verify_signature(doc.alg, canonical.as_bytes(), &sig_bytes, key_hex)
+        }
+        None => false,
+    };
+
+    if let Some(check) = result.checks.iter_mut().find(|c| c.name == "signature_valid") {
+        check.passed = sig_valid;
+        check.message = if sig_valid {
+            "Issuer signature is valid (verified via trust root)".to_string()
+        } else if resolved_key.is_none() {
+            format!("issuer `{}` not found in trust root (key may have been rotated out)", doc.issuer.id)
+        } else {
+            "verification failed: issuer signature does not match the trust root's current key".to_string()
+        };
+    }
+    result.valid = result.checks.iter().all(|c| c.passed);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covenant::{build_covenant, CovenantBuilderOptions, Party};
+
+    fn make_covenant(issuer_kp: &crypto::KeyPair) -> CovenantDocument {
+        let beneficiary_kp = crypto::generate_key_pair().unwrap();
+        build_covenant(CovenantBuilderOptions {
+            issuer: Party { id: "issuer-1".to_string(), public_key: issuer_kp.public_key_hex.clone(), role: "issuer".to_string() },
+            beneficiary: Party { id: "beneficiary-1".to_string(), public_key: beneficiary_kp.public_key_hex, role: "beneficiary".to_string() },
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key.clone(),
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap()
+    }
+
+    fn make_trust_root(issuer_kp: &crypto::KeyPair, root_kps: &[crypto::KeyPair], threshold: usize) -> TrustRoot {
+        let mut issuers = BTreeMap::new();
+        issuers.insert("issuer-1".to_string(), issuer_kp.public_key_hex.clone());
+        let root_keys: Vec<String> = root_kps.iter().map(|kp| kp.public_key_hex.clone()).collect();
+        let mut root = TrustRoot::new(1, issuers, root_keys, threshold, "2099-01-01T00:00:00Z".to_string());
+        for kp in root_kps {
+            root.sign(kp).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_verify_covenant_with_trust_root_valid() {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let root_kps = vec![crypto::generate_key_pair().unwrap(), crypto::generate_key_pair().unwrap()];
+        let doc = make_covenant(&issuer_kp);
+        let trust_root = make_trust_root(&issuer_kp, &root_kps, 2);
+
+        assert!(trust_root.is_self_consistent());
+        let result = verify_covenant_with_trust_root(&doc, &trust_root).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_verify_covenant_with_trust_root_reports_unknown_issuer() {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let root_kps = vec![crypto::generate_key_pair().unwrap()];
+        let doc = make_covenant(&issuer_kp);
+        let trust_root = TrustRoot::new(1, BTreeMap::new(), vec![root_kps[0].public_key_hex.clone()], 1, "2099-01-01T00:00:00Z".to_string());
+
+        let result = verify_covenant_with_trust_root(&doc, &trust_root).unwrap();
+        assert!(!result.valid);
+        let check = result.checks.iter().find(|c| c.name == "signature_valid").unwrap();
+        assert!(check.message.contains("not found in trust root"), "message was: {}", check.message);
+    }
+
+    #[test]
+    fn test_verify_covenant_with_trust_root_reports_expired_root() {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let root_kps = vec![crypto::generate_key_pair().unwrap()];
+        let doc = make_covenant(&issuer_kp);
+        let mut trust_root = make_trust_root(&issuer_kp, &root_kps, 1);
+        trust_root.expires_at = "2000-01-01T00:00:00Z".to_string();
+
+        let result = verify_covenant_with_trust_root(&doc, &trust_root).unwrap();
+        assert!(!result.valid);
+        let check = result.checks.iter().find(|c| c.name == "signature_valid").unwrap();
+        assert!(check.message.contains("expired"), "message was: {}", check.message);
+    }
+
+    #[test]
+    fn test_rotated_out_key_fails_verification() {
+        let old_issuer_kp = crypto::generate_key_pair().unwrap();
+        let new_issuer_kp = crypto::generate_key_pair().unwrap();
+        let root_kps = vec![crypto::generate_key_pair().unwrap()];
+        let doc = make_covenant(&old_issuer_kp);
+        // The trust root now maps `issuer-1` to a *different* key.
+        let trust_root = make_trust_root(&new_issuer_kp, &root_kps, 1);
+
+        let result = verify_covenant_with_trust_root(&doc, &trust_root).unwrap();
+        assert!(!result.valid);
+        let check = result.checks.iter().find(|c| c.name == "signature_valid").unwrap();
+        assert!(check.message.contains("does not match the trust root's current key"), "message was: {}", check.message);
+    }
+
+    #[test]
+    fn test_update_accepts_monotonic_version_with_threshold_signatures() {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let root_kps = vec![crypto::generate_key_pair().unwrap(), crypto::generate_key_pair().unwrap()];
+        let root_v1 = make_trust_root(&issuer_kp, &root_kps, 2);
+
+        let mut issuers = BTreeMap::new();
+        issuers.insert("issuer-1".to_string(), issuer_kp.public_key_hex.clone());
+        let mut root_v2 = TrustRoot::new(2, issuers, root_v1.root_keys.clone(), 2, "2099-06-01T00:00:00Z".to_string());
+        root_v2.sign(&root_kps[0]).unwrap();
+        root_v2.sign(&root_kps[1]).unwrap();
+
+        let updated = root_v1.update(&root_v2).unwrap();
+        assert_eq!(updated.version, 2);
+    }
+
+    #[test]
+    fn test_update_rejects_non_monotonic_version() {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let root_kps = vec![crypto::generate_key_pair().unwrap()];
+        let root_v1 = make_trust_root(&issuer_kp, &root_kps, 1);
+        let root_v1_again = make_trust_root(&issuer_kp, &root_kps, 1);
+
+        assert!(root_v1.update(&root_v1_again).is_err());
+    }
+
+    #[test]
+    fn test_update_rejects_insufficient_prior_signatures() {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let root_kps = vec![crypto::generate_key_pair().unwrap(), crypto::generate_key_pair().unwrap()];
+        let root_v1 = make_trust_root(&issuer_kp, &root_kps, 2);
+
+        let mut issuers = BTreeMap::new();
+        issuers.insert("issuer-1".to_string(), issuer_kp.public_key_hex.clone());
+        let mut root_v2 = TrustRoot::new(2, issuers, root_v1.root_keys.clone(), 2, "2099-06-01T00:00:00Z".to_string());
+        // Only one of the two required prior root keys signs.
+        root_v2.sign(&root_kps[0]).unwrap();
+
+        assert!(root_v1.update(&root_v2).is_err());
+    }
+}
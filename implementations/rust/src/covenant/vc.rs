@@ -0,0 +1,623 @@
+//! W3C Verifiable Credential and standards-compliant compact JWT export.
+//!
+//! Unlike [`jws::to_jws`](super::jws::to_jws), which re-presents a covenant
+//! as a Nobulex-flavored JWS only this crate's own [`jws::from_jws`](super::jws::from_jws)
+//! understands, [`to_verifiable_credential`] and [`to_jws_compact`] target
+//! existing DID/VC and JWT verifier tooling. The former wraps the covenant
+//! as a `credentialSubject`, promotes `issuer.id` to `issuer` and
+//! `created_at`/`expires_at` to `issuanceDate`/`expirationDate`, and
+//! carries the signature as a `DataIntegrityProof` (`eddsa-jcs-2022`)
+//! computed over the same JCS [`canonical_form`](super::canonical_form)
+//! [`verify_covenant`](super::verify_covenant) already checks, so the hash
+//! is unchanged. The latter emits a compact `header.payload.signature`
+//! JWT with registered claims (`iss`, `sub`, `nbf`, `exp`, `jti`) plus the
+//! CCL constraints as a `ccl` claim, reusing the same detached signature
+//! rather than signing the JWT's own `header.payload` bytes -- so, like
+//! [`jws::to_jws`](super::jws::to_jws), it can't be re-signed without the
+//! issuer's private key.
+//!
+//! Both forms are Ed25519-only: `eddsa-jcs-2022` and the JWT `"alg":
+//! "EdDSA"` header only name that algorithm, so covenants signed with
+//! ECDSA P-256 or RSA-2048 are rejected rather than silently mislabeled.
+//! Use [`jws::to_jws`](super::jws::to_jws) for those instead.
+//!
+//! The compact JWT's registered claims are a narrowed view of a covenant
+//! -- there's no room for `issuer.publicKey`, `nonce`, `beneficiary`, or
+//! `chain` -- so [`from_jws_compact`] decodes it into [`JwtClaims`]
+//! rather than a full [`CovenantDocument`]; see its docs for how callers
+//! are expected to verify it against the original document.
+//!
+//! [`to_jwt_vc`] goes one step further, for tooling that expects the
+//! standard "JWT-encoded Verifiable Credential" shape: the whole
+//! credential (including `credentialSubject`) travels as a `vc` claim,
+//! and the JWT's own signature -- over `header.payload`, not the
+//! covenant's detached `signature` field -- names the signing key via a
+//! `kid` header, so the token verifies on its own with no document
+//! lookup. [`from_jwt_vc`] decodes one back into a [`CovenantDocument`]
+//! without checking either signature (mirroring [`from_jws_compact`]);
+//! [`verify_jwt_vc`] does, checking the outer JWT signature against
+//! `kid` and then the embedded covenant's own signature the same way
+//! [`from_verifiable_credential`] does.
+
+use super::jws::{base64url_decode, base64url_encode};
+use super::{canonical_form, parse_timestamp, CovenantDocument};
+use crate::crypto;
+use crate::SteleError;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JwtHeader {
+    alg: String,
+    typ: String,
+}
+
+fn require_ed25519(doc: &CovenantDocument, fn_name: &str) -> Result<(), SteleError> {
+    if doc.alg != crypto::SignatureAlgorithm::Ed25519 {
+        return Err(SteleError::InvalidInput(format!(
+            "{} only supports Ed25519-signed covenants",
+            fn_name
+        )));
+    }
+    Ok(())
+}
+
+/// Export `doc` as a W3C Verifiable Credential. See the module docs for
+/// how the `proof` differs from a general-purpose Data Integrity Proof.
+///
+/// # Errors
+/// Returns `SteleError::InvalidInput` if `doc.alg` is not `Ed25519`, or
+/// `SteleError::SerializationError` if the document fails to serialize.
+pub fn to_verifiable_credential(doc: &CovenantDocument) -> Result<String, SteleError> {
+    require_ed25519(doc, "to_verifiable_credential")?;
+
+    let subject = serde_json::to_value(doc)
+        .map_err(|e| SteleError::SerializationError(format!("failed to serialize covenant: {}", e)))?;
+
+    let mut vc = serde_json::json!({
+        "@context": [
+            "https://www.w3.org/2018/credentials/v1",
+            "https://w3id.org/security/data-integrity/v2"
+        ],
+        "id": format!("urn:stele:covenant:{}", doc.id),
+        "type": ["VerifiableCredential", "SteleCovenant"],
+        "issuer": doc.issuer.id,
+        "issuanceDate": doc.created_at,
+        "credentialSubject": subject,
+        "proof": {
+            "type": "DataIntegrityProof",
+            "cryptosuite": "eddsa-jcs-2022",
+            "proofPurpose": "assertionMethod",
+            "verificationMethod": doc.issuer.public_key,
+            "proofValue": doc.signature,
+        },
+    });
+
+    if let Some(expires_at) = &doc.expires_at {
+        vc["expirationDate"] = serde_json::Value::String(expires_at.clone());
+    }
+
+    serde_json::to_string_pretty(&vc)
+        .map_err(|e| SteleError::SerializationError(format!("failed to serialize verifiable credential: {}", e)))
+}
+
+/// Parse a Verifiable Credential produced by [`to_verifiable_credential`],
+/// reconstructing the embedded `CovenantDocument` and verifying its proof.
+///
+/// # Errors
+/// Returns `SteleError::SerializationError` if `json` is not a
+/// well-formed credential of this shape, or `SteleError::VerificationFailed`
+/// if the promoted `issuer`/`issuanceDate` fields disagree with
+/// `credentialSubject`, or the proof's signature does not verify.
+pub fn from_verifiable_credential(json: &str) -> Result<CovenantDocument, SteleError> {
+    let vc: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| SteleError::SerializationError(format!("invalid verifiable credential JSON: {}", e)))?;
+
+    let proof = vc
+        .get("proof")
+        .ok_or_else(|| SteleError::SerializationError("verifiable credential is missing `proof`".to_string()))?;
+    let cryptosuite = proof.get("cryptosuite").and_then(|v| v.as_str()).unwrap_or_default();
+    if cryptosuite != "eddsa-jcs-2022" {
+        return Err(SteleError::InvalidInput(format!("unsupported proof cryptosuite: {}", cryptosuite)));
+    }
+    let proof_value = proof
+        .get("proofValue")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| SteleError::SerializationError("proof is missing `proofValue`".to_string()))?
+        .to_string();
+
+    let subject = vc
+        .get("credentialSubject")
+        .cloned()
+        .ok_or_else(|| SteleError::SerializationError("verifiable credential is missing `credentialSubject`".to_string()))?;
+    let mut doc: CovenantDocument = serde_json::from_value(subject)
+        .map_err(|e| SteleError::SerializationError(format!("failed to reconstruct covenant from credentialSubject: {}", e)))?;
+    require_ed25519(&doc, "from_verifiable_credential")?;
+
+    let vc_issuer = vc.get("issuer").and_then(|v| v.as_str()).unwrap_or_default();
+    if vc_issuer != doc.issuer.id {
+        return Err(SteleError::VerificationFailed(format!(
+            "credential issuer `{}` does not match credentialSubject issuer id `{}`",
+            vc_issuer, doc.issuer.id
+        )));
+    }
+    let vc_issuance_date = vc.get("issuanceDate").and_then(|v| v.as_str()).unwrap_or_default();
+    if vc_issuance_date != doc.created_at {
+        return Err(SteleError::VerificationFailed(
+            "credential issuanceDate does not match credentialSubject createdAt".to_string(),
+        ));
+    }
+    if !crypto::constant_time_equal(proof_value.as_bytes(), doc.signature.as_bytes()) {
+        return Err(SteleError::VerificationFailed(
+            "proof proofValue does not match credentialSubject signature".to_string(),
+        ));
+    }
+
+    let canonical = canonical_form(&doc)
+        .map_err(|e| SteleError::SerializationError(format!("failed to compute canonical form: {}", e)))?;
+    let sig_bytes = hex::decode(&doc.signature)
+        .map_err(|e| SteleError::SerializationError(format!("signature is not valid hex: {}", e)))?;
+    if !crypto::verify_signature(crypto::SignatureAlgorithm::Ed25519, canonical.as_bytes(), &sig_bytes, &doc.issuer.public_key) {
+        return Err(SteleError::VerificationFailed(
+            "verifiable credential proof failed signature verification".to_string(),
+        ));
+    }
+
+    doc.id = crypto::sha256_string(&canonical);
+    Ok(doc)
+}
+
+/// Export `doc` as a compact JWT: `base64url(header).base64url(payload).base64url(signature)`.
+/// See the module docs for the claim mapping and why the signature is
+/// detached rather than computed over `header.payload`.
+///
+/// # Errors
+/// Returns `SteleError::InvalidInput` if `doc.alg` is not `Ed25519`, or
+/// `SteleError::SerializationError` if `doc.activatesAt`/`expiresAt` are
+/// not valid timestamps or the document fails to serialize.
+pub fn to_jws_compact(doc: &CovenantDocument) -> Result<String, SteleError> {
+    require_ed25519(doc, "to_jws_compact")?;
+
+    let header = JwtHeader { alg: "EdDSA".to_string(), typ: "JWT".to_string() };
+    let header_json = serde_json::to_string(&header)
+        .map_err(|e| SteleError::SerializationError(format!("failed to serialize JWT header: {}", e)))?;
+    let header_b64 = base64url_encode(header_json.as_bytes());
+
+    let mut payload = serde_json::json!({
+        "iss": doc.issuer.id,
+        "sub": doc.beneficiary.id,
+        "jti": doc.id,
+        "ccl": doc.constraints,
+    });
+    if let Some(activates_at) = &doc.activates_at {
+        let nbf = parse_timestamp(activates_at)
+            .ok_or_else(|| SteleError::SerializationError("activatesAt is not a valid timestamp".to_string()))?
+            .timestamp();
+        payload["nbf"] = serde_json::json!(nbf);
+    }
+    if let Some(expires_at) = &doc.expires_at {
+        let exp = parse_timestamp(expires_at)
+            .ok_or_else(|| SteleError::SerializationError("expiresAt is not a valid timestamp".to_string()))?
+            .timestamp();
+        payload["exp"] = serde_json::json!(exp);
+    }
+
+    let payload_json = serde_json::to_string(&payload)
+        .map_err(|e| SteleError::SerializationError(format!("failed to serialize JWT payload: {}", e)))?;
+    let payload_b64 = base64url_encode(payload_json.as_bytes());
+
+    let sig_bytes = hex::decode(&doc.signature)
+        .map_err(|e| SteleError::SerializationError(format!("signature is not valid hex: {}", e)))?;
+    let sig_b64 = base64url_encode(&sig_bytes);
+
+    Ok(format!("{}.{}.{}", header_b64, payload_b64, sig_b64))
+}
+
+/// Registered claims decoded from a [`to_jws_compact`] token, plus the
+/// covenant-specific `ccl` claim.
+///
+/// This is deliberately not a `CovenantDocument`: the compact form has no
+/// room for `issuer.publicKey`, `nonce`, `beneficiary`, or `chain`, so it
+/// can't be reconstructed from the token alone. Callers that need the
+/// full document should look it up by `jti` (the covenant id) and run
+/// [`verify_covenant`](super::verify_covenant) on it; compare that
+/// document's fields against these claims and its `signature` against the
+/// token's detached signature segment to confirm the token describes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwtClaims {
+    pub iss: String,
+    pub sub: String,
+    pub jti: String,
+    pub ccl: String,
+    pub nbf: Option<i64>,
+    pub exp: Option<i64>,
+}
+
+/// Decode a compact JWT produced by [`to_jws_compact`]. See [`JwtClaims`]
+/// for why this returns claims rather than a full `CovenantDocument`, and
+/// for how to check the detached signature segment against one.
+///
+/// # Errors
+/// Returns `SteleError::SerializationError` if `input` is not a
+/// well-formed 3-segment compact JWT or a required claim is missing, or
+/// `SteleError::InvalidInput` if the header names an unsupported
+/// `alg`/`typ`.
+pub fn from_jws_compact(input: &str) -> Result<JwtClaims, SteleError> {
+    let parts: Vec<&str> = input.trim().split('.').collect();
+    if parts.len() != 3 {
+        return Err(SteleError::SerializationError(
+            "compact JWT must have exactly 3 dot-separated segments".to_string(),
+        ));
+    }
+
+    let header_bytes = base64url_decode(parts[0])?;
+    let header: JwtHeader = serde_json::from_slice(&header_bytes)
+        .map_err(|e| SteleError::SerializationError(format!("invalid JWT header: {}", e)))?;
+    if header.alg != "EdDSA" {
+        return Err(SteleError::InvalidInput(format!("unsupported JWT alg: {}", header.alg)));
+    }
+    if header.typ != "JWT" {
+        return Err(SteleError::InvalidInput(format!("unsupported JWT typ: {}", header.typ)));
+    }
+
+    let payload_bytes = base64url_decode(parts[1])?;
+    let payload: serde_json::Value = serde_json::from_slice(&payload_bytes)
+        .map_err(|e| SteleError::SerializationError(format!("invalid JWT payload: {}", e)))?;
+
+    // The signature segment is detached (see `to_jws_compact`'s docs): it
+    // is the covenant's own signature over its canonical form, not a
+    // signature over `header.payload`, so it can only be checked against
+    // a looked-up document's `signature` field, not verified here.
+    let _sig_bytes = base64url_decode(parts[2])?;
+
+    let get_str = |key: &str| -> Result<String, SteleError> {
+        payload
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| SteleError::SerializationError(format!("JWT payload is missing `{}`", key)))
+    };
+
+    Ok(JwtClaims {
+        iss: get_str("iss")?,
+        sub: get_str("sub")?,
+        jti: get_str("jti")?,
+        ccl: get_str("ccl")?,
+        nbf: payload.get("nbf").and_then(|v| v.as_i64()),
+        exp: payload.get("exp").and_then(|v| v.as_i64()),
+    })
+}
+
+/// Header for [`to_jwt_vc`]'s JWT-encoded Verifiable Credential. Adds
+/// `kid`, naming the key the JWT signature (over `header.payload`) is
+/// made with, since -- unlike [`to_jws_compact`]'s detached signature --
+/// this token must be verifiable on its own.
+#[derive(Debug, Serialize, Deserialize)]
+struct JwtVcHeader {
+    alg: String,
+    typ: String,
+    kid: String,
+}
+
+/// Export `doc` as a JWT-encoded W3C Verifiable Credential: a standard
+/// `header.payload.signature` JWT whose `payload` carries the whole
+/// credential (including `credentialSubject`) as a `vc` claim, alongside
+/// `iss`/`sub`/`jti`/`nbf`/`exp`. Unlike [`to_jws_compact`], the
+/// signature covers `header.payload` itself (a fresh signature, not
+/// `doc.signature` reused), so [`verify_jwt_vc`] can check the token
+/// without looking up the original document.
+///
+/// # Errors
+/// Returns `SteleError::InvalidInput` if `doc.alg` is not `Ed25519`, or
+/// `SteleError::SerializationError` if `doc.activatesAt`/`expiresAt` are
+/// not valid timestamps, the document fails to serialize, or signing
+/// fails.
+pub fn to_jwt_vc(doc: &CovenantDocument, signing_key: &ed25519_dalek::SigningKey) -> Result<String, SteleError> {
+    require_ed25519(doc, "to_jwt_vc")?;
+
+    let header = JwtVcHeader {
+        alg: "EdDSA".to_string(),
+        typ: "JWT".to_string(),
+        kid: doc.issuer.public_key.clone(),
+    };
+    let header_json = serde_json::to_string(&header)
+        .map_err(|e| SteleError::SerializationError(format!("failed to serialize JWT header: {}", e)))?;
+    let header_b64 = base64url_encode(header_json.as_bytes());
+
+    let subject = serde_json::to_value(doc)
+        .map_err(|e| SteleError::SerializationError(format!("failed to serialize covenant: {}", e)))?;
+    let vc = serde_json::json!({
+        "@context": ["https://www.w3.org/2018/credentials/v1"],
+        "type": ["VerifiableCredential", "SteleCovenant"],
+        "credentialSubject": subject,
+    });
+
+    let mut payload = serde_json::json!({
+        "iss": doc.issuer.id,
+        "sub": doc.beneficiary.id,
+        "jti": doc.id,
+        "vc": vc,
+    });
+    if let Some(activates_at) = &doc.activates_at {
+        let nbf = parse_timestamp(activates_at)
+            .ok_or_else(|| SteleError::SerializationError("activatesAt is not a valid timestamp".to_string()))?
+            .timestamp();
+        payload["nbf"] = serde_json::json!(nbf);
+    }
+    if let Some(expires_at) = &doc.expires_at {
+        let exp = parse_timestamp(expires_at)
+            .ok_or_else(|| SteleError::SerializationError("expiresAt is not a valid timestamp".to_string()))?
+            .timestamp();
+        payload["exp"] = serde_json::json!(exp);
+    }
+
+    let payload_json = serde_json::to_string(&payload)
+        .map_err(|e| SteleError::SerializationError(format!("failed to serialize JWT payload: {}", e)))?;
+    let payload_b64 = base64url_encode(payload_json.as_bytes());
+
+    let signing_input = format!("{}.{}", header_b64, payload_b64);
+    let sig_bytes = crypto::sign(signing_input.as_bytes(), signing_key)?;
+    let sig_b64 = base64url_encode(&sig_bytes);
+
+    Ok(format!("{}.{}.{}", header_b64, payload_b64, sig_b64))
+}
+
+/// Decode a JWT-encoded Verifiable Credential produced by [`to_jwt_vc`]
+/// back into a [`CovenantDocument`], without checking either signature.
+/// Use [`verify_jwt_vc`] when the token comes from an untrusted source.
+///
+/// # Errors
+/// Returns `SteleError::SerializationError` if `input` is not a
+/// well-formed 3-segment JWT carrying a `vc.credentialSubject` claim, or
+/// `SteleError::InvalidInput` if the header names an unsupported
+/// `alg`/`typ`.
+pub fn from_jwt_vc(input: &str) -> Result<CovenantDocument, SteleError> {
+    let parts: Vec<&str> = input.trim().split('.').collect();
+    if parts.len() != 3 {
+        return Err(SteleError::SerializationError(
+            "JWT VC must have exactly 3 dot-separated segments".to_string(),
+        ));
+    }
+
+    let header_bytes = base64url_decode(parts[0])?;
+    let header: JwtVcHeader = serde_json::from_slice(&header_bytes)
+        .map_err(|e| SteleError::SerializationError(format!("invalid JWT header: {}", e)))?;
+    if header.alg != "EdDSA" {
+        return Err(SteleError::InvalidInput(format!("unsupported JWT alg: {}", header.alg)));
+    }
+    if header.typ != "JWT" {
+        return Err(SteleError::InvalidInput(format!("unsupported JWT typ: {}", header.typ)));
+    }
+
+    let payload_bytes = base64url_decode(parts[1])?;
+    let payload: serde_json::Value = serde_json::from_slice(&payload_bytes)
+        .map_err(|e| SteleError::SerializationError(format!("invalid JWT payload: {}", e)))?;
+
+    let subject = payload
+        .get("vc")
+        .and_then(|vc| vc.get("credentialSubject"))
+        .cloned()
+        .ok_or_else(|| SteleError::SerializationError("JWT VC payload is missing `vc.credentialSubject`".to_string()))?;
+    let doc: CovenantDocument = serde_json::from_value(subject)
+        .map_err(|e| SteleError::SerializationError(format!("failed to reconstruct covenant from vc.credentialSubject: {}", e)))?;
+    require_ed25519(&doc, "from_jwt_vc")?;
+
+    Ok(doc)
+}
+
+/// Decode a JWT-encoded Verifiable Credential exactly as [`from_jwt_vc`]
+/// does, then verify it: the JWT signature over `header.payload` against
+/// the key named by `kid` (which must match the embedded covenant's own
+/// `issuer.publicKey`), and the embedded covenant's own signature over
+/// its canonical form -- the signature check [`from_verifiable_credential`]
+/// performs -- reusing [`crypto::verify_signature`] both times.
+///
+/// # Errors
+/// Same error conditions as [`from_jwt_vc`], plus
+/// `SteleError::VerificationFailed` if `kid` does not match the embedded
+/// covenant's issuer key, the JWT signature does not verify, or the
+/// embedded covenant's own signature does not verify.
+pub fn verify_jwt_vc(input: &str) -> Result<CovenantDocument, SteleError> {
+    let parts: Vec<&str> = input.trim().split('.').collect();
+    if parts.len() != 3 {
+        return Err(SteleError::SerializationError(
+            "JWT VC must have exactly 3 dot-separated segments".to_string(),
+        ));
+    }
+
+    let header_bytes = base64url_decode(parts[0])?;
+    let header: JwtVcHeader = serde_json::from_slice(&header_bytes)
+        .map_err(|e| SteleError::SerializationError(format!("invalid JWT header: {}", e)))?;
+
+    let doc = from_jwt_vc(input)?;
+    if header.kid != doc.issuer.public_key {
+        return Err(SteleError::VerificationFailed(
+            "JWT `kid` does not match the embedded covenant's issuer public key".to_string(),
+        ));
+    }
+
+    let signing_input = format!("{}.{}", parts[0], parts[1]);
+    let sig_bytes = base64url_decode(parts[2])?;
+    if !crypto::verify_signature(crypto::SignatureAlgorithm::Ed25519, signing_input.as_bytes(), &sig_bytes, &header.kid) {
+        return Err(SteleError::VerificationFailed("JWT VC signature failed verification".to_string()));
+    }
+
+    let canonical = canonical_form(&doc)
+        .map_err(|e| SteleError::SerializationError(format!("failed to compute canonical form: {}", e)))?;
+    let doc_sig_bytes = hex::decode(&doc.signature)
+        .map_err(|e| SteleError::SerializationError(format!("signature is not valid hex: {}", e)))?;
+    if !crypto::verify_signature(crypto::SignatureAlgorithm::Ed25519, canonical.as_bytes(), &doc_sig_bytes, &doc.issuer.public_key) {
+        return Err(SteleError::VerificationFailed(
+            "embedded covenant's own signature failed verification".to_string(),
+        ));
+    }
+
+    Ok(doc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covenant::{
+        build_covenant, build_covenant_with_key, CovenantBuilderOptions, CovenantIssuerKey, CovenantKeyedBuilderOptions, Party,
+    };
+
+    fn make_test_covenant() -> CovenantDocument {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary_kp = crypto::generate_key_pair().unwrap();
+        let issuer = Party {
+            id: "issuer-1".to_string(),
+            public_key: issuer_kp.public_key_hex.clone(),
+            role: "issuer".to_string(),
+        };
+        let beneficiary = Party {
+            id: "beneficiary-1".to_string(),
+            public_key: beneficiary_kp.public_key_hex,
+            role: "beneficiary".to_string(),
+        };
+        build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key,
+            chain: None,
+            expires_at: Some("2030-01-01T00:00:00Z".to_string()),
+            activates_at: Some("2026-01-01T00:00:00Z".to_string()),
+            metadata: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_verifiable_credential_roundtrip() {
+        let doc = make_test_covenant();
+        let vc = to_verifiable_credential(&doc).unwrap();
+        assert!(vc.contains("DataIntegrityProof"));
+        assert!(vc.contains("eddsa-jcs-2022"));
+
+        let restored = from_verifiable_credential(&vc).unwrap();
+        assert_eq!(restored.id, doc.id);
+        assert_eq!(restored.signature, doc.signature);
+    }
+
+    #[test]
+    fn test_from_verifiable_credential_rejects_tampered_proof() {
+        let doc = make_test_covenant();
+        let vc = to_verifiable_credential(&doc).unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&vc).unwrap();
+        value["proof"]["proofValue"] = serde_json::Value::String("00".repeat(64));
+        assert!(from_verifiable_credential(&value.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_verifiable_credential_rejects_non_ed25519() {
+        let kp = crypto::generate_ecdsa_p256_key_pair().unwrap();
+        let bene_kp = crypto::generate_key_pair().unwrap();
+        let issuer = Party { id: "issuer-1".to_string(), public_key: kp.public_key_hex.clone(), role: "issuer".to_string() };
+        let beneficiary = Party { id: "beneficiary-1".to_string(), public_key: bene_kp.public_key_hex, role: "beneficiary".to_string() };
+        let doc = build_covenant_with_key(CovenantKeyedBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: CovenantIssuerKey::EcdsaP256(&kp.signing_key),
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+        assert!(to_verifiable_credential(&doc).is_err());
+    }
+
+    #[test]
+    fn test_jws_compact_roundtrip_claims() {
+        let doc = make_test_covenant();
+        let token = to_jws_compact(&doc).unwrap();
+        assert_eq!(token.matches('.').count(), 2);
+
+        let claims = from_jws_compact(&token).unwrap();
+        assert_eq!(claims.iss, doc.issuer.id);
+        assert_eq!(claims.sub, doc.beneficiary.id);
+        assert_eq!(claims.jti, doc.id);
+        assert_eq!(claims.ccl, doc.constraints);
+        assert!(claims.nbf.is_some());
+        assert!(claims.exp.is_some());
+    }
+
+    #[test]
+    fn test_from_jws_compact_rejects_malformed_token() {
+        assert!(from_jws_compact("not-a-jwt").is_err());
+    }
+
+    #[test]
+    fn test_jwt_vc_roundtrip_and_verify() {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary_kp = crypto::generate_key_pair().unwrap();
+        let issuer = Party { id: "issuer-1".to_string(), public_key: issuer_kp.public_key_hex.clone(), role: "issuer".to_string() };
+        let beneficiary = Party { id: "beneficiary-1".to_string(), public_key: beneficiary_kp.public_key_hex, role: "beneficiary".to_string() };
+        let doc = build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key.clone(),
+            chain: None,
+            expires_at: Some("2030-01-01T00:00:00Z".to_string()),
+            activates_at: Some("2026-01-01T00:00:00Z".to_string()),
+            metadata: None,
+        })
+        .unwrap();
+
+        let token = to_jwt_vc(&doc, &issuer_kp.signing_key).unwrap();
+        assert_eq!(token.matches('.').count(), 2);
+
+        let decoded = from_jwt_vc(&token).unwrap();
+        assert_eq!(decoded.id, doc.id);
+
+        let verified = verify_jwt_vc(&token).unwrap();
+        assert_eq!(verified.id, doc.id);
+    }
+
+    #[test]
+    fn test_verify_jwt_vc_rejects_tampered_signature() {
+        let doc = make_test_covenant();
+        let issuer_kp_signing_key = {
+            // Re-derive a fresh Ed25519 key isn't possible from `doc` alone;
+            // sign with an unrelated key so the JWT signature is simply bogus.
+            crypto::generate_key_pair().unwrap().signing_key
+        };
+        let token = to_jwt_vc(&doc, &issuer_kp_signing_key).unwrap();
+        assert!(verify_jwt_vc(&token).is_err());
+    }
+
+    #[test]
+    fn test_from_jwt_vc_rejects_malformed_token() {
+        assert!(from_jwt_vc("not-a-jwt").is_err());
+    }
+
+    #[test]
+    fn test_to_jwt_vc_rejects_non_ed25519() {
+        let kp = crypto::generate_ecdsa_p256_key_pair().unwrap();
+        let bene_kp = crypto::generate_key_pair().unwrap();
+        let issuer = Party { id: "issuer-1".to_string(), public_key: kp.public_key_hex.clone(), role: "issuer".to_string() };
+        let beneficiary = Party { id: "beneficiary-1".to_string(), public_key: bene_kp.public_key_hex, role: "beneficiary".to_string() };
+        let doc = build_covenant_with_key(CovenantKeyedBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: CovenantIssuerKey::EcdsaP256(&kp.signing_key),
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+        let unrelated_ed25519_key = crypto::generate_key_pair().unwrap().signing_key;
+        assert!(to_jwt_vc(&doc, &unrelated_ed25519_key).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+//! Deterministic, `no_std`-compatible covenant verification entrypoint.
+//!
+//! [`super::verify_covenant`] calls the system clock (`chrono::Utc::now`)
+//! to check expiry/activation. That's unavailable, and untrustworthy, for
+//! a verifier embedded in a WASM smart-contract host: every node
+//! evaluating the same covenant must reach the same verdict, so "now"
+//! has to come from the host (e.g. the block timestamp) rather than the
+//! local clock. This module runs a subset of `verify_covenant`'s checks
+//! (listed on [`verify_covenant_deterministic`]) but takes `now` as an
+//! explicit argument, plus a compact byte-oriented
+//! entrypoint, [`verify_covenant_bytes`], that a host chain can call
+//! directly to settle whether an agent honored its covenant.
+
+use super::{
+    canonical_form, compute_id, CovenantDocument, VerificationCheck, VerificationResult,
+    MAX_CHAIN_DEPTH, MAX_CONSTRAINTS, MAX_DOCUMENT_SIZE,
+};
+use crate::{ccl, crypto};
+use crate::SteleError;
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Verify a covenant document against an explicit `now` (RFC 3339 /
+/// ISO 8601) instead of the system clock, so the verdict is
+/// deterministic and reproducible across every node re-running the
+/// check. Runs these checks of [`super::verify_covenant`]: `id_match`,
+/// `signature_valid`, `not_expired`, `active`, `ccl_parses`,
+/// `chain_depth`, `document_size`, and `nonce_present`, with
+/// `not_expired`/`active` comparing against `now` rather than
+/// `chrono::Utc::now()`. The `enforcement_valid`, `proof_valid`,
+/// `countersignatures`, and `timestamp` checks are not run.
+pub fn verify_covenant_deterministic(
+    doc: &CovenantDocument,
+    now: &str,
+) -> Result<VerificationResult, SteleError> {
+    let now = parse_timestamp(now)
+        .ok_or_else(|| SteleError::InvalidInput(format!("invalid `now` timestamp: {}", now)))?;
+    let mut checks: Vec<VerificationCheck> = Vec::new();
+
+    // 1. ID match
+    let expected_id = compute_id(doc).map_err(|_| SteleError::SerializationError("failed to compute canonical document id".to_string()))?;
+    checks.push(VerificationCheck {
+        name: "id_match".to_string(),
+        passed: doc.id == expected_id,
+        message: if doc.id == expected_id {
+            "Document ID matches canonical hash".to_string()
+        } else {
+            format!("ID mismatch: expected {}, got {}", expected_id, doc.id)
+        },
+    });
+
+    // 2. Signature valid
+    let sig_valid = {
+        let canonical = canonical_form(doc).map_err(|_| SteleError::SerializationError("failed to compute canonical form".to_string()))?;
+        let sig_bytes = hex::decode(&doc.signature).unwrap_or_default();
+        crypto::verify_signature(doc.alg, canonical.as_bytes(), &sig_bytes, &doc.issuer.public_key)
+    };
+    checks.push(VerificationCheck {
+        name: "signature_valid".to_string(),
+        passed: sig_valid,
+        message: if sig_valid {
+            "Issuer signature is valid".to_string()
+        } else {
+            "Issuer signature verification failed".to_string()
+        },
+    });
+
+    // 3. Not expired
+    let not_expired = match doc.expires_at.as_ref().and_then(|s| parse_timestamp(s)) {
+        Some(expires) => now < expires,
+        None => true,
+    };
+    checks.push(VerificationCheck {
+        name: "not_expired".to_string(),
+        passed: not_expired,
+        message: if not_expired {
+            "Document has not expired".to_string()
+        } else {
+            format!("Document expired at {}", doc.expires_at.clone().unwrap_or_default())
+        },
+    });
+
+    // 4. Active
+    let is_active = match doc.activates_at.as_ref().and_then(|s| parse_timestamp(s)) {
+        Some(activates) => now >= activates,
+        None => true,
+    };
+    checks.push(VerificationCheck {
+        name: "active".to_string(),
+        passed: is_active,
+        message: if is_active {
+            "Document is active".to_string()
+        } else {
+            format!("Document activates at {}", doc.activates_at.clone().unwrap_or_default())
+        },
+    });
+
+    // 5. CCL parses
+    let (ccl_parses, ccl_msg) = match ccl::parse(&doc.constraints) {
+        Ok(parsed) => {
+            if parsed.statements.len() > MAX_CONSTRAINTS {
+                (false, format!("Constraints exceed maximum of {} statements", MAX_CONSTRAINTS))
+            } else {
+                (true, format!("CCL parsed successfully ({} statement(s))", parsed.statements.len()))
+            }
+        }
+        Err(e) => (false, format!("CCL parse error: {}", e)),
+    };
+    checks.push(VerificationCheck {
+        name: "ccl_parses".to_string(),
+        passed: ccl_parses,
+        message: ccl_msg,
+    });
+
+    // 8. Chain depth
+    if let Some(ref chain) = doc.chain {
+        let depth_ok = chain.depth >= 1 && chain.depth <= MAX_CHAIN_DEPTH;
+        checks.push(VerificationCheck {
+            name: "chain_depth".to_string(),
+            passed: depth_ok,
+            message: if depth_ok {
+                format!("Chain depth {} is within limit", chain.depth)
+            } else {
+                format!("Chain depth {} exceeds maximum of {}", chain.depth, MAX_CHAIN_DEPTH)
+            },
+        });
+    } else {
+        checks.push(VerificationCheck {
+            name: "chain_depth".to_string(),
+            passed: true,
+            message: "No chain reference present".to_string(),
+        });
+    }
+
+    // 9. Document size
+    let serialized_len = serde_json::to_vec(doc).map(|b| b.len()).unwrap_or(usize::MAX);
+    let size_ok = serialized_len <= MAX_DOCUMENT_SIZE;
+    checks.push(VerificationCheck {
+        name: "document_size".to_string(),
+        passed: size_ok,
+        message: if size_ok {
+            format!("Document size {} bytes is within limit", serialized_len)
+        } else {
+            format!("Document size {} bytes exceeds maximum of {}", serialized_len, MAX_DOCUMENT_SIZE)
+        },
+    });
+
+    // 11. Nonce present
+    let nonce_ok = !doc.nonce.is_empty() && doc.nonce.len() == 64 && doc.nonce.chars().all(|c| c.is_ascii_hexdigit());
+    checks.push(VerificationCheck {
+        name: "nonce_present".to_string(),
+        passed: nonce_ok,
+        message: if nonce_ok {
+            "Nonce is present and valid (64-char hex)".to_string()
+        } else {
+            "Nonce is missing or malformed".to_string()
+        },
+    });
+
+    let valid = checks.iter().all(|c| c.passed);
+    Ok(VerificationResult { valid, checks })
+}
+
+/// Compact entrypoint for a host chain: deserialize `bytes` as a
+/// `CovenantDocument`, verify it deterministically against `now` (e.g.
+/// the block timestamp), and collapse the result to pass/fail.
+///
+/// # Errors
+/// Returns `SteleError::SerializationError` if `bytes` is not a valid
+/// covenant document, or `SteleError::VerificationFailed` naming every
+/// failed check.
+pub fn verify_covenant_bytes(bytes: &[u8], now: &str) -> Result<(), SteleError> {
+    let doc: CovenantDocument = serde_json::from_slice(bytes)
+        .map_err(|e| SteleError::SerializationError(format!("Failed to parse covenant bytes: {}", e)))?;
+    let result = verify_covenant_deterministic(&doc, now)?;
+    if result.valid {
+        Ok(())
+    } else {
+        let failed: Vec<&str> = result
+            .checks
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| c.name.as_str())
+            .collect();
+        Err(SteleError::VerificationFailed(format!(
+            "failed checks: {}",
+            failed.join(", ")
+        )))
+    }
+}
+
+/// Parse an RFC 3339 / ISO 8601 timestamp into whole milliseconds since
+/// the Unix epoch, without touching the system clock.
+fn parse_timestamp(s: &str) -> Option<i64> {
+    let dt = chrono::DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.3fZ").map(|dt| dt.and_utc()))
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%SZ").map(|dt| dt.and_utc()))
+        .ok()?;
+    Some(dt.timestamp_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covenant::{build_covenant, CovenantBuilderOptions, Party};
+
+    fn make_test_covenant() -> CovenantDocument {
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary_kp = crypto::generate_key_pair().unwrap();
+        let issuer = Party {
+            id: "issuer-1".to_string(),
+            public_key: issuer_kp.public_key_hex.clone(),
+            role: "issuer".to_string(),
+        };
+        let beneficiary = Party {
+            id: "beneficiary-1".to_string(),
+            public_key: beneficiary_kp.public_key_hex,
+            role: "beneficiary".to_string(),
+        };
+        build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key,
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_verify_covenant_deterministic_matches_verify_covenant() {
+        let doc = make_test_covenant();
+        let now = crypto::timestamp();
+        let result = verify_covenant_deterministic(&doc, &now).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_verify_covenant_bytes_roundtrip() {
+        let doc = make_test_covenant();
+        let bytes = serde_json::to_vec(&doc).unwrap();
+        let now = crypto::timestamp();
+        assert!(verify_covenant_bytes(&bytes, &now).is_ok());
+    }
+
+    #[test]
+    fn test_verify_covenant_bytes_rejects_garbage() {
+        let now = crypto::timestamp();
+        assert!(verify_covenant_bytes(b"not json", &now).is_err());
+    }
+
+    #[test]
+    fn test_verify_covenant_deterministic_rejects_expired() {
+        let mut doc = make_test_covenant();
+        doc.expires_at = Some("2000-01-01T00:00:00.000Z".to_string());
+        let now = crypto::timestamp();
+        let result = verify_covenant_deterministic(&doc, &now).unwrap();
+        assert!(!result.valid);
+    }
+}
@@ -0,0 +1,189 @@
+//! Pluggable signing backends.
+//!
+//! `crypto::sign`/`crypto::verify` assume an in-memory Ed25519 secret
+//! key. The `Signer`/`Verifier` traits here abstract over *where* a
+//! signature comes from, so the `covenant` and `identity` builders can
+//! accept any `&dyn Signer` -- including a hardware token whose private
+//! key never leaves the device. The crate keeps the digest computation
+//! (JCS canonicalization + SHA-256) in-crate; a backend's only job is to
+//! produce an Ed25519 signature over that 32-byte digest, so
+//! verification stays backend-independent.
+
+use crate::crypto;
+use crate::SteleError;
+use ed25519_dalek::Verifier as _;
+
+/// Something that can produce an Ed25519 signature over a 32-byte
+/// digest without exposing the private key material it uses to do so.
+pub trait Signer {
+    /// Sign a 32-byte digest, returning the 64-byte Ed25519 signature.
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Vec<u8>, SteleError>;
+
+    /// The hex-encoded Ed25519 public key corresponding to this signer.
+    fn public_key_hex(&self) -> String;
+}
+
+/// Something that can verify an Ed25519 signature over a 32-byte digest.
+pub trait Verifier {
+    /// Verify `signature` over `digest`. Never panics on malformed input.
+    fn verify_digest(&self, digest: &[u8; 32], signature: &[u8]) -> bool;
+}
+
+/// Compute the digest a `Signer` is expected to sign: the SHA-256 hash
+/// of the JCS-canonicalized document bytes.
+pub fn signing_digest(canonical: &str) -> [u8; 32] {
+    signing_digest_bytes(canonical.as_bytes())
+}
+
+/// Compute the digest a `Signer` is expected to sign over raw bytes, e.g.
+/// a COSE `Sig_structure`, rather than a JCS-canonicalized string.
+pub fn signing_digest_bytes(data: &[u8]) -> [u8; 32] {
+    let hex_digest = crypto::sha256_hex(data);
+    let bytes = hex::decode(&hex_digest).unwrap_or_default();
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&bytes[..32]);
+    out
+}
+
+/// A software-backed signer wrapping an in-memory Ed25519 signing key.
+pub struct SoftwareSigner {
+    signing_key: ed25519_dalek::SigningKey,
+    public_key_hex: String,
+}
+
+impl SoftwareSigner {
+    /// Wrap an existing `KeyPair` as a `Signer`.
+    pub fn new(key_pair: &crypto::KeyPair) -> Self {
+        SoftwareSigner {
+            signing_key: ed25519_dalek::SigningKey::from_bytes(&key_pair.signing_key.to_bytes()),
+            public_key_hex: key_pair.public_key_hex.clone(),
+        }
+    }
+}
+
+impl Signer for SoftwareSigner {
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Vec<u8>, SteleError> {
+        crypto::sign(digest, &self.signing_key)
+    }
+
+    fn public_key_hex(&self) -> String {
+        self.public_key_hex.clone()
+    }
+}
+
+/// A signer backed by a PKCS#11 / FIDO2-style hardware token. The
+/// private key never enters process memory: the crate only ever sends
+/// the token a 32-byte digest and receives back a signature.
+///
+/// `sign_fn` models the token's signing operation (e.g. a PKCS#11
+/// `C_Sign` call, or a FIDO2 `get-assertion` over the digest).
+pub struct HardwareTokenSigner<F>
+where
+    F: Fn(&[u8; 32]) -> Result<Vec<u8>, SteleError>,
+{
+    public_key_hex: String,
+    sign_fn: F,
+}
+
+impl<F> HardwareTokenSigner<F>
+where
+    F: Fn(&[u8; 32]) -> Result<Vec<u8>, SteleError>,
+{
+    /// Create a hardware-token signer for the token whose public key is
+    /// `public_key_hex`, using `sign_fn` to perform the on-device sign.
+    pub fn new(public_key_hex: String, sign_fn: F) -> Self {
+        HardwareTokenSigner { public_key_hex, sign_fn }
+    }
+}
+
+impl<F> Signer for HardwareTokenSigner<F>
+where
+    F: Fn(&[u8; 32]) -> Result<Vec<u8>, SteleError>,
+{
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<Vec<u8>, SteleError> {
+        let sig = (self.sign_fn)(digest)?;
+        if sig.len() != 64 {
+            return Err(SteleError::CryptoError(format!(
+                "hardware token returned {}-byte signature, expected 64",
+                sig.len()
+            )));
+        }
+        Ok(sig)
+    }
+
+    fn public_key_hex(&self) -> String {
+        self.public_key_hex.clone()
+    }
+}
+
+/// A verifier backed by a raw Ed25519 verifying key.
+pub struct SoftwareVerifier {
+    verifying_key: ed25519_dalek::VerifyingKey,
+}
+
+impl SoftwareVerifier {
+    pub fn from_public_key_hex(public_key_hex: &str) -> Result<Self, SteleError> {
+        let bytes = hex::decode(public_key_hex)
+            .map_err(|e| SteleError::CryptoError(format!("invalid public key hex: {}", e)))?;
+        let array: [u8; 32] = bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| SteleError::CryptoError("public key must be 32 bytes".to_string()))?;
+        let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&array)
+            .map_err(|e| SteleError::CryptoError(format!("invalid public key: {}", e)))?;
+        Ok(SoftwareVerifier { verifying_key })
+    }
+}
+
+impl Verifier for SoftwareVerifier {
+    fn verify_digest(&self, digest: &[u8; 32], signature: &[u8]) -> bool {
+        if signature.len() != 64 {
+            return false;
+        }
+        let sig_bytes: [u8; 64] = match signature.try_into() {
+            Ok(b) => b,
+            Err(_) => return false,
+        };
+        let sig = ed25519_dalek::Signature::from_bytes(&sig_bytes);
+        self.verifying_key.verify(digest, &sig).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_software_signer_roundtrip() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let signer = SoftwareSigner::new(&kp);
+        let verifier = SoftwareVerifier::from_public_key_hex(&signer.public_key_hex()).unwrap();
+
+        let digest = signing_digest(r#"{"a":1}"#);
+        let sig = signer.sign_digest(&digest).unwrap();
+        assert!(verifier.verify_digest(&digest, &sig));
+        assert!(!verifier.verify_digest(&signing_digest(r#"{"a":2}"#), &sig));
+    }
+
+    #[test]
+    fn test_hardware_token_signer_delegates_to_callback() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&kp.signing_key.to_bytes());
+        let public_key_hex = kp.public_key_hex.clone();
+        let token = HardwareTokenSigner::new(public_key_hex.clone(), move |digest| {
+            crypto::sign(digest, &signing_key)
+        });
+
+        let verifier = SoftwareVerifier::from_public_key_hex(&public_key_hex).unwrap();
+        let digest = signing_digest(r#"{"b":2}"#);
+        let sig = token.sign_digest(&digest).unwrap();
+        assert!(verifier.verify_digest(&digest, &sig));
+    }
+
+    #[test]
+    fn test_hardware_token_signer_rejects_malformed_signature() {
+        let token = HardwareTokenSigner::new("ff".repeat(32), |_digest| Ok(vec![0u8; 10]));
+        let digest = signing_digest("x");
+        assert!(token.sign_digest(&digest).is_err());
+    }
+}
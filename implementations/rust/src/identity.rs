@@ -4,11 +4,27 @@
 //! agent's operator, model, capabilities, and deployment context. Identities
 //! evolve through a hash-linked lineage chain, where each evolution is signed
 //! and records the type of change made.
+//!
+//! [`create_identity`]/[`evolve_identity`] sign with Ed25519;
+//! [`create_identity_ecdsa_p256`]/[`create_identity_rsa2048`] and their
+//! `evolve_*` counterparts exist for operators whose HSM or platform is
+//! constrained to a different algorithm -- see
+//! [`crypto::SignatureAlgorithm`].
 
 use crate::crypto;
+use crate::crypto::signer::Signer;
+use crate::telemetry;
 use crate::SteleError;
+use core::num::NonZeroUsize;
 use serde::{Deserialize, Serialize};
 
+pub mod attestation;
+pub mod delegation;
+// Uses `HashMap`/`HashSet` for its in-memory identity store and
+// uniqueness checks, so (like `store`) it's only available with `std`.
+#[cfg(feature = "std")]
+pub mod did;
+
 // ---------------------------------------------------------------------------
 // Types
 // ---------------------------------------------------------------------------
@@ -27,6 +43,66 @@ pub struct DeploymentInfo {
     pub runtime: String,
 }
 
+/// An m-of-n set of operator keys jointly controlling an identity,
+/// modeled on the role/threshold scheme content-addressed metadata
+/// systems use for multi-key control: an identity change is authorized
+/// once at least `threshold` *distinct* keys from `keys` have signed
+/// it, not necessarily all of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeySet {
+    /// Hex-encoded public keys, sorted so the same logical set always
+    /// serializes (and hashes) identically regardless of insertion
+    /// order.
+    pub keys: Vec<String>,
+    pub threshold: NonZeroUsize,
+}
+
+impl KeySet {
+    /// Build a key set from `keys` and `threshold`, deduping and
+    /// sorting the keys first.
+    ///
+    /// # Errors
+    /// Returns `SteleError::InvalidInput` if `threshold` is zero or
+    /// exceeds the number of distinct keys.
+    pub fn new(keys: Vec<String>, threshold: usize) -> Result<Self, SteleError> {
+        let mut keys = keys;
+        keys.sort();
+        keys.dedup();
+
+        let threshold = NonZeroUsize::new(threshold)
+            .ok_or_else(|| SteleError::InvalidInput("threshold must be nonzero".to_string()))?;
+        if threshold.get() > keys.len() {
+            return Err(SteleError::InvalidInput(format!(
+                "threshold {} exceeds key set size {}",
+                threshold,
+                keys.len()
+            )));
+        }
+        Ok(KeySet { keys, threshold })
+    }
+
+    /// A single-key set with threshold 1, for operators signing with
+    /// just one key (the common case, and the only one the
+    /// algorithm-specific `create_identity_*`/`*_with_signer` helpers
+    /// support).
+    pub fn single(key: String) -> Self {
+        KeySet {
+            keys: vec![key],
+            threshold: NonZeroUsize::new(1).expect("1 is nonzero"),
+        }
+    }
+}
+
+/// One signer's signature over an identity's canonical body, naming the
+/// operator key it was produced by so [`verify_identity`] can match it
+/// against the identity's [`KeySet`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperatorSignature {
+    #[serde(rename = "signerKey")]
+    pub signer_key: String,
+    pub signature: String,
+}
+
 /// A single entry in an agent's lineage chain.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LineageEntry {
@@ -38,41 +114,100 @@ pub struct LineageEntry {
     pub change_type: String,
     pub description: String,
     pub timestamp: String,
+    /// Operator key set authorized to produce the *next* lineage entry,
+    /// as of this version. Equal to the parent entry's `operator_keys`
+    /// except for a `change_type == "key_rotation"` entry, where this
+    /// holds the post-rotation set -- the new keys take effect starting
+    /// with the entry *after* the rotation, not the rotation entry
+    /// itself.
+    #[serde(rename = "operatorKeys")]
+    pub operator_keys: KeySet,
+    /// Public key, hex-encoded, that authorized this evolution. Must
+    /// belong to the *parent* entry's `operator_keys` (or, for the
+    /// genesis entry, this entry's own `operator_keys`, since it has no
+    /// parent).
+    #[serde(rename = "signerPublicKey")]
+    pub signer_public_key: String,
+    /// Signature over `identity_hash` by `signer_public_key`, proving
+    /// this evolution was actually authorized by the prior version
+    /// rather than merely hash-linked to it.
+    pub signature: String,
 }
 
 /// A complete, signed AI agent identity.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentIdentity {
     pub id: String,
-    #[serde(rename = "operatorPublicKey")]
-    pub operator_public_key: String,
+    #[serde(rename = "operatorKeys")]
+    pub operator_keys: KeySet,
     pub model: ModelInfo,
     pub capabilities: Vec<String>,
     pub deployment: DeploymentInfo,
     pub version: u32,
     pub lineage: Vec<LineageEntry>,
-    pub signature: String,
+    pub signatures: Vec<OperatorSignature>,
     #[serde(rename = "createdAt")]
     pub created_at: String,
+    /// Hash of a bound [`attestation::Attestation`], if this identity's
+    /// signing key was proven to live inside a TEE. Part of the signed
+    /// body, so tampering with it invalidates `signature_valid`.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "attestationHash")]
+    pub attestation_hash: Option<String>,
+    /// Capability-delegation link to a parent identity, if this
+    /// identity was minted by [`delegation::delegate_identity`] rather
+    /// than [`create_identity`]. Not part of the signed body (like
+    /// `signatures`): `parent_signature` is produced over this
+    /// identity's own `id`, which would depend on itself if this field
+    /// were covered by the hash that produces `id`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delegation: Option<delegation::DelegationLink>,
+    /// Algorithm the operator signature was produced with. Included in
+    /// the signed body, so claiming a different algorithm than the one
+    /// that actually produced `signature` is detected as tampering
+    /// rather than quietly accepted. Absent on identities written
+    /// before this field existed, which defaults to `Ed25519` so they
+    /// remain verifiable.
+    #[serde(default)]
+    pub alg: crypto::SignatureAlgorithm,
 }
 
 /// Options for creating a new agent identity.
+///
+/// `signing_keys` forms the identity's [`KeySet`] (one public key per
+/// entry, derived from each `SigningKey`'s verifying key) and each
+/// signs the canonical body, producing one [`OperatorSignature`] per
+/// key. `threshold` is how many of those signatures [`verify_identity`]
+/// requires later to consider the identity authorized -- it need not
+/// equal `signing_keys.len()`.
 pub struct CreateIdentityOptions {
-    pub signing_key: ed25519_dalek::SigningKey,
-    pub public_key_hex: String,
+    pub signing_keys: Vec<ed25519_dalek::SigningKey>,
+    pub threshold: usize,
     pub model: ModelInfo,
     pub capabilities: Vec<String>,
     pub deployment: DeploymentInfo,
 }
 
 /// Options for evolving an existing identity.
+///
+/// `signing_keys` need not cover every key in the identity's
+/// [`KeySet`] -- only enough distinct, valid signatures to meet its
+/// `threshold` are required for [`verify_identity`] to consider the
+/// evolved identity authorized. One of `signing_keys` must belong to
+/// the identity's *current* (pre-evolution) key set, to authorize the
+/// lineage entry itself.
+///
+/// `new_operator_keys` rotates the identity to a new key set and is
+/// only valid when `change_type` is `"key_rotation"`; the rotation
+/// entry itself must still be authorized by a `signing_keys` member of
+/// the *old* set.
 pub struct EvolveIdentityOptions {
-    pub signing_key: ed25519_dalek::SigningKey,
+    pub signing_keys: Vec<ed25519_dalek::SigningKey>,
     pub change_type: String,
     pub description: String,
     pub model: Option<ModelInfo>,
     pub capabilities: Option<Vec<String>>,
     pub deployment: Option<DeploymentInfo>,
+    pub new_operator_keys: Option<KeySet>,
 }
 
 /// Result of verifying an agent identity.
@@ -102,7 +237,7 @@ pub fn compute_identity_hash(body: &serde_json::Value) -> String {
     crypto::sha256_string(&crypto::canonicalize_json(body))
 }
 
-/// Build the JSON body used for hashing/signing (excludes `id` and `signature`).
+/// Build the JSON body used for hashing/signing (excludes `id`, `signatures`, and `delegation`).
 fn identity_body(identity: &AgentIdentity) -> Result<serde_json::Value, SteleError> {
     let val = serde_json::to_value(identity)
         .map_err(|e| SteleError::SerializationError(format!("Failed to serialize identity: {}", e)))?;
@@ -113,11 +248,68 @@ fn identity_body(identity: &AgentIdentity) -> Result<serde_json::Value, SteleErr
     };
 
     obj.remove("id");
-    obj.remove("signature");
+    obj.remove("signatures");
+    obj.remove("delegation");
 
     Ok(serde_json::Value::Object(obj))
 }
 
+/// Sign `payload` with each of `signing_keys`, producing one
+/// [`OperatorSignature`] per key.
+fn sign_with_all(payload: &str, signing_keys: &[ed25519_dalek::SigningKey]) -> Result<Vec<OperatorSignature>, SteleError> {
+    signing_keys
+        .iter()
+        .map(|key| {
+            let sig_bytes = crypto::sign(payload.as_bytes(), key)?;
+            Ok(OperatorSignature {
+                signer_key: hex::encode(key.verifying_key().as_bytes()),
+                signature: hex::encode(&sig_bytes),
+            })
+        })
+        .collect()
+}
+
+/// Decode a hex-encoded SHA-256 identity hash into its raw 32-byte
+/// digest, for `Signer` backends that expect a pre-hashed digest
+/// rather than a message to hash themselves. Identity hashes are
+/// always a 64-character hex SHA-256 digest, so this never needs to
+/// hash anything further.
+fn lineage_digest(identity_hash_hex: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    if let Ok(hex_str) = core::str::from_utf8(identity_hash_hex) {
+        if let Ok(bytes) = hex::decode(hex_str) {
+            if bytes.len() == 32 {
+                out.copy_from_slice(&bytes);
+            }
+        }
+    }
+    out
+}
+
+/// Pick the first of `signing_keys` whose public key belongs to
+/// `authorized_keys`, to produce a lineage entry's authorization
+/// signature over its `identity_hash`.
+///
+/// # Errors
+/// Returns `SteleError::InvalidInput` if none of `signing_keys` belong
+/// to `authorized_keys`.
+fn pick_lineage_signer<'a>(
+    signing_keys: &'a [ed25519_dalek::SigningKey],
+    authorized_keys: &KeySet,
+) -> Result<&'a ed25519_dalek::SigningKey, SteleError> {
+    signing_keys
+        .iter()
+        .find(|key| {
+            let hex_key = hex::encode(key.verifying_key().as_bytes());
+            authorized_keys.keys.iter().any(|k| k == &hex_key)
+        })
+        .ok_or_else(|| {
+            SteleError::InvalidInput(
+                "no signing key belongs to the authorized operator key set".to_string(),
+            )
+        })
+}
+
 // ---------------------------------------------------------------------------
 // Create identity
 // ---------------------------------------------------------------------------
@@ -131,47 +323,218 @@ fn identity_body(identity: &AgentIdentity) -> Result<serde_json::Value, SteleErr
 /// Returns `SteleError::InvalidInput` for missing fields or
 /// `SteleError::CryptoError` for signing failures.
 pub fn create_identity(opts: CreateIdentityOptions) -> Result<AgentIdentity, SteleError> {
-    if opts.public_key_hex.is_empty() {
+    let mut lifecycle_span = telemetry::IdentityLifecycleSpan::start("identity.create");
+    lifecycle_span.set_model(&opts.model.provider, &opts.model.model_id);
+    lifecycle_span.set_capability_count(opts.capabilities.len());
+
+    let public_keys: Vec<String> = opts
+        .signing_keys
+        .iter()
+        .map(|key| hex::encode(key.verifying_key().as_bytes()))
+        .collect();
+    let operator_keys = KeySet::new(public_keys, opts.threshold)?;
+    let lineage_signer = pick_lineage_signer(&opts.signing_keys, &operator_keys)?;
+    let lineage_signer_key_hex = hex::encode(lineage_signer.verifying_key().as_bytes());
+
+    let mut identity = draft_identity(
+        operator_keys,
+        opts.model,
+        opts.capabilities,
+        opts.deployment,
+        crypto::SignatureAlgorithm::Ed25519,
+        &lineage_signer_key_hex,
+        |payload| crypto::sign(payload, lineage_signer),
+    )?;
+
+    let signing_payload = crypto::canonicalize_json(&identity_body(&identity)?);
+    let sign_start = std::time::Instant::now();
+    identity.signatures = sign_with_all(&signing_payload, &opts.signing_keys)?;
+    telemetry::record_signing_latency_ms(sign_start.elapsed().as_secs_f64() * 1000.0);
+
+    lifecycle_span.set_version(identity.version);
+    telemetry::record_identity_created();
+
+    Ok(identity)
+}
+
+/// Create a brand-new agent identity signed with an ECDSA P-256 key,
+/// for operators whose HSM or platform only offers P-256 instead of
+/// Ed25519.
+///
+/// # Errors
+/// Same error conditions as [`create_identity`].
+pub fn create_identity_ecdsa_p256(
+    public_key_hex: String,
+    model: ModelInfo,
+    capabilities: Vec<String>,
+    deployment: DeploymentInfo,
+    signing_key: &p256::ecdsa::SigningKey,
+) -> Result<AgentIdentity, SteleError> {
+    let operator_keys = KeySet::single(public_key_hex.clone());
+    let mut identity = draft_identity(
+        operator_keys,
+        model,
+        capabilities,
+        deployment,
+        crypto::SignatureAlgorithm::EcdsaP256,
+        &public_key_hex,
+        |payload| crypto::sign_ecdsa_p256(payload, signing_key),
+    )?;
+
+    let signing_payload = crypto::canonicalize_json(&identity_body(&identity)?);
+    let sig_bytes = crypto::sign_ecdsa_p256(signing_payload.as_bytes(), signing_key)?;
+    identity.signatures = vec![OperatorSignature {
+        signer_key: public_key_hex,
+        signature: hex::encode(&sig_bytes),
+    }];
+
+    Ok(identity)
+}
+
+/// Create a brand-new agent identity signed with a 2048-bit RSA key,
+/// for operators whose HSM or platform only offers RSA instead of
+/// Ed25519.
+///
+/// # Errors
+/// Same error conditions as [`create_identity`].
+pub fn create_identity_rsa2048(
+    public_key_hex: String,
+    model: ModelInfo,
+    capabilities: Vec<String>,
+    deployment: DeploymentInfo,
+    signing_key: &rsa::RsaPrivateKey,
+) -> Result<AgentIdentity, SteleError> {
+    let operator_keys = KeySet::single(public_key_hex.clone());
+    let mut identity = draft_identity(
+        operator_keys,
+        model,
+        capabilities,
+        deployment,
+        crypto::SignatureAlgorithm::Rsa2048,
+        &public_key_hex,
+        |payload| crypto::sign_rsa2048(payload, signing_key),
+    )?;
+
+    let signing_payload = crypto::canonicalize_json(&identity_body(&identity)?);
+    let sig_bytes = crypto::sign_rsa2048(signing_payload.as_bytes(), signing_key)?;
+    identity.signatures = vec![OperatorSignature {
+        signer_key: public_key_hex,
+        signature: hex::encode(&sig_bytes),
+    }];
+
+    Ok(identity)
+}
+
+/// Create a brand-new agent identity using any `&dyn Signer` backend.
+///
+/// Identical to [`create_identity`] except the operator signature is
+/// produced by a pluggable [`Signer`] (software, hardware token, etc.)
+/// over the SHA-256 digest of the canonicalized identity body.
+///
+/// # Errors
+/// Same error conditions as [`create_identity`], plus any error surfaced
+/// by the backing `Signer`.
+pub fn create_identity_with_signer(
+    public_key_hex: String,
+    model: ModelInfo,
+    capabilities: Vec<String>,
+    deployment: DeploymentInfo,
+    signer: &dyn Signer,
+) -> Result<AgentIdentity, SteleError> {
+    // `Signer` only ever produces Ed25519 signatures (see its docs), so
+    // the recorded `alg` is fixed rather than threaded through.
+    let operator_keys = KeySet::single(public_key_hex.clone());
+    let mut identity = draft_identity(
+        operator_keys,
+        model,
+        capabilities,
+        deployment,
+        crypto::SignatureAlgorithm::Ed25519,
+        &public_key_hex,
+        |identity_hash| signer.sign_digest(&lineage_digest(identity_hash)),
+    )?;
+
+    let signing_payload = crypto::canonicalize_json(&identity_body(&identity)?);
+    let digest = crypto::signer::signing_digest(&signing_payload);
+    let sig_bytes = signer.sign_digest(&digest)?;
+    identity.signatures = vec![OperatorSignature {
+        signer_key: public_key_hex,
+        signature: hex::encode(&sig_bytes),
+    }];
+
+    Ok(identity)
+}
+
+/// Validate inputs and assemble an unsigned `AgentIdentity` with its
+/// initial lineage entry and computed `id`, shared by every create path.
+/// The returned identity has an empty `signatures` list awaiting the
+/// caller's signing step; the genesis lineage entry, however, is
+/// already signed by `lineage_signer_key_hex` (via `sign_lineage`)
+/// since it must be authorized under the identity's own key set before
+/// `id` can be computed.
+///
+/// # Errors
+/// Returns `SteleError::InvalidInput` if `lineage_signer_key_hex` isn't
+/// a member of `operator_keys`.
+fn draft_identity(
+    operator_keys: KeySet,
+    model: ModelInfo,
+    capabilities: Vec<String>,
+    deployment: DeploymentInfo,
+    alg: crypto::SignatureAlgorithm,
+    lineage_signer_key_hex: &str,
+    sign_lineage: impl Fn(&[u8]) -> Result<Vec<u8>, SteleError>,
+) -> Result<AgentIdentity, SteleError> {
+    if operator_keys.keys.is_empty() {
         return Err(SteleError::InvalidInput(
-            "operatorPublicKey is required".to_string(),
+            "operatorKeys must contain at least one key".to_string(),
         ));
     }
-    if opts.model.provider.is_empty() || opts.model.model_id.is_empty() {
+    if !operator_keys.keys.iter().any(|k| k == lineage_signer_key_hex) {
+        return Err(SteleError::InvalidInput(
+            "lineage signer must be a member of the identity's own operator key set".to_string(),
+        ));
+    }
+    if model.provider.is_empty() || model.model_id.is_empty() {
         return Err(SteleError::InvalidInput(
             "model.provider and model.modelId are required".to_string(),
         ));
     }
-    if opts.capabilities.is_empty() {
+    if capabilities.is_empty() {
         return Err(SteleError::InvalidInput(
             "capabilities array must not be empty".to_string(),
         ));
     }
-    if opts.deployment.runtime.is_empty() {
+    if deployment.runtime.is_empty() {
         return Err(SteleError::InvalidInput(
             "deployment.runtime is required".to_string(),
         ));
     }
 
     let now = crypto::timestamp();
-    let mut capabilities = opts.capabilities.clone();
+    let mut capabilities = capabilities;
     capabilities.sort();
 
-    // Build partial identity without id/signature to compute hash
+    // Build partial identity without id/signatures to compute hash
     let mut identity = AgentIdentity {
         id: String::new(),
-        operator_public_key: opts.public_key_hex.clone(),
-        model: opts.model,
+        operator_keys,
+        model,
         capabilities,
-        deployment: opts.deployment,
+        deployment,
         version: 1,
         lineage: Vec::new(),
-        signature: String::new(),
+        signatures: Vec::new(),
         created_at: now.clone(),
+        attestation_hash: None,
+        delegation: None,
+        alg,
     };
 
     // Compute identity hash for the first lineage entry
     let body = identity_body(&identity)?;
     let identity_hash = compute_identity_hash(&body);
+    let lineage_sig = sign_lineage(identity_hash.as_bytes())?;
 
     // Create the initial lineage entry
     let lineage_entry = LineageEntry {
@@ -180,6 +543,9 @@ pub fn create_identity(opts: CreateIdentityOptions) -> Result<AgentIdentity, Ste
         change_type: "created".to_string(),
         description: "Identity created".to_string(),
         timestamp: now,
+        operator_keys: identity.operator_keys.clone(),
+        signer_public_key: lineage_signer_key_hex.to_string(),
+        signature: hex::encode(&lineage_sig),
     };
 
     identity.lineage = vec![lineage_entry];
@@ -189,11 +555,6 @@ pub fn create_identity(opts: CreateIdentityOptions) -> Result<AgentIdentity, Ste
     let final_hash = compute_identity_hash(&body_with_lineage);
     identity.id = final_hash;
 
-    // Sign the identity
-    let signing_payload = crypto::canonicalize_json(&identity_body(&identity)?);
-    let sig_bytes = crypto::sign(signing_payload.as_bytes(), &opts.signing_key)?;
-    identity.signature = hex::encode(&sig_bytes);
-
     Ok(identity)
 }
 
@@ -213,45 +574,255 @@ pub fn evolve_identity(
     identity: &AgentIdentity,
     opts: EvolveIdentityOptions,
 ) -> Result<AgentIdentity, SteleError> {
-    if opts.change_type.is_empty() {
+    let mut lifecycle_span = telemetry::IdentityLifecycleSpan::start("identity.evolve");
+    let change_type_for_metrics = opts.change_type.clone();
+
+    let lineage_signer = pick_lineage_signer(&opts.signing_keys, &identity.operator_keys)?;
+    let lineage_signer_key_hex = hex::encode(lineage_signer.verifying_key().as_bytes());
+
+    let mut evolved = draft_evolved_identity(
+        identity,
+        opts.change_type,
+        opts.description,
+        opts.model,
+        opts.capabilities,
+        opts.deployment,
+        opts.new_operator_keys,
+        &lineage_signer_key_hex,
+        |payload| crypto::sign(payload, lineage_signer),
+    )?;
+
+    let signing_payload = crypto::canonicalize_json(&identity_body(&evolved)?);
+    let sign_start = std::time::Instant::now();
+    evolved.signatures = sign_with_all(&signing_payload, &opts.signing_keys)?;
+    telemetry::record_signing_latency_ms(sign_start.elapsed().as_secs_f64() * 1000.0);
+
+    lifecycle_span.set_model(&evolved.model.provider, &evolved.model.model_id);
+    lifecycle_span.set_capability_count(evolved.capabilities.len());
+    lifecycle_span.set_version(evolved.version);
+    telemetry::record_identity_evolved(&change_type_for_metrics);
+
+    Ok(evolved)
+}
+
+/// Evolve an identity created with [`create_identity_ecdsa_p256`].
+///
+/// # Errors
+/// Returns `SteleError::InvalidInput` if `identity.alg` is not
+/// `EcdsaP256` -- evolving with a different key type than the identity
+/// was created with would silently change its trust anchor, so it's
+/// rejected rather than allowed to re-sign under a mismatched algorithm.
+/// Same error conditions as [`evolve_identity`] otherwise.
+#[allow(clippy::too_many_arguments)]
+pub fn evolve_identity_ecdsa_p256(
+    identity: &AgentIdentity,
+    change_type: String,
+    description: String,
+    model: Option<ModelInfo>,
+    capabilities: Option<Vec<String>>,
+    deployment: Option<DeploymentInfo>,
+    signing_key: &p256::ecdsa::SigningKey,
+) -> Result<AgentIdentity, SteleError> {
+    if identity.alg != crypto::SignatureAlgorithm::EcdsaP256 {
+        return Err(SteleError::InvalidInput(
+            "identity was not created with an EcdsaP256 key".to_string(),
+        ));
+    }
+    let lineage_signer_key_hex = identity.operator_keys.keys[0].clone();
+    let mut evolved = draft_evolved_identity(
+        identity,
+        change_type,
+        description,
+        model,
+        capabilities,
+        deployment,
+        None,
+        &lineage_signer_key_hex,
+        |payload| crypto::sign_ecdsa_p256(payload, signing_key),
+    )?;
+
+    let signing_payload = crypto::canonicalize_json(&identity_body(&evolved)?);
+    let sig_bytes = crypto::sign_ecdsa_p256(signing_payload.as_bytes(), signing_key)?;
+    evolved.signatures = vec![OperatorSignature {
+        signer_key: evolved.operator_keys.keys[0].clone(),
+        signature: hex::encode(&sig_bytes),
+    }];
+
+    Ok(evolved)
+}
+
+/// Evolve an identity created with [`create_identity_rsa2048`].
+///
+/// # Errors
+/// Same error conditions as [`evolve_identity_ecdsa_p256`], checked
+/// against `SignatureAlgorithm::Rsa2048` instead.
+#[allow(clippy::too_many_arguments)]
+pub fn evolve_identity_rsa2048(
+    identity: &AgentIdentity,
+    change_type: String,
+    description: String,
+    model: Option<ModelInfo>,
+    capabilities: Option<Vec<String>>,
+    deployment: Option<DeploymentInfo>,
+    signing_key: &rsa::RsaPrivateKey,
+) -> Result<AgentIdentity, SteleError> {
+    if identity.alg != crypto::SignatureAlgorithm::Rsa2048 {
+        return Err(SteleError::InvalidInput(
+            "identity was not created with an Rsa2048 key".to_string(),
+        ));
+    }
+    let lineage_signer_key_hex = identity.operator_keys.keys[0].clone();
+    let mut evolved = draft_evolved_identity(
+        identity,
+        change_type,
+        description,
+        model,
+        capabilities,
+        deployment,
+        None,
+        &lineage_signer_key_hex,
+        |payload| crypto::sign_rsa2048(payload, signing_key),
+    )?;
+
+    let signing_payload = crypto::canonicalize_json(&identity_body(&evolved)?);
+    let sig_bytes = crypto::sign_rsa2048(signing_payload.as_bytes(), signing_key)?;
+    evolved.signatures = vec![OperatorSignature {
+        signer_key: evolved.operator_keys.keys[0].clone(),
+        signature: hex::encode(&sig_bytes),
+    }];
+
+    Ok(evolved)
+}
+
+/// Evolve an existing agent identity using any `&dyn Signer` backend.
+///
+/// Identical to [`evolve_identity`] except the operator signature is
+/// produced by a pluggable [`Signer`] over the SHA-256 digest of the
+/// canonicalized identity body.
+///
+/// # Errors
+/// Same error conditions as [`evolve_identity`], plus any error surfaced
+/// by the backing `Signer`.
+#[allow(clippy::too_many_arguments)]
+pub fn evolve_identity_with_signer(
+    identity: &AgentIdentity,
+    change_type: String,
+    description: String,
+    model: Option<ModelInfo>,
+    capabilities: Option<Vec<String>>,
+    deployment: Option<DeploymentInfo>,
+    signer: &dyn Signer,
+) -> Result<AgentIdentity, SteleError> {
+    let lineage_signer_key_hex = identity.operator_keys.keys[0].clone();
+    let mut evolved = draft_evolved_identity(
+        identity,
+        change_type,
+        description,
+        model,
+        capabilities,
+        deployment,
+        None,
+        &lineage_signer_key_hex,
+        |identity_hash| signer.sign_digest(&lineage_digest(identity_hash)),
+    )?;
+
+    let signing_payload = crypto::canonicalize_json(&identity_body(&evolved)?);
+    let digest = crypto::signer::signing_digest(&signing_payload);
+    let sig_bytes = signer.sign_digest(&digest)?;
+    evolved.signatures = vec![OperatorSignature {
+        signer_key: evolved.operator_keys.keys[0].clone(),
+        signature: hex::encode(&sig_bytes),
+    }];
+
+    Ok(evolved)
+}
+
+/// Validate inputs and assemble an unsigned, evolved `AgentIdentity` with
+/// its appended lineage entry and recomputed `id`, shared by every
+/// evolve path. The returned identity has an empty `signatures` list
+/// awaiting the caller's signing step; the new lineage entry, however,
+/// is already signed by `lineage_signer_key_hex` (via `sign_lineage`)
+/// since it must be authorized under the parent version's key set
+/// before `id` can be computed.
+///
+/// `new_operator_keys` rotates `operator_keys` to a new set and is only
+/// valid when `change_type == "key_rotation"`; the new set takes effect
+/// starting with the *next* evolution, not this one -- this entry's own
+/// `signer_public_key` must still belong to the *parent* (pre-rotation)
+/// key set.
+///
+/// # Errors
+/// Returns `SteleError::InvalidInput` if `change_type`/`description`
+/// are empty, if `new_operator_keys` is supplied without
+/// `change_type == "key_rotation"` (or vice versa), or if
+/// `lineage_signer_key_hex` isn't a member of `identity.operator_keys`.
+#[allow(clippy::too_many_arguments)]
+fn draft_evolved_identity(
+    identity: &AgentIdentity,
+    change_type: String,
+    description: String,
+    model: Option<ModelInfo>,
+    capabilities: Option<Vec<String>>,
+    deployment: Option<DeploymentInfo>,
+    new_operator_keys: Option<KeySet>,
+    lineage_signer_key_hex: &str,
+    sign_lineage: impl Fn(&[u8]) -> Result<Vec<u8>, SteleError>,
+) -> Result<AgentIdentity, SteleError> {
+    if change_type.is_empty() {
         return Err(SteleError::InvalidInput(
             "changeType is required for evolution".to_string(),
         ));
     }
-    if opts.description.is_empty() {
+    if description.is_empty() {
         return Err(SteleError::InvalidInput(
             "description is required for evolution".to_string(),
         ));
     }
+    if new_operator_keys.is_some() && change_type != "key_rotation" {
+        return Err(SteleError::InvalidInput(
+            "new_operator_keys may only be set when changeType is \"key_rotation\"".to_string(),
+        ));
+    }
+    if new_operator_keys.is_none() && change_type == "key_rotation" {
+        return Err(SteleError::InvalidInput(
+            "changeType \"key_rotation\" requires new_operator_keys".to_string(),
+        ));
+    }
+    if !identity.operator_keys.keys.iter().any(|k| k == lineage_signer_key_hex) {
+        return Err(SteleError::InvalidInput(
+            "lineage signer must be a member of the parent version's operator key set".to_string(),
+        ));
+    }
 
     let now = crypto::timestamp();
 
     // Apply updates
-    let model = opts.model.unwrap_or_else(|| identity.model.clone());
-    let mut capabilities = opts
-        .capabilities
-        .unwrap_or_else(|| identity.capabilities.clone());
+    let model = model.unwrap_or_else(|| identity.model.clone());
+    let mut capabilities = capabilities.unwrap_or_else(|| identity.capabilities.clone());
     capabilities.sort();
-    let deployment = opts
-        .deployment
-        .unwrap_or_else(|| identity.deployment.clone());
+    let deployment = deployment.unwrap_or_else(|| identity.deployment.clone());
+    let operator_keys = new_operator_keys.unwrap_or_else(|| identity.operator_keys.clone());
 
     // Build the evolved identity
     let mut evolved = AgentIdentity {
         id: String::new(),
-        operator_public_key: identity.operator_public_key.clone(),
+        operator_keys,
         model,
         capabilities,
         deployment,
         version: identity.version + 1,
         lineage: identity.lineage.clone(),
-        signature: String::new(),
+        signatures: Vec::new(),
         created_at: identity.created_at.clone(),
+        attestation_hash: identity.attestation_hash.clone(),
+        delegation: identity.delegation.clone(),
+        alg: identity.alg,
     };
 
     // Compute the new identity hash
     let body = identity_body(&evolved)?;
     let new_hash = compute_identity_hash(&body);
+    let lineage_sig = sign_lineage(new_hash.as_bytes())?;
 
     // Get the parent hash (last lineage entry's identity_hash)
     let parent_hash = identity.lineage.last().map(|e| e.identity_hash.clone());
@@ -260,9 +831,12 @@ pub fn evolve_identity(
     let lineage_entry = LineageEntry {
         identity_hash: new_hash,
         parent_hash,
-        change_type: opts.change_type,
-        description: opts.description,
+        change_type,
+        description,
         timestamp: now,
+        operator_keys: evolved.operator_keys.clone(),
+        signer_public_key: lineage_signer_key_hex.to_string(),
+        signature: hex::encode(&lineage_sig),
     };
 
     evolved.lineage.push(lineage_entry);
@@ -272,11 +846,6 @@ pub fn evolve_identity(
     let final_hash = compute_identity_hash(&body_with_lineage);
     evolved.id = final_hash;
 
-    // Sign
-    let signing_payload = crypto::canonicalize_json(&identity_body(&evolved)?);
-    let sig_bytes = crypto::sign(signing_payload.as_bytes(), &opts.signing_key)?;
-    evolved.signature = hex::encode(&sig_bytes);
-
     Ok(evolved)
 }
 
@@ -284,16 +853,23 @@ pub fn evolve_identity(
 // Verify identity
 // ---------------------------------------------------------------------------
 
-/// Verify an agent identity's integrity and signature.
+/// Verify an agent identity's integrity and signatures.
 ///
 /// Checks:
 /// 1. `id_match` -- ID matches the hash of the identity body
-/// 2. `signature_valid` -- Operator signature is valid
+/// 2. `signature_valid` -- Every attached signature is cryptographically valid
 /// 3. `lineage_chain` -- Lineage entries form a valid hash chain
 /// 4. `version_match` -- Version matches lineage length
+/// 5. `threshold_met` -- At least `operator_keys.threshold` *distinct*
+///    keys from the key set produced a valid signature
+/// 6. `lineage_authorized` -- Every lineage entry's own signature is
+///    valid and was produced by a key belonging to the *prior* entry's
+///    key set (the genesis entry's own key set, for itself), so an
+///    attacker holding only the current key cannot rewrite history
 pub fn verify_identity(
     identity: &AgentIdentity,
 ) -> Result<IdentityVerificationResult, SteleError> {
+    let mut verification_span = telemetry::IdentityVerificationSpan::start(&identity.id);
     let mut checks: Vec<IdentityCheck> = Vec::new();
 
     // 1. ID match
@@ -312,26 +888,26 @@ pub fn verify_identity(
         },
     });
 
-    // 2. Signature valid
+    // 2. Signature valid -- every attached signature must verify
+    // against its own claimed `signer_key` under `identity.alg`, so a
+    // public key whose encoding doesn't match the declared algorithm
+    // (e.g. an Ed25519 key paired with a claimed `EcdsaP256`) is
+    // rejected as a decoding failure rather than silently accepted.
     let signing_payload = crypto::canonicalize_json(&body);
-    let sig_bytes = hex::decode(&identity.signature).unwrap_or_default();
-    let pub_key_bytes = hex::decode(&identity.operator_public_key).unwrap_or_default();
-    let pub_array: [u8; 32] = pub_key_bytes
-        .as_slice()
-        .try_into()
-        .unwrap_or([0u8; 32]);
-    let sig_valid = if let Ok(vk) = ed25519_dalek::VerifyingKey::from_bytes(&pub_array) {
-        crypto::verify(signing_payload.as_bytes(), &sig_bytes, &vk)
-    } else {
-        false
-    };
+    let all_sigs_valid = !identity.signatures.is_empty()
+        && identity.signatures.iter().all(|sig| {
+            let sig_bytes = hex::decode(&sig.signature).unwrap_or_default();
+            crypto::verify_signature(identity.alg, signing_payload.as_bytes(), &sig_bytes, &sig.signer_key)
+        });
     checks.push(IdentityCheck {
         name: "signature_valid".to_string(),
-        passed: sig_valid,
-        message: if sig_valid {
-            "Operator signature is valid".to_string()
+        passed: all_sigs_valid,
+        message: if all_sigs_valid {
+            format!("All {} attached signature(s) are valid", identity.signatures.len())
+        } else if identity.signatures.is_empty() {
+            "No signatures present".to_string()
         } else {
-            "Operator signature verification failed".to_string()
+            "One or more attached signatures failed verification".to_string()
         },
     });
 
@@ -379,7 +955,76 @@ pub fn verify_identity(
         },
     });
 
+    // 5. Threshold met -- count *distinct* keys in `operator_keys.keys`
+    // whose attached signature verifies; a key set's worth of garbage
+    // signatures, or ten signatures from the same key, must not count
+    // as more than one toward the threshold.
+    let mut valid_signers: Vec<&str> = identity
+        .signatures
+        .iter()
+        .filter(|sig| identity.operator_keys.keys.iter().any(|key| key == &sig.signer_key))
+        .filter(|sig| {
+            let sig_bytes = hex::decode(&sig.signature).unwrap_or_default();
+            crypto::verify_signature(identity.alg, signing_payload.as_bytes(), &sig_bytes, &sig.signer_key)
+        })
+        .map(|sig| sig.signer_key.as_str())
+        .collect();
+    valid_signers.sort_unstable();
+    valid_signers.dedup();
+    let threshold_met = valid_signers.len() >= identity.operator_keys.threshold.get();
+    checks.push(IdentityCheck {
+        name: "threshold_met".to_string(),
+        passed: threshold_met,
+        message: format!(
+            "{} distinct valid operator signature(s), {} required",
+            valid_signers.len(),
+            identity.operator_keys.threshold
+        ),
+    });
+
+    // 6. Lineage authorized -- each entry's signature must verify under
+    // its own claimed `signer_public_key`, and that key must belong to
+    // the *parent* entry's key set (the genesis entry has no parent, so
+    // it's checked against its own key set instead). A `key_rotation`
+    // entry's own `operator_keys` is the *new*, post-rotation set, so
+    // it only becomes the authorizing set starting with the *next*
+    // entry -- which is exactly what comparing against `lineage[i - 1]`
+    // (rather than `lineage[i]`) gives us for free.
+    let mut lineage_authorized = true;
+    let mut lineage_authorized_msg = "Every evolution is cryptographically authorized by its prior version".to_string();
+    for (i, entry) in identity.lineage.iter().enumerate() {
+        let authorizing_keys = if i == 0 { &entry.operator_keys } else { &identity.lineage[i - 1].operator_keys };
+
+        let signer_authorized = authorizing_keys.keys.iter().any(|k| k == &entry.signer_public_key);
+        let sig_bytes = hex::decode(&entry.signature).unwrap_or_default();
+        let sig_valid = crypto::verify_signature(identity.alg, entry.identity_hash.as_bytes(), &sig_bytes, &entry.signer_public_key);
+
+        if !signer_authorized || !sig_valid {
+            lineage_authorized = false;
+            lineage_authorized_msg = format!(
+                "Lineage entry {} not authorized: signer {} {}",
+                i,
+                entry.signer_public_key,
+                if !signer_authorized {
+                    "was not a member of the prior version's key set"
+                } else {
+                    "signature failed verification"
+                }
+            );
+            break;
+        }
+    }
+    checks.push(IdentityCheck {
+        name: "lineage_authorized".to_string(),
+        passed: lineage_authorized,
+        message: lineage_authorized_msg,
+    });
+
     let valid = checks.iter().all(|c| c.passed);
+    for check in &checks {
+        verification_span.record_check(&check.name, check.passed);
+    }
+    verification_span.finish(valid);
 
     Ok(IdentityVerificationResult { valid, checks })
 }
@@ -408,8 +1053,8 @@ mod tests {
     fn test_create_identity() {
         let kp = crypto::generate_key_pair().unwrap();
         let identity = create_identity(CreateIdentityOptions {
-            signing_key: kp.signing_key,
-            public_key_hex: kp.public_key_hex,
+            signing_keys: vec![kp.signing_key],
+            threshold: 1,
             model: ModelInfo {
                 provider: "anthropic".to_string(),
                 model_id: "claude-3".to_string(),
@@ -431,8 +1076,8 @@ mod tests {
     fn test_evolve_identity() {
         let kp = crypto::generate_key_pair().unwrap();
         let identity = create_identity(CreateIdentityOptions {
-            signing_key: kp.signing_key.clone(),
-            public_key_hex: kp.public_key_hex.clone(),
+            signing_keys: vec![kp.signing_key.clone()],
+            threshold: 1,
             model: ModelInfo {
                 provider: "anthropic".to_string(),
                 model_id: "claude-3".to_string(),
@@ -447,12 +1092,13 @@ mod tests {
         let evolved = evolve_identity(
             &identity,
             EvolveIdentityOptions {
-                signing_key: kp.signing_key,
+                signing_keys: vec![kp.signing_key],
                 change_type: "capability_change".to_string(),
                 description: "Added write capability".to_string(),
                 model: None,
                 capabilities: Some(vec!["read".to_string(), "write".to_string()]),
                 deployment: None,
+                new_operator_keys: None,
             },
         )
         .unwrap();
@@ -466,8 +1112,8 @@ mod tests {
     fn test_serialize_deserialize_identity() {
         let kp = crypto::generate_key_pair().unwrap();
         let identity = create_identity(CreateIdentityOptions {
-            signing_key: kp.signing_key,
-            public_key_hex: kp.public_key_hex,
+            signing_keys: vec![kp.signing_key],
+            threshold: 1,
             model: ModelInfo {
                 provider: "anthropic".to_string(),
                 model_id: "claude-3".to_string(),
@@ -482,6 +1128,434 @@ mod tests {
         let json = serialize_identity(&identity).unwrap();
         let restored = deserialize_identity(&json).unwrap();
         assert_eq!(identity.id, restored.id);
-        assert_eq!(identity.signature, restored.signature);
+        assert_eq!(identity.signatures.len(), restored.signatures.len());
+    }
+
+    #[test]
+    fn test_threshold_identity_requires_m_of_n_signatures() {
+        let kp1 = crypto::generate_key_pair().unwrap();
+        let kp2 = crypto::generate_key_pair().unwrap();
+        let kp3 = crypto::generate_key_pair().unwrap();
+
+        let identity = create_identity(CreateIdentityOptions {
+            signing_keys: vec![kp1.signing_key, kp2.signing_key],
+            threshold: 2,
+            model: ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            capabilities: vec!["read".to_string()],
+            deployment: DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+        })
+        .unwrap();
+
+        assert_eq!(identity.operator_keys.keys.len(), 2);
+        assert_eq!(identity.signatures.len(), 2);
+        let result = verify_identity(&identity).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+
+        // Drop one of the two required signatures: threshold is no longer met.
+        let mut under_threshold = identity.clone();
+        under_threshold.signatures.pop();
+        let result = verify_identity(&under_threshold).unwrap();
+        assert!(!result.valid);
+        assert!(result.checks.iter().any(|c| c.name == "threshold_met" && !c.passed));
+
+        // A signature from a key outside the set doesn't count toward it.
+        let mut with_outside_signer = identity.clone();
+        with_outside_signer.signatures.pop();
+        with_outside_signer.signatures.push(OperatorSignature {
+            signer_key: kp3.public_key_hex,
+            signature: hex::encode(crypto::sign(b"garbage", &kp3.signing_key).unwrap()),
+        });
+        let result = verify_identity(&with_outside_signer).unwrap();
+        assert!(!result.valid);
+        assert!(result.checks.iter().any(|c| c.name == "threshold_met" && !c.passed));
+    }
+
+    #[test]
+    fn test_create_identity_rejects_zero_threshold() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let result = create_identity(CreateIdentityOptions {
+            signing_keys: vec![kp.signing_key],
+            threshold: 0,
+            model: ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            capabilities: vec!["read".to_string()],
+            deployment: DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_create_identity_rejects_threshold_above_key_count() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let result = create_identity(CreateIdentityOptions {
+            signing_keys: vec![kp.signing_key],
+            threshold: 2,
+            model: ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            capabilities: vec!["read".to_string()],
+            deployment: DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_key_set_dedupes_duplicate_keys() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let keys = KeySet::new(vec![kp.public_key_hex.clone(), kp.public_key_hex], 1).unwrap();
+        assert_eq!(keys.keys.len(), 1);
+    }
+
+    #[test]
+    fn test_create_identity_with_signer() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let signer = crate::crypto::signer::SoftwareSigner::new(&kp);
+        let identity = create_identity_with_signer(
+            kp.public_key_hex,
+            ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            vec!["read".to_string()],
+            DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+            &signer,
+        )
+        .unwrap();
+
+        let result = verify_identity(&identity).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_create_identity_defaults_to_ed25519_alg() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let identity = create_identity(CreateIdentityOptions {
+            signing_keys: vec![kp.signing_key],
+            threshold: 1,
+            model: ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            capabilities: vec!["read".to_string()],
+            deployment: DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+        })
+        .unwrap();
+
+        assert_eq!(identity.alg, crypto::SignatureAlgorithm::Ed25519);
+    }
+
+    #[test]
+    fn test_create_and_evolve_identity_ecdsa_p256() {
+        let kp = crypto::generate_ecdsa_p256_key_pair().unwrap();
+        let identity = create_identity_ecdsa_p256(
+            kp.public_key_hex.clone(),
+            ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            vec!["read".to_string()],
+            DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+            &kp.signing_key,
+        )
+        .unwrap();
+
+        assert_eq!(identity.alg, crypto::SignatureAlgorithm::EcdsaP256);
+        let result = verify_identity(&identity).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+
+        let evolved = evolve_identity_ecdsa_p256(
+            &identity,
+            "capability_change".to_string(),
+            "Added write capability".to_string(),
+            None,
+            Some(vec!["read".to_string(), "write".to_string()]),
+            None,
+            &kp.signing_key,
+        )
+        .unwrap();
+        let evolved_result = verify_identity(&evolved).unwrap();
+        assert!(evolved_result.valid, "Verification failed: {:?}", evolved_result.checks);
+    }
+
+    #[test]
+    fn test_create_and_evolve_identity_rsa2048() {
+        let kp = crypto::generate_rsa2048_key_pair().unwrap();
+        let identity = create_identity_rsa2048(
+            kp.public_key_hex.clone(),
+            ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            vec!["read".to_string()],
+            DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+            &kp.signing_key,
+        )
+        .unwrap();
+
+        assert_eq!(identity.alg, crypto::SignatureAlgorithm::Rsa2048);
+        let result = verify_identity(&identity).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+
+        let evolved = evolve_identity_rsa2048(
+            &identity,
+            "capability_change".to_string(),
+            "Added write capability".to_string(),
+            None,
+            Some(vec!["read".to_string(), "write".to_string()]),
+            None,
+            &kp.signing_key,
+        )
+        .unwrap();
+        let evolved_result = verify_identity(&evolved).unwrap();
+        assert!(evolved_result.valid, "Verification failed: {:?}", evolved_result.checks);
+    }
+
+    #[test]
+    fn test_evolve_identity_rejects_algorithm_mismatch() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let identity = create_identity(CreateIdentityOptions {
+            signing_keys: vec![kp.signing_key],
+            threshold: 1,
+            model: ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            capabilities: vec!["read".to_string()],
+            deployment: DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+        })
+        .unwrap();
+
+        let ecdsa_kp = crypto::generate_ecdsa_p256_key_pair().unwrap();
+        let result = evolve_identity_ecdsa_p256(
+            &identity,
+            "capability_change".to_string(),
+            "Added write capability".to_string(),
+            None,
+            None,
+            None,
+            &ecdsa_kp.signing_key,
+        );
+        assert!(result.is_err(), "evolving an Ed25519 identity with an EcdsaP256 key should fail");
+    }
+
+    #[test]
+    fn test_verify_identity_rejects_tampered_alg() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let mut identity = create_identity(CreateIdentityOptions {
+            signing_keys: vec![kp.signing_key],
+            threshold: 1,
+            model: ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            capabilities: vec!["read".to_string()],
+            deployment: DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+        })
+        .unwrap();
+
+        // Claiming a different algorithm than the one that actually
+        // signed the body must invalidate the signature, not be quietly
+        // accepted.
+        identity.alg = crypto::SignatureAlgorithm::EcdsaP256;
+        let result = verify_identity(&identity).unwrap();
+        assert!(!result.valid);
+        assert!(result.checks.iter().any(|c| c.name == "signature_valid" && !c.passed));
+    }
+
+    #[test]
+    fn test_evolve_identity_rejects_signer_not_in_current_key_set() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let identity = create_identity(CreateIdentityOptions {
+            signing_keys: vec![kp.signing_key],
+            threshold: 1,
+            model: ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            capabilities: vec!["read".to_string()],
+            deployment: DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+        })
+        .unwrap();
+
+        let outsider = crypto::generate_key_pair().unwrap();
+        let result = evolve_identity(
+            &identity,
+            EvolveIdentityOptions {
+                signing_keys: vec![outsider.signing_key],
+                change_type: "capability_change".to_string(),
+                description: "Added write capability".to_string(),
+                model: None,
+                capabilities: Some(vec!["read".to_string(), "write".to_string()]),
+                deployment: None,
+                new_operator_keys: None,
+            },
+        );
+        assert!(result.is_err(), "evolving with a key outside the operator set should fail");
+    }
+
+    #[test]
+    fn test_verify_identity_rejects_rewritten_lineage() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let identity = create_identity(CreateIdentityOptions {
+            signing_keys: vec![kp.signing_key.clone()],
+            threshold: 1,
+            model: ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            capabilities: vec!["read".to_string()],
+            deployment: DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+        })
+        .unwrap();
+
+        let evolved = evolve_identity(
+            &identity,
+            EvolveIdentityOptions {
+                signing_keys: vec![kp.signing_key],
+                change_type: "capability_change".to_string(),
+                description: "Added write capability".to_string(),
+                model: None,
+                capabilities: Some(vec!["read".to_string(), "write".to_string()]),
+                deployment: None,
+                new_operator_keys: None,
+            },
+        )
+        .unwrap();
+        let result = verify_identity(&evolved).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+
+        // An attacker who only controls a *new, unrelated* key tries to
+        // rewrite the genesis entry's authorization so it looks as if
+        // their key always controlled the identity.
+        let attacker = crypto::generate_key_pair().unwrap();
+        let mut rewritten = evolved.clone();
+        let forged_sig = crypto::sign(
+            rewritten.lineage[0].identity_hash.as_bytes(),
+            &attacker.signing_key,
+        )
+        .unwrap();
+        rewritten.lineage[0].signer_public_key = attacker.public_key_hex;
+        rewritten.lineage[0].signature = hex::encode(&forged_sig);
+
+        let result = verify_identity(&rewritten).unwrap();
+        assert!(!result.valid);
+        assert!(result.checks.iter().any(|c| c.name == "lineage_authorized" && !c.passed));
+    }
+
+    #[test]
+    fn test_key_rotation_takes_effect_for_next_evolution_only() {
+        let old_kp = crypto::generate_key_pair().unwrap();
+        let new_kp = crypto::generate_key_pair().unwrap();
+
+        let identity = create_identity(CreateIdentityOptions {
+            signing_keys: vec![old_kp.signing_key.clone()],
+            threshold: 1,
+            model: ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            capabilities: vec!["read".to_string()],
+            deployment: DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+        })
+        .unwrap();
+
+        let new_keys = KeySet::new(vec![new_kp.public_key_hex.clone()], 1).unwrap();
+
+        // The rotation entry must be authorized by the *old* key, even
+        // though it installs the new key set.
+        let rotated = evolve_identity(
+            &identity,
+            EvolveIdentityOptions {
+                signing_keys: vec![old_kp.signing_key.clone(), new_kp.signing_key.clone()],
+                change_type: "key_rotation".to_string(),
+                description: "Rotating to a fresh operator key".to_string(),
+                model: None,
+                capabilities: None,
+                deployment: None,
+                new_operator_keys: Some(new_keys),
+            },
+        )
+        .unwrap();
+        assert_eq!(rotated.operator_keys.keys, vec![new_kp.public_key_hex.clone()]);
+        let result = verify_identity(&rotated).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
+
+        // Attempting to rotate using only the old key, without
+        // supplying new_operator_keys, is not a rotation at all.
+        let bad_rotation = evolve_identity(
+            &identity,
+            EvolveIdentityOptions {
+                signing_keys: vec![old_kp.signing_key.clone()],
+                change_type: "key_rotation".to_string(),
+                description: "Missing new key set".to_string(),
+                model: None,
+                capabilities: None,
+                deployment: None,
+                new_operator_keys: None,
+            },
+        );
+        assert!(bad_rotation.is_err());
+
+        // The next evolution after rotation must be authorized by the
+        // *new* key -- the old key no longer counts.
+        let stale_key_evolution = evolve_identity(
+            &rotated,
+            EvolveIdentityOptions {
+                signing_keys: vec![old_kp.signing_key],
+                change_type: "capability_change".to_string(),
+                description: "Attempting to evolve with the retired key".to_string(),
+                model: None,
+                capabilities: Some(vec!["read".to_string(), "write".to_string()]),
+                deployment: None,
+                new_operator_keys: None,
+            },
+        );
+        assert!(stale_key_evolution.is_err(), "the retired key should no longer authorize evolutions");
+
+        let evolved_with_new_key = evolve_identity(
+            &rotated,
+            EvolveIdentityOptions {
+                signing_keys: vec![new_kp.signing_key],
+                change_type: "capability_change".to_string(),
+                description: "Evolving with the rotated-in key".to_string(),
+                model: None,
+                capabilities: Some(vec!["read".to_string(), "write".to_string()]),
+                deployment: None,
+                new_operator_keys: None,
+            },
+        )
+        .unwrap();
+        let result = verify_identity(&evolved_with_new_key).unwrap();
+        assert!(result.valid, "Verification failed: {:?}", result.checks);
     }
 }
@@ -0,0 +1,265 @@
+//! TEE remote-attestation binding for agent identities.
+//!
+//! Proves an identity's signing key was generated, and lives, inside a
+//! trusted execution environment: [`bind_attestation`] commits a hash of
+//! the attestation quote into the identity document before it is
+//! signed, and [`verify_attested_identity`] checks that the quote's
+//! report data commits to the identity's operator public key. This
+//! lets a relying party distinguish covenants signed by an attested
+//! agent runtime from those signed by an arbitrary key.
+//!
+//! Parsing and chain-of-trust verification of the raw quote itself
+//! (e.g. Intel DCAP for SGX, AMD SEV-SNP's attestation report) is
+//! platform- and vendor-specific and out of scope here. Callers are
+//! expected to have already verified the quote against the TEE
+//! vendor's root of trust, and hand this module the quote bytes plus
+//! the report data the enclave embedded in it.
+
+use super::{
+    compute_identity_hash, draft_identity, identity_body, lineage_digest, AgentIdentity,
+    DeploymentInfo, KeySet, ModelInfo, OperatorSignature,
+};
+use crate::crypto;
+use crate::crypto::signer::Signer;
+use crate::SteleError;
+use serde::{Deserialize, Serialize};
+
+/// A TEE remote-attestation quote bound to an agent identity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attestation {
+    /// The raw attestation quote, hex-encoded.
+    pub quote: String,
+    /// The enclave measurement (e.g. SGX MRENCLAVE), hex-encoded.
+    #[serde(rename = "enclaveMeasurement")]
+    pub enclave_measurement: String,
+    /// Report data the enclave embedded in the quote, hex-encoded.
+    #[serde(rename = "reportData")]
+    pub report_data: String,
+}
+
+/// A single attestation verification check and its result.
+#[derive(Debug)]
+pub struct AttestationCheck {
+    pub name: String,
+    pub passed: bool,
+    pub message: String,
+}
+
+/// Complete result of verifying an identity's bound attestation.
+#[derive(Debug)]
+pub struct AttestationVerificationResult {
+    pub valid: bool,
+    pub checks: Vec<AttestationCheck>,
+}
+
+/// Compute the content hash of an attestation, for inclusion in (and
+/// later comparison against) an identity document's `attestationHash`.
+pub fn attestation_hash(attestation: &Attestation) -> String {
+    let canonical = format!(
+        "{}:{}:{}",
+        attestation.quote, attestation.enclave_measurement, attestation.report_data
+    );
+    crypto::sha256_string(&canonical)
+}
+
+/// The report data an attested identity's quote must commit to: the
+/// SHA-256 digest of the operator's hex-encoded public key.
+pub fn expected_report_data(operator_public_key_hex: &str) -> String {
+    crypto::sha256_string(operator_public_key_hex)
+}
+
+/// Create a brand-new agent identity bound to a TEE attestation, signed
+/// with any `&dyn Signer` backend.
+///
+/// Drafts the identity exactly as [`super::create_identity`] would,
+/// then commits `attestation_hash(attestation)` into the document as
+/// `attestationHash` and recomputes the identity `id` before signing,
+/// so the binding is covered by the signature.
+///
+/// # Errors
+/// Same error conditions as [`super::create_identity`].
+pub fn create_attested_identity(
+    public_key_hex: String,
+    model: ModelInfo,
+    capabilities: Vec<String>,
+    deployment: DeploymentInfo,
+    attestation: &Attestation,
+    signer: &dyn Signer,
+) -> Result<AgentIdentity, SteleError> {
+    let operator_keys = KeySet::single(public_key_hex.clone());
+    let identity = draft_identity(
+        operator_keys,
+        model,
+        capabilities,
+        deployment,
+        crypto::SignatureAlgorithm::Ed25519,
+        &public_key_hex,
+        |identity_hash| signer.sign_digest(&lineage_digest(identity_hash)),
+    )?;
+    let mut identity = bind_attestation(identity, attestation)?;
+
+    let signing_payload = crypto::canonicalize_json(&identity_body(&identity)?);
+    let digest = crypto::signer::signing_digest(&signing_payload);
+    let sig_bytes = signer.sign_digest(&digest)?;
+    identity.signatures = vec![OperatorSignature {
+        signer_key: public_key_hex,
+        signature: hex::encode(&sig_bytes),
+    }];
+
+    Ok(identity)
+}
+
+/// Bind `attestation` to an as-yet-unsigned identity: sets
+/// `attestation_hash` and recomputes `id` to cover it. Must be called
+/// before the identity is signed -- binding an already-signed identity
+/// invalidates its existing signature.
+pub fn bind_attestation(
+    mut identity: AgentIdentity,
+    attestation: &Attestation,
+) -> Result<AgentIdentity, SteleError> {
+    identity.attestation_hash = Some(attestation_hash(attestation));
+    let body = identity_body(&identity)?;
+    identity.id = compute_identity_hash(&body);
+    Ok(identity)
+}
+
+/// Verify that `identity` is bound to `attestation`, and that the
+/// attestation's report data commits to the identity's operator public
+/// key.
+///
+/// Checks:
+/// 1. `attestation_bound` -- `identity.attestation_hash` matches this attestation
+/// 2. `report_data_binds_key` -- the quote's report data commits to the operator's public key
+pub fn verify_attested_identity(
+    identity: &AgentIdentity,
+    attestation: &Attestation,
+) -> AttestationVerificationResult {
+    let mut checks: Vec<AttestationCheck> = Vec::new();
+
+    let expected_hash = attestation_hash(attestation);
+    let hash_matches = identity.attestation_hash.as_deref() == Some(expected_hash.as_str());
+    checks.push(AttestationCheck {
+        name: "attestation_bound".to_string(),
+        passed: hash_matches,
+        message: if hash_matches {
+            "Identity commits to this attestation".to_string()
+        } else {
+            "Identity's attestationHash does not match the supplied quote".to_string()
+        },
+    });
+
+    let expected_report = expected_report_data(
+        identity.operator_keys.keys.first().map(String::as_str).unwrap_or(""),
+    );
+    let report_matches = attestation.report_data == expected_report;
+    checks.push(AttestationCheck {
+        name: "report_data_binds_key".to_string(),
+        passed: report_matches,
+        message: if report_matches {
+            "Quote report data commits to the identity's public key".to_string()
+        } else {
+            "Quote report data does not commit to the identity's public key".to_string()
+        },
+    });
+
+    let valid = checks.iter().all(|c| c.passed);
+    AttestationVerificationResult { valid, checks }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::signer::SoftwareSigner;
+    use crate::identity::verify_identity;
+
+    fn make_attestation(operator_public_key_hex: &str) -> Attestation {
+        Attestation {
+            quote: "deadbeef".to_string(),
+            enclave_measurement: "f".repeat(64),
+            report_data: expected_report_data(operator_public_key_hex),
+        }
+    }
+
+    #[test]
+    fn test_create_attested_identity_roundtrip() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let signer = SoftwareSigner::new(&kp);
+        let attestation = make_attestation(&kp.public_key_hex);
+
+        let identity = create_attested_identity(
+            kp.public_key_hex.clone(),
+            ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            vec!["read".to_string()],
+            DeploymentInfo {
+                runtime: "sgx-enclave".to_string(),
+            },
+            &attestation,
+            &signer,
+        )
+        .unwrap();
+
+        assert!(identity.attestation_hash.is_some());
+
+        let identity_result = verify_identity(&identity).unwrap();
+        assert!(identity_result.valid, "Identity verification failed: {:?}", identity_result.checks);
+
+        let attestation_result = verify_attested_identity(&identity, &attestation);
+        assert!(attestation_result.valid, "Attestation verification failed: {:?}", attestation_result.checks);
+    }
+
+    #[test]
+    fn test_verify_attested_identity_rejects_wrong_report_data() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let signer = SoftwareSigner::new(&kp);
+        let attestation = make_attestation(&kp.public_key_hex);
+
+        let identity = create_attested_identity(
+            kp.public_key_hex.clone(),
+            ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            vec!["read".to_string()],
+            DeploymentInfo {
+                runtime: "sgx-enclave".to_string(),
+            },
+            &attestation,
+            &signer,
+        )
+        .unwrap();
+
+        let mut mismatched = attestation.clone();
+        mismatched.report_data = "0".repeat(64);
+        let result = verify_attested_identity(&identity, &mismatched);
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_bind_attestation_changes_id() {
+        let kp = crypto::generate_key_pair().unwrap();
+        let unattested = draft_identity(
+            KeySet::single(kp.public_key_hex.clone()),
+            ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            vec!["read".to_string()],
+            DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+            crypto::SignatureAlgorithm::Ed25519,
+            &kp.public_key_hex,
+            |payload| crypto::sign(payload, &kp.signing_key),
+        )
+        .unwrap();
+        let unattested_id = unattested.id.clone();
+
+        let attestation = make_attestation(&kp.public_key_hex);
+        let attested = bind_attestation(unattested, &attestation).unwrap();
+
+        assert_ne!(unattested_id, attested.id);
+    }
+}
@@ -0,0 +1,388 @@
+//! UCAN-style capability delegation between agent identities.
+//!
+//! [`delegate_identity`] mints a child identity whose `capabilities`
+//! must each narrow (never broaden) a capability the parent holds,
+//! recording a [`DelegationLink`] signed by one of the parent's
+//! operator keys over the child's `id`. [`verify_delegation`] re-checks
+//! both properties given a resolver callback to fetch the parent
+//! identity by id, so revoking, evolving away a capability, or simply
+//! deleting the parent invalidates every identity it delegated to.
+//!
+//! Capability strings use the `action:resource` shape (e.g.
+//! `"read:/data/reports/*"`) and narrow hierarchically rather than by
+//! exact-string equality: the `action` and `resource` halves are each
+//! matched against the parent's with the same `**`/`*` narrowing rules
+//! [`ccl::validate_narrowing`](crate::ccl::validate_narrowing) uses for
+//! covenant chains, so `"read:/data/**"` may delegate down to
+//! `"read:/data/reports/*"` but not the reverse.
+
+use super::{
+    draft_identity, identity_body, pick_lineage_signer, sign_with_all, AgentIdentity,
+    DeploymentInfo, KeySet, ModelInfo,
+};
+use crate::ccl;
+use crate::crypto;
+use crate::SteleError;
+use serde::{Deserialize, Serialize};
+
+/// Links a delegated identity back to the parent whose authority it was
+/// minted from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegationLink {
+    #[serde(rename = "parentId")]
+    pub parent_id: String,
+    /// Signature by one of the parent's operator keys over the child's
+    /// `id`, proving the parent actually authorized this delegation
+    /// rather than the child merely claiming `parent_id`.
+    #[serde(rename = "parentSignature")]
+    pub parent_signature: String,
+}
+
+/// A single delegation verification check and its result.
+#[derive(Debug)]
+pub struct DelegationCheck {
+    pub name: String,
+    pub passed: bool,
+    pub message: String,
+}
+
+/// Complete result of verifying a delegated identity's authority chain.
+#[derive(Debug)]
+pub struct DelegationVerificationResult {
+    pub valid: bool,
+    pub checks: Vec<DelegationCheck>,
+}
+
+/// Options for delegating a new child identity from a parent.
+///
+/// `signing_keys`/`threshold` form the child's own [`KeySet`], exactly
+/// as in [`super::CreateIdentityOptions`]. `parent_signing_keys` is
+/// searched for a key belonging to `parent.operator_keys`, used to
+/// produce the [`DelegationLink::parent_signature`].
+pub struct DelegateIdentityOptions {
+    pub signing_keys: Vec<ed25519_dalek::SigningKey>,
+    pub threshold: usize,
+    pub parent_signing_keys: Vec<ed25519_dalek::SigningKey>,
+    pub model: ModelInfo,
+    pub capabilities: Vec<String>,
+    pub deployment: DeploymentInfo,
+}
+
+/// Minimal lookup interface [`verify_delegation`] needs to fetch a
+/// parent identity by id, kept separate from
+/// [`did::IdentityStore`](super::did::IdentityStore) since that one is
+/// `std`-only and this needs to work under `no_std` too.
+pub trait ParentResolver {
+    fn resolve_parent(&self, parent_id: &str) -> Result<Option<AgentIdentity>, SteleError>;
+}
+
+impl<F> ParentResolver for F
+where
+    F: Fn(&str) -> Result<Option<AgentIdentity>, SteleError>,
+{
+    fn resolve_parent(&self, parent_id: &str) -> Result<Option<AgentIdentity>, SteleError> {
+        self(parent_id)
+    }
+}
+
+/// Is `capability` (an `action:resource` string) at most as broad as
+/// `parent_capability`? Both halves narrow hierarchically via
+/// [`ccl::is_subset_pattern`]; a capability with no `:` separator (or
+/// one that doesn't match the parent's shape) falls back to exact
+/// string equality, since there's no hierarchy to narrow within.
+fn capability_is_subset(capability: &str, parent_capability: &str) -> bool {
+    if capability == parent_capability {
+        return true;
+    }
+
+    match (capability.split_once(':'), parent_capability.split_once(':')) {
+        (Some((action, resource)), Some((parent_action, parent_resource))) => {
+            ccl::is_subset_pattern(action, parent_action, ".")
+                && ccl::is_subset_pattern(resource, parent_resource, "/")
+        }
+        _ => false,
+    }
+}
+
+/// Mint a child identity delegated from `parent`, attenuating its
+/// authority: every entry in `opts.capabilities` must narrow at least
+/// one of `parent.capabilities`.
+///
+/// # Errors
+/// Returns `SteleError::InvalidInput` if any requested capability
+/// broadens every one of the parent's capabilities, or if
+/// `opts.parent_signing_keys` contains no key belonging to
+/// `parent.operator_keys`. Other error conditions match
+/// [`super::create_identity`].
+pub fn delegate_identity(
+    parent: &AgentIdentity,
+    opts: DelegateIdentityOptions,
+) -> Result<AgentIdentity, SteleError> {
+    for capability in &opts.capabilities {
+        let narrows_parent = parent
+            .capabilities
+            .iter()
+            .any(|parent_capability| capability_is_subset(capability, parent_capability));
+        if !narrows_parent {
+            return Err(SteleError::InvalidInput(format!(
+                "capability '{}' does not narrow any of the parent's capabilities",
+                capability
+            )));
+        }
+    }
+
+    let parent_signer = pick_lineage_signer(&opts.parent_signing_keys, &parent.operator_keys)?;
+
+    let public_keys: Vec<String> = opts
+        .signing_keys
+        .iter()
+        .map(|key| hex::encode(key.verifying_key().as_bytes()))
+        .collect();
+    let operator_keys = KeySet::new(public_keys, opts.threshold)?;
+    let lineage_signer = pick_lineage_signer(&opts.signing_keys, &operator_keys)?;
+    let lineage_signer_key_hex = hex::encode(lineage_signer.verifying_key().as_bytes());
+
+    let mut child = draft_identity(
+        operator_keys,
+        opts.model,
+        opts.capabilities,
+        opts.deployment,
+        crypto::SignatureAlgorithm::Ed25519,
+        &lineage_signer_key_hex,
+        |payload| crypto::sign(payload, lineage_signer),
+    )?;
+
+    let parent_sig = crypto::sign(child.id.as_bytes(), parent_signer)?;
+    child.delegation = Some(DelegationLink {
+        parent_id: parent.id.clone(),
+        parent_signature: hex::encode(&parent_sig),
+    });
+
+    let signing_payload = crypto::canonicalize_json(&identity_body(&child)?);
+    child.signatures = sign_with_all(&signing_payload, &opts.signing_keys)?;
+
+    Ok(child)
+}
+
+/// Verify that `identity`'s delegation (if any) is still valid: its
+/// `parent_signature` verifies under a key belonging to the parent's
+/// *current* operator key set, and every one of `identity`'s
+/// capabilities still narrows one of the parent's.
+///
+/// An identity with no [`DelegationLink`] trivially passes both checks
+/// -- delegation validity is only meaningful for identities that claim
+/// to be delegated.
+///
+/// # Errors
+/// Propagates any error `resolver` returns while fetching the parent.
+pub fn verify_delegation(
+    identity: &AgentIdentity,
+    resolver: &impl ParentResolver,
+) -> Result<DelegationVerificationResult, SteleError> {
+    let mut checks: Vec<DelegationCheck> = Vec::new();
+
+    let link = match &identity.delegation {
+        Some(link) => link,
+        None => {
+            checks.push(DelegationCheck {
+                name: "delegation_valid".to_string(),
+                passed: true,
+                message: "Identity is not delegated".to_string(),
+            });
+            return Ok(DelegationVerificationResult { valid: true, checks });
+        }
+    };
+
+    let parent = match resolver.resolve_parent(&link.parent_id)? {
+        Some(parent) => parent,
+        None => {
+            checks.push(DelegationCheck {
+                name: "delegation_valid".to_string(),
+                passed: false,
+                message: format!("Parent identity '{}' could not be resolved", link.parent_id),
+            });
+            return Ok(DelegationVerificationResult { valid: false, checks });
+        }
+    };
+
+    let sig_bytes = hex::decode(&link.parent_signature).unwrap_or_default();
+    let signer_authorized = parent
+        .operator_keys
+        .keys
+        .iter()
+        .any(|key| crypto::verify_signature(parent.alg, identity.id.as_bytes(), &sig_bytes, key));
+    checks.push(DelegationCheck {
+        name: "parent_signature_valid".to_string(),
+        passed: signer_authorized,
+        message: if signer_authorized {
+            "Parent signature verifies under the parent's current operator key set".to_string()
+        } else {
+            "Parent signature does not verify under any of the parent's current operator keys".to_string()
+        },
+    });
+
+    let capabilities_narrowed = identity.capabilities.iter().all(|capability| {
+        parent
+            .capabilities
+            .iter()
+            .any(|parent_capability| capability_is_subset(capability, parent_capability))
+    });
+    checks.push(DelegationCheck {
+        name: "capabilities_narrowed".to_string(),
+        passed: capabilities_narrowed,
+        message: if capabilities_narrowed {
+            "All capabilities narrow the parent's current capabilities".to_string()
+        } else {
+            "One or more capabilities broaden the parent's current capabilities".to_string()
+        },
+    });
+
+    let valid = checks.iter().all(|c| c.passed);
+    Ok(DelegationVerificationResult { valid, checks })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::{create_identity, verify_identity, CreateIdentityOptions};
+
+    fn make_parent(capabilities: Vec<String>) -> (AgentIdentity, ed25519_dalek::SigningKey) {
+        let kp = crypto::generate_key_pair().unwrap();
+        let identity = create_identity(CreateIdentityOptions {
+            signing_keys: vec![kp.signing_key.clone()],
+            threshold: 1,
+            model: ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude-3".to_string(),
+            },
+            capabilities,
+            deployment: DeploymentInfo {
+                runtime: "container".to_string(),
+            },
+        })
+        .unwrap();
+        (identity, kp.signing_key)
+    }
+
+    #[test]
+    fn test_delegate_identity_narrows_capabilities() {
+        let (parent, parent_key) = make_parent(vec!["read:/data/**".to_string()]);
+        let child_kp = crypto::generate_key_pair().unwrap();
+
+        let child = delegate_identity(
+            &parent,
+            DelegateIdentityOptions {
+                signing_keys: vec![child_kp.signing_key],
+                threshold: 1,
+                parent_signing_keys: vec![parent_key],
+                model: parent.model.clone(),
+                capabilities: vec!["read:/data/reports/*".to_string()],
+                deployment: parent.deployment.clone(),
+            },
+        )
+        .unwrap();
+
+        let identity_result = verify_identity(&child).unwrap();
+        assert!(identity_result.valid, "Verification failed: {:?}", identity_result.checks);
+
+        let resolver = |id: &str| -> Result<Option<AgentIdentity>, SteleError> {
+            Ok(if id == parent.id { Some(parent.clone()) } else { None })
+        };
+        let result = verify_delegation(&child, &resolver).unwrap();
+        assert!(result.valid, "Delegation verification failed: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_delegate_identity_rejects_broadened_capability() {
+        let (parent, parent_key) = make_parent(vec!["read:/data/reports/*".to_string()]);
+        let child_kp = crypto::generate_key_pair().unwrap();
+
+        let result = delegate_identity(
+            &parent,
+            DelegateIdentityOptions {
+                signing_keys: vec![child_kp.signing_key],
+                threshold: 1,
+                parent_signing_keys: vec![parent_key],
+                model: parent.model.clone(),
+                capabilities: vec!["read:/data/**".to_string()],
+                deployment: parent.deployment.clone(),
+            },
+        );
+        assert!(result.is_err(), "delegating a broader capability than the parent holds should fail");
+    }
+
+    #[test]
+    fn test_verify_delegation_rejects_unresolvable_parent() {
+        let (parent, parent_key) = make_parent(vec!["read:/data/**".to_string()]);
+        let child_kp = crypto::generate_key_pair().unwrap();
+
+        let child = delegate_identity(
+            &parent,
+            DelegateIdentityOptions {
+                signing_keys: vec![child_kp.signing_key],
+                threshold: 1,
+                parent_signing_keys: vec![parent_key],
+                model: parent.model.clone(),
+                capabilities: vec!["read:/data/reports/*".to_string()],
+                deployment: parent.deployment.clone(),
+            },
+        )
+        .unwrap();
+
+        let resolver = |_: &str| -> Result<Option<AgentIdentity>, SteleError> { Ok(None) };
+        let result = verify_delegation(&child, &resolver).unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_verify_delegation_rejects_capability_broadened_after_parent_evolves() {
+        use crate::identity::{evolve_identity, EvolveIdentityOptions};
+
+        let (parent, parent_key) = make_parent(vec!["read:/data/**".to_string()]);
+        let child_kp = crypto::generate_key_pair().unwrap();
+
+        let child = delegate_identity(
+            &parent,
+            DelegateIdentityOptions {
+                signing_keys: vec![child_kp.signing_key],
+                threshold: 1,
+                parent_signing_keys: vec![parent_key.clone()],
+                model: parent.model.clone(),
+                capabilities: vec!["read:/data/reports/*".to_string()],
+                deployment: parent.deployment.clone(),
+            },
+        )
+        .unwrap();
+
+        // The parent narrows its own capabilities down, no longer
+        // covering what it previously delegated to the child.
+        let narrowed_parent = evolve_identity(
+            &parent,
+            EvolveIdentityOptions {
+                signing_keys: vec![parent_key],
+                change_type: "capability_change".to_string(),
+                description: "Narrowed to metrics only".to_string(),
+                model: None,
+                capabilities: Some(vec!["read:/data/metrics/*".to_string()]),
+                deployment: None,
+                new_operator_keys: None,
+            },
+        )
+        .unwrap();
+
+        let resolver = |id: &str| -> Result<Option<AgentIdentity>, SteleError> {
+            Ok(if id == narrowed_parent.id { Some(narrowed_parent.clone()) } else { None })
+        };
+        let result = verify_delegation(&child, &resolver).unwrap();
+        assert!(!result.valid);
+        assert!(result.checks.iter().any(|c| c.name == "capabilities_narrowed" && !c.passed));
+    }
+
+    #[test]
+    fn test_verify_delegation_trivially_passes_for_non_delegated_identity() {
+        let (parent, _parent_key) = make_parent(vec!["read:/data/**".to_string()]);
+        let resolver = |_: &str| -> Result<Option<AgentIdentity>, SteleError> { Ok(None) };
+        let result = verify_delegation(&parent, &resolver).unwrap();
+        assert!(result.valid);
+    }
+}
@@ -0,0 +1,355 @@
+//! W3C-style DID documents for agent identities.
+//!
+//! An [`AgentIdentity`] is already conceptually a decentralized
+//! identifier: `operator_keys` holds its verification key(s), `lineage`
+//! is version history, and `capabilities` describe what the identity
+//! can do. [`to_did_document`] projects an identity into a `did:stele:<id>`
+//! DID document so it can be consumed by existing DID resolvers and
+//! verifiable-credential tooling; [`resolve_did`] looks an identity up
+//! by DID in a store and returns its current document alongside its
+//! version.
+//!
+//! Verification methods and services are held in [`OrderedSet`], an
+//! insertion-ordered collection keyed by `id` (named for the equivalent
+//! structure in IOTA's `identity.rs`) so that re-deriving a document
+//! from an evolved identity can never produce duplicate entries.
+
+use super::AgentIdentity;
+use crate::crypto::SignatureAlgorithm;
+use crate::SteleError;
+use serde::{Deserialize, Serialize};
+
+const DID_METHOD_PREFIX: &str = "did:stele:";
+
+/// An entry that can be deduplicated by a stable `id` key.
+pub trait KeyedEntry {
+    fn entry_id(&self) -> &str;
+}
+
+/// An insertion-ordered collection where each element's [`KeyedEntry::entry_id`]
+/// is unique: inserting an entry whose id already exists overwrites the
+/// existing one in place instead of appending, so duplicate ids are
+/// impossible by construction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OrderedSet<T> {
+    items: Vec<T>,
+}
+
+impl<T> Default for OrderedSet<T> {
+    fn default() -> Self {
+        OrderedSet { items: Vec::new() }
+    }
+}
+
+impl<T: KeyedEntry> OrderedSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert `item`, replacing any existing entry with the same
+    /// `entry_id` in place so insertion order of the *first* occurrence
+    /// of an id is preserved.
+    pub fn insert(&mut self, item: T) {
+        match self.items.iter().position(|existing| existing.entry_id() == item.entry_id()) {
+            Some(index) => self.items[index] = item,
+            None => self.items.push(item),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Whether every entry's `entry_id` is distinct. Always `true` for
+    /// an `OrderedSet` built solely through [`insert`](Self::insert);
+    /// exposed so tests can assert the invariant holds after arbitrary
+    /// sequences of inserts.
+    pub fn has_unique_keys(&self) -> bool {
+        let mut seen = std::collections::HashSet::new();
+        self.items.iter().all(|item| seen.insert(item.entry_id()))
+    }
+}
+
+/// A cryptographic key a DID controller can use to authenticate as the
+/// agent, analogous to a W3C DID document's `verificationMethod` entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationMethod {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub method_type: String,
+    pub controller: String,
+    #[serde(rename = "publicKeyHex")]
+    pub public_key_hex: String,
+}
+
+impl KeyedEntry for VerificationMethod {
+    fn entry_id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// A capability exposed by the agent, projected as a DID document
+/// `service` entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceEndpoint {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub service_type: String,
+    #[serde(rename = "serviceEndpoint")]
+    pub service_endpoint: String,
+}
+
+impl KeyedEntry for ServiceEndpoint {
+    fn entry_id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// A W3C-style DID document resolved from an [`AgentIdentity`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DidDocument {
+    pub id: String,
+    #[serde(rename = "verificationMethod")]
+    pub verification_method: OrderedSet<VerificationMethod>,
+    pub service: OrderedSet<ServiceEndpoint>,
+    /// The identity's `version` at the time this document was derived.
+    #[serde(rename = "versionId")]
+    pub version_id: u32,
+    /// The identity's full lineage chain, carried over as versioned
+    /// metadata rather than a DID-spec field proper.
+    pub lineage: Vec<super::LineageEntry>,
+}
+
+/// Map a signature algorithm to the verification method `type` string a
+/// DID resolver would expect for it.
+fn verification_method_type(alg: SignatureAlgorithm) -> &'static str {
+    match alg {
+        SignatureAlgorithm::Ed25519 => "Ed25519VerificationKey2020",
+        SignatureAlgorithm::EcdsaP256 => "EcdsaSecp256r1VerificationKey2019",
+        SignatureAlgorithm::Rsa2048 => "RsaVerificationKey2018",
+        SignatureAlgorithm::Secp256k1Schnorr => "Bip340VerificationKey2024",
+    }
+}
+
+/// Derive the `did:stele:<id>` DID for an agent identity.
+pub fn did_for_identity(identity: &AgentIdentity) -> String {
+    format!("{}{}", DID_METHOD_PREFIX, identity.id)
+}
+
+/// Project an agent identity into a W3C-style DID document: each key in
+/// the operator's [`KeySet`](super::KeySet) becomes a verification
+/// method, each capability becomes a service entry, and the lineage
+/// chain is carried as versioned metadata.
+pub fn to_did_document(identity: &AgentIdentity) -> DidDocument {
+    let did = did_for_identity(identity);
+
+    let mut verification_method = OrderedSet::new();
+    for (index, key) in identity.operator_keys.keys.iter().enumerate() {
+        verification_method.insert(VerificationMethod {
+            id: format!("{}#operator-key-{}", did, index),
+            method_type: verification_method_type(identity.alg).to_string(),
+            controller: did.clone(),
+            public_key_hex: key.clone(),
+        });
+    }
+
+    let mut service = OrderedSet::new();
+    for capability in &identity.capabilities {
+        service.insert(ServiceEndpoint {
+            id: format!("{}#{}", did, capability),
+            service_type: "AgentCapability".to_string(),
+            service_endpoint: capability.clone(),
+        });
+    }
+
+    DidDocument {
+        id: did,
+        verification_method,
+        service,
+        version_id: identity.version,
+        lineage: identity.lineage.clone(),
+    }
+}
+
+/// Minimal lookup interface [`resolve_did`] needs from an identity
+/// store. Kept separate from [`crate::store::Store`], which is specific
+/// to covenant documents rather than identities.
+pub trait IdentityStore {
+    /// Retrieve the current identity for `id` (the part of a DID after
+    /// `did:stele:`), if one is stored.
+    fn get_identity(&self, id: &str) -> Result<Option<&AgentIdentity>, SteleError>;
+}
+
+/// In-memory `IdentityStore`, keyed by identity `id`. Suitable for
+/// testing and single-process use, matching
+/// [`store::MemoryStore`](crate::store::MemoryStore)'s role for
+/// covenant documents.
+#[derive(Default)]
+pub struct MemoryIdentityStore {
+    identities: std::collections::HashMap<String, AgentIdentity>,
+}
+
+impl MemoryIdentityStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store `identity`, keyed by its `id`. Overwrites any identity
+    /// previously stored under the same id.
+    pub fn put_identity(&mut self, identity: AgentIdentity) {
+        self.identities.insert(identity.id.clone(), identity);
+    }
+}
+
+impl IdentityStore for MemoryIdentityStore {
+    fn get_identity(&self, id: &str) -> Result<Option<&AgentIdentity>, SteleError> {
+        Ok(self.identities.get(id))
+    }
+}
+
+/// Resolve `did` (a `did:stele:<id>` string) against `store`, returning
+/// its current DID document and version if the identity is stored.
+///
+/// # Errors
+/// Returns `SteleError::InvalidInput` if `did` isn't a `did:stele:`
+/// DID.
+pub fn resolve_did(
+    store: &impl IdentityStore,
+    did: &str,
+) -> Result<Option<(DidDocument, u32)>, SteleError> {
+    let id = did.strip_prefix(DID_METHOD_PREFIX).ok_or_else(|| {
+        SteleError::InvalidInput(format!("not a {} DID: {}", DID_METHOD_PREFIX, did))
+    })?;
+
+    Ok(store
+        .get_identity(id)?
+        .map(|identity| (to_did_document(identity), identity.version)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::{self, CreateIdentityOptions, DeploymentInfo, EvolveIdentityOptions, ModelInfo};
+    use rand::Rng;
+
+    fn make_identity(capabilities: Vec<String>) -> AgentIdentity {
+        make_identity_with_key(crate::crypto::generate_key_pair().unwrap().signing_key, capabilities)
+    }
+
+    fn make_identity_with_key(signing_key: ed25519_dalek::SigningKey, capabilities: Vec<String>) -> AgentIdentity {
+        identity::create_identity(CreateIdentityOptions {
+            signing_keys: vec![signing_key],
+            threshold: 1,
+            model: ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude".to_string(),
+            },
+            capabilities,
+            deployment: DeploymentInfo {
+                runtime: "cloud".to_string(),
+            },
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_to_did_document_shape() {
+        let identity = make_identity(vec!["read".to_string(), "write".to_string()]);
+        let doc = to_did_document(&identity);
+
+        assert_eq!(doc.id, format!("did:stele:{}", identity.id));
+        assert_eq!(doc.verification_method.len(), 1);
+        assert_eq!(doc.service.len(), 2);
+        assert_eq!(doc.version_id, 1);
+        assert!(doc.verification_method.has_unique_keys());
+        assert!(doc.service.has_unique_keys());
+    }
+
+    #[test]
+    fn test_duplicate_capability_does_not_duplicate_service_entry() {
+        let identity = make_identity(vec!["read".to_string(), "read".to_string()]);
+        let doc = to_did_document(&identity);
+
+        assert_eq!(doc.service.len(), 1);
+        assert!(doc.service.has_unique_keys());
+    }
+
+    #[test]
+    fn test_resolve_did_roundtrip() {
+        let identity = make_identity(vec!["read".to_string()]);
+        let mut store = MemoryIdentityStore::new();
+        store.put_identity(identity.clone());
+
+        let did = did_for_identity(&identity);
+        let (doc, version) = resolve_did(&store, &did).unwrap().unwrap();
+        assert_eq!(doc.id, did);
+        assert_eq!(version, identity.version);
+    }
+
+    #[test]
+    fn test_resolve_did_unknown_id_returns_none() {
+        let store = MemoryIdentityStore::new();
+        let result = resolve_did(&store, "did:stele:nonexistent").unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_resolve_did_rejects_wrong_method() {
+        let store = MemoryIdentityStore::new();
+        let result = resolve_did(&store, "did:key:nonexistent");
+        assert!(result.is_err());
+    }
+
+    /// Property test: for arbitrary sequences of `evolve_identity` calls
+    /// with randomly generated (and possibly duplicate) capability
+    /// lists, the derived DID document's verification methods and
+    /// service entries never contain a duplicate key.
+    #[test]
+    fn test_ordered_set_unique_keys_after_arbitrary_evolutions() {
+        let mut rng = rand::thread_rng();
+        let pool = ["read", "write", "delegate", "revoke", "admin"];
+
+        for _ in 0..50 {
+            let kp = crate::crypto::generate_key_pair().unwrap();
+            let mut identity = make_identity_with_key(
+                kp.signing_key.clone(),
+                vec![pool[rng.gen_range(0..pool.len())].to_string()],
+            );
+
+            let steps = rng.gen_range(0..8);
+            for _ in 0..steps {
+                let count = rng.gen_range(1..=pool.len());
+                let capabilities: Vec<String> = (0..count)
+                    .map(|_| pool[rng.gen_range(0..pool.len())].to_string())
+                    .collect();
+
+                identity = identity::evolve_identity(
+                    &identity,
+                    EvolveIdentityOptions {
+                        signing_keys: vec![kp.signing_key.clone()],
+                        change_type: "capabilities_updated".to_string(),
+                        description: "randomized property test step".to_string(),
+                        model: None,
+                        capabilities: Some(capabilities),
+                        deployment: None,
+                        new_operator_keys: None,
+                    },
+                )
+                .unwrap();
+            }
+
+            let doc = to_did_document(&identity);
+            assert!(doc.verification_method.has_unique_keys());
+            assert!(doc.service.has_unique_keys());
+        }
+    }
+}
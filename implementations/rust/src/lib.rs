@@ -7,32 +7,69 @@
 //! - **ccl**: Covenant Constraint Language parser and evaluator
 //! - **covenant**: Covenant document building, verification, and chaining
 //! - **identity**: Agent identity creation, evolution, and verification
-//! - **store**: In-memory covenant storage
+//! - **store**: Covenant storage (in-memory and disk-backed)
+//! - **telemetry**: Optional OpenTelemetry spans/metrics for the verification hot path
+//! - **transparency**: Append-only Merkle transparency log for covenants
+//! - **x509**: DER-encoded X.509 certificate export for agent identities
+//!
+//! # `no_std`
+//!
+//! With the (default-on) `std` feature disabled, this crate builds under
+//! `no_std` + `alloc`: the `crypto` and `ccl` primitives plus
+//! `covenant::wasm::verify_covenant_bytes` -- the deterministic,
+//! clock-free entrypoint a WASM host chain calls to settle whether an
+//! agent honored its covenant -- remain available. Anything that needs
+//! the OS clock or RNG (`crypto::timestamp`, `crypto::generate_key_pair`,
+//! `crypto::generate_nonce`, `crypto::mnemonic::generate_mnemonic`) or the
+//! filesystem (`store`) is gated behind
+//! `std` instead, since signing and persistence are not part of the
+//! on-chain verification path.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
 
 pub mod ccl;
 pub mod covenant;
 pub mod crypto;
 pub mod identity;
+#[cfg(feature = "std")]
 pub mod store;
+pub mod telemetry;
+pub mod transparency;
+// DER encoding/decoding of `Vec<u8>` and certificate names is a
+// PKI-bridging concern, not part of the on-chain verification path, so
+// (like `store`) it's only available with `std`.
+#[cfg(feature = "std")]
+pub mod x509;
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
 
 /// Errors that can occur throughout the Stele protocol.
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug)]
 pub enum SteleError {
-    #[error("Invalid input: {0}")]
     InvalidInput(String),
-
-    #[error("Crypto error: {0}")]
     CryptoError(String),
-
-    #[error("CCL parse error: {0}")]
     CCLParseError(String),
-
-    #[error("Verification failed: {0}")]
     VerificationFailed(String),
-
-    #[error("Serialization error: {0}")]
     SerializationError(String),
-
-    #[error("Storage error: {0}")]
     StorageError(String),
 }
+
+impl core::fmt::Display for SteleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SteleError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            SteleError::CryptoError(msg) => write!(f, "Crypto error: {}", msg),
+            SteleError::CCLParseError(msg) => write!(f, "CCL parse error: {}", msg),
+            SteleError::VerificationFailed(msg) => write!(f, "Verification failed: {}", msg),
+            SteleError::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
+            SteleError::StorageError(msg) => write!(f, "Storage error: {}", msg),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SteleError {}
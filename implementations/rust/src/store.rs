@@ -3,11 +3,20 @@
 //! Provides a trait-based storage abstraction and an in-memory implementation.
 //! The `Store` trait defines the minimal interface for storing and retrieving
 //! covenant documents; `MemoryStore` is a simple HashMap-backed implementation
-//! suitable for testing and lightweight use cases.
+//! suitable for testing and lightweight use cases. [`indexed::FileStore`]
+//! adds disk persistence and queryable secondary indexes.
+//! [`InstrumentedStore`] and [`VerifyingStore`] wrap any `Store` with
+//! telemetry and content-hash tamper detection respectively, regardless of
+//! which backing implementation is in use.
 
-use crate::covenant::CovenantDocument;
+use crate::covenant;
+use crate::covenant::{CovenantDocument, RevocationCertificate};
+use crate::telemetry;
 use crate::SteleError;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+
+pub mod indexed;
+pub mod log;
 
 /// Trait for covenant document storage.
 ///
@@ -38,6 +47,27 @@ pub trait Store {
 
     /// Return the number of stored documents.
     fn count(&self) -> usize;
+
+    /// Store a revocation certificate, keyed by the covenant ID it revokes.
+    ///
+    /// If a revocation already exists for that covenant ID, it is overwritten.
+    fn put_revocation(&mut self, revocation: RevocationCertificate) -> Result<(), SteleError>;
+
+    /// Retrieve the revocation certificate for a covenant ID, if any.
+    fn get_revocation(&self, covenant_id: &str) -> Result<Option<&RevocationCertificate>, SteleError>;
+
+    /// Record a single use of `action` by `covenant_id` at `timestamp_ms`
+    /// (Unix epoch milliseconds), for sliding-window rate-limit accounting.
+    fn record_usage(&mut self, covenant_id: &str, action: &str, timestamp_ms: i64) -> Result<(), SteleError>;
+
+    /// Count how many usages of `action` by `covenant_id` were recorded at
+    /// or after `since_ms`.
+    fn count_usage(&self, covenant_id: &str, action: &str, since_ms: i64) -> Result<i64, SteleError>;
+
+    /// Discard recorded usages of `action` by `covenant_id` older than
+    /// `before_ms`, so a (covenant, action) pair's history doesn't grow
+    /// without bound once its rate-limit window has rolled past them.
+    fn prune_usage(&mut self, covenant_id: &str, action: &str, before_ms: i64) -> Result<(), SteleError>;
 }
 
 /// In-memory covenant store backed by a `HashMap`.
@@ -46,6 +76,8 @@ pub trait Store {
 /// across restarts and not thread-safe (wrap in a `Mutex` if needed).
 pub struct MemoryStore {
     documents: HashMap<String, CovenantDocument>,
+    revocations: HashMap<String, RevocationCertificate>,
+    usage: HashMap<(String, String), VecDeque<i64>>,
 }
 
 impl MemoryStore {
@@ -53,6 +85,8 @@ impl MemoryStore {
     pub fn new() -> Self {
         MemoryStore {
             documents: HashMap::new(),
+            revocations: HashMap::new(),
+            usage: HashMap::new(),
         }
     }
 }
@@ -91,6 +125,209 @@ impl Store for MemoryStore {
     fn count(&self) -> usize {
         self.documents.len()
     }
+
+    fn put_revocation(&mut self, revocation: RevocationCertificate) -> Result<(), SteleError> {
+        self.revocations.insert(revocation.covenant_id.clone(), revocation);
+        Ok(())
+    }
+
+    fn get_revocation(&self, covenant_id: &str) -> Result<Option<&RevocationCertificate>, SteleError> {
+        Ok(self.revocations.get(covenant_id))
+    }
+
+    fn record_usage(&mut self, covenant_id: &str, action: &str, timestamp_ms: i64) -> Result<(), SteleError> {
+        self.usage
+            .entry((covenant_id.to_string(), action.to_string()))
+            .or_default()
+            .push_back(timestamp_ms);
+        Ok(())
+    }
+
+    fn count_usage(&self, covenant_id: &str, action: &str, since_ms: i64) -> Result<i64, SteleError> {
+        let count = self
+            .usage
+            .get(&(covenant_id.to_string(), action.to_string()))
+            .map(|timestamps| timestamps.iter().filter(|&&ts| ts >= since_ms).count())
+            .unwrap_or(0);
+        Ok(count as i64)
+    }
+
+    fn prune_usage(&mut self, covenant_id: &str, action: &str, before_ms: i64) -> Result<(), SteleError> {
+        if let Some(timestamps) = self.usage.get_mut(&(covenant_id.to_string(), action.to_string())) {
+            while let Some(&front) = timestamps.front() {
+                if front < before_ms {
+                    timestamps.pop_front();
+                } else {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Wraps any [`Store`] with OpenTelemetry instrumentation (behind the
+/// `telemetry` feature, a zero-cost pass-through otherwise): counts of
+/// `put`/`delete` calls, a `get` hit/miss breakdown, and the store's
+/// current [`Store::count`] recorded as a gauge after every mutation.
+///
+/// A single generic wrapper rather than instrumenting each `Store`
+/// implementation individually, so `MemoryStore`/`FileStore`/
+/// `MemoryLog`/`FileLog` stay free of observability concerns -- wrap
+/// whichever one an operator is already using.
+pub struct InstrumentedStore<S> {
+    inner: S,
+}
+
+impl<S: Store> InstrumentedStore<S> {
+    pub fn new(inner: S) -> Self {
+        InstrumentedStore { inner }
+    }
+
+    /// Unwrap back to the underlying, uninstrumented store.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Store> Store for InstrumentedStore<S> {
+    fn put(&mut self, id: &str, doc: CovenantDocument) -> Result<(), SteleError> {
+        let result = self.inner.put(id, doc);
+        telemetry::record_store_op("put");
+        telemetry::record_store_count(self.inner.count());
+        result
+    }
+
+    fn get(&self, id: &str) -> Result<Option<&CovenantDocument>, SteleError> {
+        let result = self.inner.get(id);
+        telemetry::record_store_get(matches!(result, Ok(Some(_))));
+        result
+    }
+
+    fn delete(&mut self, id: &str) -> Result<bool, SteleError> {
+        let result = self.inner.delete(id);
+        telemetry::record_store_op("delete");
+        telemetry::record_store_count(self.inner.count());
+        result
+    }
+
+    fn list(&self) -> Vec<&CovenantDocument> {
+        self.inner.list()
+    }
+
+    fn has(&self, id: &str) -> bool {
+        self.inner.has(id)
+    }
+
+    fn count(&self) -> usize {
+        self.inner.count()
+    }
+
+    fn put_revocation(&mut self, revocation: RevocationCertificate) -> Result<(), SteleError> {
+        self.inner.put_revocation(revocation)
+    }
+
+    fn get_revocation(&self, covenant_id: &str) -> Result<Option<&RevocationCertificate>, SteleError> {
+        self.inner.get_revocation(covenant_id)
+    }
+
+    fn record_usage(&mut self, covenant_id: &str, action: &str, timestamp_ms: i64) -> Result<(), SteleError> {
+        self.inner.record_usage(covenant_id, action, timestamp_ms)
+    }
+
+    fn count_usage(&self, covenant_id: &str, action: &str, since_ms: i64) -> Result<i64, SteleError> {
+        self.inner.count_usage(covenant_id, action, since_ms)
+    }
+
+    fn prune_usage(&mut self, covenant_id: &str, action: &str, before_ms: i64) -> Result<(), SteleError> {
+        self.inner.prune_usage(covenant_id, action, before_ms)
+    }
+}
+
+/// Wraps any [`Store`] with content-hash verification: on every
+/// `put`/`get`, recomputes the document's canonical-JSON hash and rejects
+/// it if that doesn't match the stored `id`, so a document tampered with
+/// on disk (bypassing `put`, e.g. hand-edited in a `FileStore` directory)
+/// is never returned or persisted silently. `list` returns the inner
+/// store's documents without this check.
+pub struct VerifyingStore<S> {
+    inner: S,
+}
+
+impl<S: Store> VerifyingStore<S> {
+    pub fn new(inner: S) -> Self {
+        VerifyingStore { inner }
+    }
+
+    /// Unwrap back to the underlying, unverified store.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn verify_content_hash(doc: &CovenantDocument, key: &str) -> Result<(), SteleError> {
+        let expected_id = covenant::compute_id(doc)
+            .map_err(|_| SteleError::SerializationError("failed to compute canonical document id".to_string()))?;
+        if doc.id != expected_id {
+            return Err(SteleError::VerificationFailed(format!(
+                "document {} failed content-hash verification: expected id {}, got {}",
+                key, expected_id, doc.id
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<S: Store> Store for VerifyingStore<S> {
+    fn put(&mut self, id: &str, doc: CovenantDocument) -> Result<(), SteleError> {
+        Self::verify_content_hash(&doc, id)?;
+        self.inner.put(id, doc)
+    }
+
+    fn get(&self, id: &str) -> Result<Option<&CovenantDocument>, SteleError> {
+        match self.inner.get(id)? {
+            Some(doc) => {
+                Self::verify_content_hash(doc, id)?;
+                Ok(Some(doc))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn delete(&mut self, id: &str) -> Result<bool, SteleError> {
+        self.inner.delete(id)
+    }
+
+    fn list(&self) -> Vec<&CovenantDocument> {
+        self.inner.list()
+    }
+
+    fn has(&self, id: &str) -> bool {
+        self.inner.has(id)
+    }
+
+    fn count(&self) -> usize {
+        self.inner.count()
+    }
+
+    fn put_revocation(&mut self, revocation: RevocationCertificate) -> Result<(), SteleError> {
+        self.inner.put_revocation(revocation)
+    }
+
+    fn get_revocation(&self, covenant_id: &str) -> Result<Option<&RevocationCertificate>, SteleError> {
+        self.inner.get_revocation(covenant_id)
+    }
+
+    fn record_usage(&mut self, covenant_id: &str, action: &str, timestamp_ms: i64) -> Result<(), SteleError> {
+        self.inner.record_usage(covenant_id, action, timestamp_ms)
+    }
+
+    fn count_usage(&self, covenant_id: &str, action: &str, since_ms: i64) -> Result<i64, SteleError> {
+        self.inner.count_usage(covenant_id, action, since_ms)
+    }
+
+    fn prune_usage(&mut self, covenant_id: &str, action: &str, before_ms: i64) -> Result<(), SteleError> {
+        self.inner.prune_usage(covenant_id, action, before_ms)
+    }
 }
 
 #[cfg(test)]
@@ -125,6 +362,33 @@ mod tests {
         .unwrap()
     }
 
+    fn make_test_covenant_with_issuer_key() -> (CovenantDocument, crypto::KeyPair) {
+        let kp = crypto::generate_key_pair().unwrap();
+        let issuer = Party {
+            id: "issuer-1".to_string(),
+            public_key: kp.public_key_hex.clone(),
+            role: "issuer".to_string(),
+        };
+        let bene_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary = Party {
+            id: "beneficiary-1".to_string(),
+            public_key: bene_kp.public_key_hex,
+            role: "beneficiary".to_string(),
+        };
+        let doc = covenant::build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: kp.signing_key,
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap();
+        (doc, kp)
+    }
+
     #[test]
     fn test_put_and_get() {
         let mut store = MemoryStore::new();
@@ -183,4 +447,95 @@ mod tests {
         let result = store.put("", doc);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_put_and_get_revocation() {
+        let mut store = MemoryStore::new();
+        let (doc, issuer_kp) = make_test_covenant_with_issuer_key();
+
+        assert!(store.get_revocation(&doc.id).unwrap().is_none());
+
+        let revocation = covenant::revoke(
+            &doc,
+            &issuer_kp,
+            covenant::RevocationReason::Superseded,
+            "replaced by a newer covenant",
+        )
+        .unwrap();
+        store.put_revocation(revocation).unwrap();
+
+        let retrieved = store.get_revocation(&doc.id).unwrap().unwrap();
+        assert_eq!(retrieved.covenant_id, doc.id);
+    }
+
+    #[test]
+    fn test_instrumented_store_delegates_to_inner() {
+        let mut store = InstrumentedStore::new(MemoryStore::new());
+        let doc = make_test_covenant();
+        let id = doc.id.clone();
+
+        store.put(&id, doc).unwrap();
+        assert!(store.has(&id));
+        assert_eq!(store.count(), 1);
+
+        let retrieved = store.get(&id).unwrap().unwrap();
+        assert_eq!(retrieved.id, id);
+
+        assert!(store.get("nonexistent").unwrap().is_none());
+
+        assert!(store.delete(&id).unwrap());
+        assert_eq!(store.count(), 0);
+    }
+
+    #[test]
+    fn test_instrumented_store_into_inner_preserves_state() {
+        let mut store = InstrumentedStore::new(MemoryStore::new());
+        let doc = make_test_covenant();
+        let id = doc.id.clone();
+        store.put(&id, doc).unwrap();
+
+        let inner = store.into_inner();
+        assert!(inner.has(&id));
+    }
+
+    #[test]
+    fn test_verifying_store_accepts_untampered_document() {
+        let mut store = VerifyingStore::new(MemoryStore::new());
+        let doc = make_test_covenant();
+        let id = doc.id.clone();
+
+        store.put(&id, doc).unwrap();
+        let retrieved = store.get(&id).unwrap().unwrap();
+        assert_eq!(retrieved.id, id);
+    }
+
+    #[test]
+    fn test_verifying_store_rejects_tampered_document_on_put() {
+        let mut store = VerifyingStore::new(MemoryStore::new());
+        let mut doc = make_test_covenant();
+        let id = doc.id.clone();
+        doc.constraints = "permit write on '/data/**'".to_string();
+
+        let result = store.put(&id, doc);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_verifying_store_rejects_document_tampered_on_disk() {
+        let mut store = VerifyingStore::new(MemoryStore::new());
+        let doc = make_test_covenant();
+        let id = doc.id.clone();
+        store.put(&id, doc).unwrap();
+
+        // Reach through to the inner store to simulate tampering that
+        // bypassed `put` entirely (e.g. a hand-edited file on disk).
+        let tampered = store.inner.get(&id).unwrap().unwrap().clone();
+        let mut tampered = tampered;
+        tampered.constraints = "permit write on '/data/**'".to_string();
+        store.inner.delete(&id).unwrap();
+        store.inner.put(&id, tampered).unwrap();
+
+        let result = store.get(&id);
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,683 @@
+//! Persistent, queryable covenant storage with secondary indexes.
+//!
+//! `MemoryStore` only supports lookup by ID. This module adds
+//! `CovenantStore`, a supertrait of [`Store`](super::Store) with filtered
+//! scans analogous to account scans in large ledger stores, plus
+//! `FileStore`, a disk-backed implementation that persists each document
+//! as a JSON file and rebuilds its secondary indexes on load. Indexes are
+//! maintained beyond the primary key for the fields audits query most:
+//! by signer (issuer public key) for bulk auditing, by constraint for
+//! exact-match lookup via [`CovenantStore::find_by`], and by chain head
+//! so that reconstructing a full covenant chain is O(chain length)
+//! rather than a full-table scan. Because documents are content-addressed,
+//! every index is a deterministic function of the stored set, so
+//! [`CovenantStore::rebuild_indexes`] can always recover from drift
+//! without needing a fresh [`FileStore::open`].
+
+use super::Store;
+use crate::covenant::{self, CovenantDocument, RevocationCertificate};
+use crate::crypto;
+use crate::SteleError;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Filters for [`CovenantStore::scan`]. All set fields are ANDed together;
+/// `None` fields are not filtered on.
+#[derive(Debug, Clone, Default)]
+pub struct ScanFilters {
+    /// Match covenants issued by this signer (issuer public key, hex).
+    pub signer: Option<String>,
+    /// Match covenants with `createdAt >= issued_after` (RFC 3339 / ISO 8601).
+    pub issued_after: Option<String>,
+    /// Match covenants with `createdAt <= issued_before` (RFC 3339 / ISO 8601).
+    pub issued_before: Option<String>,
+    /// Match covenants whose `activatesAt >= activates_after` (RFC 3339 / ISO 8601).
+    pub activates_after: Option<String>,
+    /// Match covenants whose `activatesAt <= activates_before` (RFC 3339 / ISO 8601).
+    pub activates_before: Option<String>,
+    /// Match covenants whose `expiresAt >= expires_after` (RFC 3339 / ISO 8601).
+    pub expires_after: Option<String>,
+    /// Match covenants whose `expiresAt <= expires_before` (RFC 3339 / ISO 8601).
+    pub expires_before: Option<String>,
+    /// Match covenants whose `chain.parentId` equals this value.
+    pub parent_id: Option<String>,
+    /// Byte-range/prefix match over a named top-level field of the
+    /// canonicalized document: `(field, prefix)`.
+    pub field_prefix: Option<(String, String)>,
+}
+
+/// Trait for covenant storage that supports filtered scans and bulk
+/// auditing beyond the basic [`Store`] CRUD interface.
+pub trait CovenantStore: Store {
+    /// Scan all stored documents matching every set field of `filters`,
+    /// in a deterministic order (ascending by document ID).
+    fn scan(&self, filters: &ScanFilters) -> Vec<&CovenantDocument>;
+
+    /// Reconstruct the full chain (in ascending depth order) that `id`
+    /// belongs to, in O(chain length) rather than a full-table scan.
+    fn chain(&self, id: &str) -> Vec<&CovenantDocument>;
+
+    /// Group all stored documents by issuer signer (public key, hex).
+    fn group_by_signer(&self) -> HashMap<String, Vec<&CovenantDocument>>;
+
+    /// Exact-match lookup of every document whose `field` equals `value`,
+    /// in ascending document-ID order. Backed by a secondary index for
+    /// `"issuer.public_key"` and `"constraints"`; any other field falls
+    /// back to a full-table scan, same as `scan`'s `field_prefix`.
+    fn find_by(&self, field: &str, value: &str) -> Vec<&CovenantDocument>;
+
+    /// Rebuild every secondary index from the current document set,
+    /// discarding whatever indexing state exists beforehand. Since
+    /// documents are content-addressed, indexes are always a
+    /// deterministic function of the stored documents, so this recovers
+    /// from any drift (a crash mid-write, manual edits under the store
+    /// directory) without needing to reopen the store.
+    fn rebuild_indexes(&mut self);
+}
+
+/// Disk-backed covenant store: each document is persisted as
+/// `<dir>/<id>.json`. Secondary indexes (by signer, by chain head) are
+/// kept in memory and rebuilt from disk on [`FileStore::open`].
+///
+/// Not thread-safe (wrap in a `Mutex` if needed), matching the
+/// single-process assumption of [`MemoryStore`](super::MemoryStore).
+pub struct FileStore {
+    dir: PathBuf,
+    documents: HashMap<String, CovenantDocument>,
+    revocations: HashMap<String, RevocationCertificate>,
+    usage: HashMap<(String, String), VecDeque<i64>>,
+    by_signer: HashMap<String, Vec<String>>,
+    by_constraint: HashMap<String, Vec<String>>,
+    chain_heads: HashMap<String, String>,
+    chain_members: HashMap<String, Vec<String>>,
+}
+
+impl FileStore {
+    /// Open (creating if necessary) a disk-backed store rooted at `dir`,
+    /// loading any existing `*.json` documents and rebuilding indexes.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, SteleError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)
+            .map_err(|e| SteleError::StorageError(format!("Failed to create store dir: {}", e)))?;
+
+        let mut store = FileStore {
+            dir,
+            documents: HashMap::new(),
+            revocations: HashMap::new(),
+            usage: HashMap::new(),
+            by_signer: HashMap::new(),
+            by_constraint: HashMap::new(),
+            chain_heads: HashMap::new(),
+            chain_members: HashMap::new(),
+        };
+        store.load_existing()?;
+        Ok(store)
+    }
+
+    fn document_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    fn revocation_path(&self, covenant_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.rev.json", covenant_id))
+    }
+
+    /// Load every `*.json` file in `dir` -- `*.rev.json` files as
+    /// revocation certificates, everything else as covenant documents --
+    /// then link chains in ascending depth order so parents are always
+    /// indexed before their children regardless of on-disk file ordering.
+    fn load_existing(&mut self) -> Result<(), SteleError> {
+        let mut loaded: Vec<CovenantDocument> = Vec::new();
+        let mut loaded_revocations: Vec<RevocationCertificate> = Vec::new();
+        let entries = fs::read_dir(&self.dir)
+            .map_err(|e| SteleError::StorageError(format!("Failed to read store dir: {}", e)))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| SteleError::StorageError(format!("Failed to read dir entry: {}", e)))?;
+            let path = entry.path();
+            let file_name = match path.file_name().and_then(|n| n.to_str()) {
+                Some(name) => name.to_string(),
+                None => continue,
+            };
+
+            if file_name.ends_with(".rev.json") {
+                let raw = fs::read_to_string(&path)
+                    .map_err(|e| SteleError::StorageError(format!("Failed to read {}: {}", path.display(), e)))?;
+                let revocation: RevocationCertificate = serde_json::from_str(&raw)
+                    .map_err(|e| SteleError::SerializationError(format!("Failed to parse revocation {}: {}", path.display(), e)))?;
+                loaded_revocations.push(revocation);
+                continue;
+            }
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let raw = fs::read_to_string(&path)
+                .map_err(|e| SteleError::StorageError(format!("Failed to read {}: {}", path.display(), e)))?;
+            loaded.push(covenant::deserialize_covenant(&raw)?);
+        }
+
+        loaded.sort_by_key(|doc| doc.chain.as_ref().map(|c| c.depth).unwrap_or(0));
+        for doc in loaded {
+            self.index(&doc);
+            self.documents.insert(doc.id.clone(), doc);
+        }
+        for revocation in loaded_revocations {
+            self.revocations.insert(revocation.covenant_id.clone(), revocation);
+        }
+        Ok(())
+    }
+
+    /// Update secondary indexes for a document being inserted. Runs
+    /// before the document is written into `self.documents`: the chain
+    /// head comes from `chain_heads` (parents precede children in
+    /// insertion order), and when the chain's members are re-sorted by
+    /// depth, the new document's own depth is read from `doc`.
+    fn index(&mut self, doc: &CovenantDocument) {
+        self.by_signer
+            .entry(doc.issuer.public_key.clone())
+            .or_default()
+            .push(doc.id.clone());
+        self.by_constraint
+            .entry(doc.constraints.clone())
+            .or_default()
+            .push(doc.id.clone());
+
+        let head = match &doc.chain {
+            Some(chain) => self
+                .chain_heads
+                .get(&chain.parent_id)
+                .cloned()
+                .unwrap_or_else(|| doc.id.clone()),
+            None => doc.id.clone(),
+        };
+        self.chain_heads.insert(doc.id.clone(), head.clone());
+        let members = self.chain_members.entry(head).or_default();
+        members.push(doc.id.clone());
+        members.sort_by_key(|member_id| {
+            if *member_id == doc.id {
+                return doc.chain.as_ref().map(|c| c.depth).unwrap_or(0);
+            }
+            self.documents
+                .get(member_id)
+                .and_then(|d| d.chain.as_ref())
+                .map(|c| c.depth)
+                .unwrap_or(0)
+        });
+    }
+
+    fn field_matches(doc: &CovenantDocument, field: &str, prefix: &str) -> bool {
+        let canonical = match covenant::canonical_form(doc) {
+            Ok(c) => c,
+            Err(_) => return false,
+        };
+        let value: serde_json::Value = match serde_json::from_str(&canonical) {
+            Ok(v) => v,
+            Err(_) => return false,
+        };
+        let field_str = match value.get(field) {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+            None => return false,
+        };
+        field_str.starts_with(prefix)
+    }
+
+    fn field_matches_exact(doc: &CovenantDocument, field: &str, value: &str) -> bool {
+        let canonical = match covenant::canonical_form(doc) {
+            Ok(c) => c,
+            Err(_) => return false,
+        };
+        let parsed: serde_json::Value = match serde_json::from_str(&canonical) {
+            Ok(v) => v,
+            Err(_) => return false,
+        };
+        match parsed.get(field) {
+            Some(serde_json::Value::String(s)) => s == value,
+            Some(other) => other.to_string() == value,
+            None => false,
+        }
+    }
+}
+
+impl Store for FileStore {
+    fn put(&mut self, id: &str, doc: CovenantDocument) -> Result<(), SteleError> {
+        if id.is_empty() {
+            return Err(SteleError::StorageError("Document ID cannot be empty".to_string()));
+        }
+        let serialized = covenant::serialize_covenant(&doc)?;
+        fs::write(self.document_path(id), serialized)
+            .map_err(|e| SteleError::StorageError(format!("Failed to write {}: {}", id, e)))?;
+
+        self.index(&doc);
+        self.documents.insert(id.to_string(), doc);
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<&CovenantDocument>, SteleError> {
+        Ok(self.documents.get(id))
+    }
+
+    fn delete(&mut self, id: &str) -> Result<bool, SteleError> {
+        if !self.documents.contains_key(id) {
+            return Ok(false);
+        }
+        let path = self.document_path(id);
+        if path.exists() {
+            fs::remove_file(&path)
+                .map_err(|e| SteleError::StorageError(format!("Failed to delete {}: {}", id, e)))?;
+        }
+        self.documents.remove(id);
+        // Secondary indexes are left untrimmed on delete (scan/chain/group_by_signer
+        // filter against `self.documents`, so stale index entries are harmless).
+        Ok(true)
+    }
+
+    fn list(&self) -> Vec<&CovenantDocument> {
+        self.documents.values().collect()
+    }
+
+    fn has(&self, id: &str) -> bool {
+        self.documents.contains_key(id)
+    }
+
+    fn count(&self) -> usize {
+        self.documents.len()
+    }
+
+    fn put_revocation(&mut self, revocation: RevocationCertificate) -> Result<(), SteleError> {
+        let serialized = serde_json::to_string_pretty(&revocation)
+            .map_err(|e| SteleError::SerializationError(format!("Failed to serialize revocation: {}", e)))?;
+        fs::write(self.revocation_path(&revocation.covenant_id), serialized)
+            .map_err(|e| SteleError::StorageError(format!("Failed to write revocation for {}: {}", revocation.covenant_id, e)))?;
+        self.revocations.insert(revocation.covenant_id.clone(), revocation);
+        Ok(())
+    }
+
+    fn get_revocation(&self, covenant_id: &str) -> Result<Option<&RevocationCertificate>, SteleError> {
+        Ok(self.revocations.get(covenant_id))
+    }
+
+    // Rate-limit usage is accounting, not an authorization artifact like a
+    // document or revocation, so it's kept in memory only and does not
+    // survive a reopen.
+    fn record_usage(&mut self, covenant_id: &str, action: &str, timestamp_ms: i64) -> Result<(), SteleError> {
+        self.usage
+            .entry((covenant_id.to_string(), action.to_string()))
+            .or_default()
+            .push_back(timestamp_ms);
+        Ok(())
+    }
+
+    fn count_usage(&self, covenant_id: &str, action: &str, since_ms: i64) -> Result<i64, SteleError> {
+        let count = self
+            .usage
+            .get(&(covenant_id.to_string(), action.to_string()))
+            .map(|timestamps| timestamps.iter().filter(|&&ts| ts >= since_ms).count())
+            .unwrap_or(0);
+        Ok(count as i64)
+    }
+
+    fn prune_usage(&mut self, covenant_id: &str, action: &str, before_ms: i64) -> Result<(), SteleError> {
+        if let Some(timestamps) = self.usage.get_mut(&(covenant_id.to_string(), action.to_string())) {
+            while let Some(&front) = timestamps.front() {
+                if front < before_ms {
+                    timestamps.pop_front();
+                } else {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CovenantStore for FileStore {
+    fn scan(&self, filters: &ScanFilters) -> Vec<&CovenantDocument> {
+        let mut ids: Vec<&String> = self.documents.keys().collect();
+        ids.sort();
+
+        ids.into_iter()
+            .filter_map(|id| self.documents.get(id))
+            .filter(|doc| {
+                filters
+                    .signer
+                    .as_ref()
+                    .map(|signer| &doc.issuer.public_key == signer)
+                    .unwrap_or(true)
+            })
+            .filter(|doc| {
+                filters
+                    .issued_after
+                    .as_ref()
+                    .map(|after| doc.created_at.as_str() >= after.as_str())
+                    .unwrap_or(true)
+            })
+            .filter(|doc| {
+                filters
+                    .issued_before
+                    .as_ref()
+                    .map(|before| doc.created_at.as_str() <= before.as_str())
+                    .unwrap_or(true)
+            })
+            .filter(|doc| {
+                filters
+                    .activates_after
+                    .as_ref()
+                    .map(|after| {
+                        doc.activates_at
+                            .as_deref()
+                            .map(|activates_at| activates_at >= after.as_str())
+                            .unwrap_or(false)
+                    })
+                    .unwrap_or(true)
+            })
+            .filter(|doc| {
+                filters
+                    .activates_before
+                    .as_ref()
+                    .map(|before| {
+                        doc.activates_at
+                            .as_deref()
+                            .map(|activates_at| activates_at <= before.as_str())
+                            .unwrap_or(false)
+                    })
+                    .unwrap_or(true)
+            })
+            .filter(|doc| {
+                filters
+                    .expires_after
+                    .as_ref()
+                    .map(|after| {
+                        doc.expires_at
+                            .as_deref()
+                            .map(|expires_at| expires_at >= after.as_str())
+                            .unwrap_or(false)
+                    })
+                    .unwrap_or(true)
+            })
+            .filter(|doc| {
+                filters
+                    .expires_before
+                    .as_ref()
+                    .map(|before| {
+                        doc.expires_at
+                            .as_deref()
+                            .map(|expires_at| expires_at <= before.as_str())
+                            .unwrap_or(false)
+                    })
+                    .unwrap_or(true)
+            })
+            .filter(|doc| {
+                filters
+                    .parent_id
+                    .as_ref()
+                    .map(|parent_id| {
+                        doc.chain
+                            .as_ref()
+                            .map(|c| &c.parent_id == parent_id)
+                            .unwrap_or(false)
+                    })
+                    .unwrap_or(true)
+            })
+            .filter(|doc| {
+                filters
+                    .field_prefix
+                    .as_ref()
+                    .map(|(field, prefix)| Self::field_matches(doc, field, prefix))
+                    .unwrap_or(true)
+            })
+            .collect()
+    }
+
+    fn chain(&self, id: &str) -> Vec<&CovenantDocument> {
+        let head = match self.chain_heads.get(id) {
+            Some(head) => head,
+            None => return Vec::new(),
+        };
+        self.chain_members
+            .get(head)
+            .map(|members| members.iter().filter_map(|member_id| self.documents.get(member_id)).collect())
+            .unwrap_or_default()
+    }
+
+    fn group_by_signer(&self) -> HashMap<String, Vec<&CovenantDocument>> {
+        let mut groups: HashMap<String, Vec<&CovenantDocument>> = HashMap::new();
+        for (signer, ids) in &self.by_signer {
+            let docs: Vec<&CovenantDocument> = ids
+                .iter()
+                .filter_map(|id| self.documents.get(id))
+                .collect();
+            if !docs.is_empty() {
+                groups.insert(signer.clone(), docs);
+            }
+        }
+        groups
+    }
+
+    fn find_by(&self, field: &str, value: &str) -> Vec<&CovenantDocument> {
+        let mut ids: Vec<String> = match field {
+            "issuer.public_key" => self.by_signer.get(value).cloned().unwrap_or_default(),
+            "constraints" => self.by_constraint.get(value).cloned().unwrap_or_default(),
+            _ => self
+                .documents
+                .values()
+                .filter(|doc| Self::field_matches_exact(doc, field, value))
+                .map(|doc| doc.id.clone())
+                .collect(),
+        };
+        ids.sort();
+        ids.iter().filter_map(|id| self.documents.get(id)).collect()
+    }
+
+    fn rebuild_indexes(&mut self) {
+        self.by_signer.clear();
+        self.by_constraint.clear();
+        self.chain_heads.clear();
+        self.chain_members.clear();
+
+        let mut docs: Vec<CovenantDocument> = self.documents.values().cloned().collect();
+        docs.sort_by_key(|doc| doc.chain.as_ref().map(|c| c.depth).unwrap_or(0));
+        for doc in &docs {
+            self.index(doc);
+        }
+    }
+}
+
+/// Derive a temp-directory path for a `FileStore`, namespaced by a
+/// random nonce so concurrent test runs never collide.
+#[cfg(test)]
+fn temp_store_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("stele-store-{}", hex::encode(crypto::generate_nonce())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covenant::{CovenantBuilderOptions, Party};
+
+    fn make_covenant(parent: Option<crate::covenant::ChainReference>) -> CovenantDocument {
+        let kp = crypto::generate_key_pair().unwrap();
+        let issuer = Party {
+            id: "issuer-1".to_string(),
+            public_key: kp.public_key_hex.clone(),
+            role: "issuer".to_string(),
+        };
+        let bene_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary = Party {
+            id: "beneficiary-1".to_string(),
+            public_key: bene_kp.public_key_hex,
+            role: "beneficiary".to_string(),
+        };
+        covenant::build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: kp.signing_key,
+            chain: parent,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_put_get_persists_to_disk() {
+        let dir = temp_store_dir();
+        let mut store = FileStore::open(&dir).unwrap();
+        let doc = make_covenant(None);
+        let id = doc.id.clone();
+        store.put(&id, doc).unwrap();
+
+        let reopened = FileStore::open(&dir).unwrap();
+        assert!(reopened.has(&id));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_scan_by_signer() {
+        let dir = temp_store_dir();
+        let mut store = FileStore::open(&dir).unwrap();
+        let doc = make_covenant(None);
+        let signer = doc.issuer.public_key.clone();
+        store.put(&doc.id.clone(), doc).unwrap();
+
+        let results = store.scan(&ScanFilters {
+            signer: Some(signer),
+            ..Default::default()
+        });
+        assert_eq!(results.len(), 1);
+
+        let no_match = store.scan(&ScanFilters {
+            signer: Some("nonexistent".to_string()),
+            ..Default::default()
+        });
+        assert!(no_match.is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_chain_reconstruction_is_indexed() {
+        let dir = temp_store_dir();
+        let mut store = FileStore::open(&dir).unwrap();
+        let root = make_covenant(None);
+        let root_id = root.id.clone();
+        store.put(&root_id, root).unwrap();
+
+        let child = make_covenant(Some(crate::covenant::ChainReference {
+            parent_id: root_id.clone(),
+            relation: "delegation".to_string(),
+            depth: 1,
+        }));
+        let child_id = child.id.clone();
+        store.put(&child_id, child).unwrap();
+
+        let chain = store.chain(&child_id);
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0].id, root_id);
+        assert_eq!(chain[1].id, child_id);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_group_by_signer() {
+        let dir = temp_store_dir();
+        let mut store = FileStore::open(&dir).unwrap();
+        let doc1 = make_covenant(None);
+        let doc2 = make_covenant(None);
+        store.put(&doc1.id.clone(), doc1).unwrap();
+        store.put(&doc2.id.clone(), doc2).unwrap();
+
+        let groups = store.group_by_signer();
+        assert_eq!(groups.len(), 2);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_find_by_constraint_and_signer() {
+        let dir = temp_store_dir();
+        let mut store = FileStore::open(&dir).unwrap();
+        let doc = make_covenant(None);
+        let signer = doc.issuer.public_key.clone();
+        let constraints = doc.constraints.clone();
+        store.put(&doc.id.clone(), doc).unwrap();
+
+        assert_eq!(store.find_by("constraints", &constraints).len(), 1);
+        assert_eq!(store.find_by("issuer.public_key", &signer).len(), 1);
+        assert!(store.find_by("constraints", "nonexistent").is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_find_by_falls_back_to_scan_for_unindexed_field() {
+        let dir = temp_store_dir();
+        let mut store = FileStore::open(&dir).unwrap();
+        let doc = make_covenant(None);
+        let version = doc.version.clone();
+        store.put(&doc.id.clone(), doc).unwrap();
+
+        assert_eq!(store.find_by("version", &version).len(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_scan_by_activates_and_expires_window() {
+        let dir = temp_store_dir();
+        let mut store = FileStore::open(&dir).unwrap();
+        let kp = crypto::generate_key_pair().unwrap();
+        let issuer = Party {
+            id: "issuer-1".to_string(),
+            public_key: kp.public_key_hex.clone(),
+            role: "issuer".to_string(),
+        };
+        let bene_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary = Party {
+            id: "beneficiary-1".to_string(),
+            public_key: bene_kp.public_key_hex,
+            role: "beneficiary".to_string(),
+        };
+        let doc = covenant::build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: kp.signing_key,
+            chain: None,
+            expires_at: Some("2026-12-31T00:00:00Z".to_string()),
+            activates_at: Some("2026-01-01T00:00:00Z".to_string()),
+            metadata: None,
+        })
+        .unwrap();
+        store.put(&doc.id.clone(), doc).unwrap();
+
+        let in_window = store.scan(&ScanFilters {
+            activates_after: Some("2025-01-01T00:00:00Z".to_string()),
+            expires_before: Some("2027-01-01T00:00:00Z".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(in_window.len(), 1);
+
+        let out_of_window = store.scan(&ScanFilters {
+            expires_before: Some("2026-01-01T00:00:00Z".to_string()),
+            ..Default::default()
+        });
+        assert!(out_of_window.is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_rebuild_indexes_recovers_from_drift() {
+        let dir = temp_store_dir();
+        let mut store = FileStore::open(&dir).unwrap();
+        let doc = make_covenant(None);
+        let signer = doc.issuer.public_key.clone();
+        store.put(&doc.id.clone(), doc).unwrap();
+
+        // Simulate index drift by clearing it directly, then recovering.
+        store.by_signer.clear();
+        assert!(store.find_by("issuer.public_key", &signer).is_empty());
+
+        store.rebuild_indexes();
+        assert_eq!(store.find_by("issuer.public_key", &signer).len(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,621 @@
+//! Log-structured covenant storage with checkpoints and replay.
+//!
+//! [`MemoryStore`](super::MemoryStore) and
+//! [`indexed::FileStore`](super::indexed::FileStore) hold only the
+//! current materialized state, with no history of how it was reached.
+//! This module adds [`LogStore`], a supertrait of [`Store`] built
+//! Bayou-style on an append-only operation log: every [`put`](Store::put)
+//! and [`delete`](Store::delete) appends a timestamped [`Operation`]
+//! keyed by a monotonic sequence number, and the materialized state is
+//! periodically folded into a [`Checkpoint`] so [`LogStore::load`] only
+//! has to replay the operation suffix after the latest one. Two
+//! replicas converge deterministically by [`LogStore::sync`]-ing their
+//! logs and replaying the merged, timestamp-ordered result.
+//!
+//! [`MemoryLog`] keeps the log and checkpoints in memory; [`FileLog`]
+//! appends operations to a log file and periodically writes checkpoint
+//! files, so both survive process restarts.
+
+use super::Store;
+use crate::covenant::{CovenantDocument, RevocationCertificate};
+use crate::SteleError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of operations between automatic checkpoints.
+const CHECKPOINT_INTERVAL: u64 = 100;
+
+/// Current wall-clock time in Unix epoch milliseconds, for stamping
+/// operations appended by `put`/`delete`/`put_revocation` (which, unlike
+/// `record_usage`, don't take a timestamp from the caller).
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// A single mutation appended to the operation log, keyed by a
+/// monotonic `seq` and stamped with the wall-clock time it was applied.
+/// `seq` orders operations from the same replica; `timestamp_ms` is what
+/// [`LogStore::sync`] uses to order operations across replicas.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Operation {
+    Put {
+        seq: u64,
+        timestamp_ms: i64,
+        id: String,
+        doc: CovenantDocument,
+    },
+    Delete {
+        seq: u64,
+        timestamp_ms: i64,
+        id: String,
+    },
+    PutRevocation {
+        seq: u64,
+        timestamp_ms: i64,
+        revocation: RevocationCertificate,
+    },
+}
+
+impl Operation {
+    fn seq(&self) -> u64 {
+        match self {
+            Operation::Put { seq, .. } => *seq,
+            Operation::Delete { seq, .. } => *seq,
+            Operation::PutRevocation { seq, .. } => *seq,
+        }
+    }
+
+    fn timestamp_ms(&self) -> i64 {
+        match self {
+            Operation::Put { timestamp_ms, .. } => *timestamp_ms,
+            Operation::Delete { timestamp_ms, .. } => *timestamp_ms,
+            Operation::PutRevocation { timestamp_ms, .. } => *timestamp_ms,
+        }
+    }
+}
+
+/// A point-in-time fold of the operation log, so [`LogStore::load`] only
+/// needs to replay operations with `seq > through_seq` rather than the
+/// whole history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Checkpoint {
+    through_seq: u64,
+    documents: HashMap<String, CovenantDocument>,
+    revocations: HashMap<String, RevocationCertificate>,
+}
+
+/// Replay `ops` (assumed already in a deterministic order) onto a fresh
+/// copy of `checkpoint`, producing the materialized state after them.
+fn replay(checkpoint: &Checkpoint, ops: &[Operation]) -> Checkpoint {
+    let mut state = checkpoint.clone();
+    for op in ops {
+        match op {
+            Operation::Put { seq, id, doc, .. } => {
+                state.documents.insert(id.clone(), doc.clone());
+                state.through_seq = state.through_seq.max(*seq);
+            }
+            Operation::Delete { seq, id, .. } => {
+                state.documents.remove(id);
+                state.through_seq = state.through_seq.max(*seq);
+            }
+            Operation::PutRevocation { seq, revocation, .. } => {
+                state.revocations.insert(revocation.covenant_id.clone(), revocation.clone());
+                state.through_seq = state.through_seq.max(*seq);
+            }
+        }
+    }
+    state
+}
+
+/// Sort operations into the deterministic order two replicas must agree
+/// on when merging: by `timestamp_ms`, then `seq` to break ties so that
+/// concurrent operations with an identical timestamp still resolve the
+/// same way on every replica (last write in this order wins).
+fn ordered(mut ops: Vec<Operation>) -> Vec<Operation> {
+    ops.sort_by_key(|op| (op.timestamp_ms(), op.seq()));
+    ops
+}
+
+/// Trait for covenant storage backed by a replayable, mergeable
+/// operation log, extending [`Store`] with history-aware lifecycle
+/// operations. `get`/`has`/`list`/`count` (and the rest of [`Store`])
+/// keep operating on the materialized state, not the log.
+pub trait LogStore: Store {
+    /// Reconstruct materialized state by loading the latest checkpoint
+    /// and replaying the operation suffix recorded after it.
+    fn load(&mut self) -> Result<(), SteleError>;
+
+    /// Merge `other`'s operation log into this store's, deterministically
+    /// ordering the combined log by timestamp (ties broken by sequence
+    /// number) and replaying it from scratch, so two replicas that sync
+    /// with each other converge on identical state.
+    fn sync(&mut self, other: &Self) -> Result<(), SteleError>;
+}
+
+/// In-memory log-structured store: the operation log and its
+/// checkpoints live only in process memory. Useful for testing the
+/// log/checkpoint/sync machinery without touching disk.
+#[derive(Default)]
+pub struct MemoryLog {
+    next_seq: u64,
+    ops: Vec<Operation>,
+    checkpoint: Checkpoint,
+    state: Checkpoint,
+}
+
+impl MemoryLog {
+    /// Create a new, empty `MemoryLog`.
+    pub fn new() -> Self {
+        MemoryLog::default()
+    }
+
+    fn append(&mut self, op: Operation) {
+        self.ops.push(op);
+        self.state = replay(&self.checkpoint, &self.ops);
+        if self.ops.len() as u64 >= CHECKPOINT_INTERVAL {
+            self.checkpoint = self.state.clone();
+            self.ops.clear();
+        }
+    }
+
+    fn next_seq(&mut self) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        seq
+    }
+
+    /// The operations appended since the last checkpoint, for use by
+    /// [`LogStore::sync`]. The checkpoint carries no operation
+    /// history of its own, so a replica that has already folded earlier
+    /// operations into a checkpoint can only contribute its uncheckpointed
+    /// suffix; this is sufficient for `sync` to converge because a
+    /// checkpointed operation's effect is still present in `self.state`.
+    fn uncheckpointed_ops(&self) -> &[Operation] {
+        &self.ops
+    }
+}
+
+impl Store for MemoryLog {
+    fn put(&mut self, id: &str, doc: CovenantDocument) -> Result<(), SteleError> {
+        if id.is_empty() {
+            return Err(SteleError::StorageError("Document ID cannot be empty".to_string()));
+        }
+        let seq = self.next_seq();
+        self.append(Operation::Put {
+            seq,
+            timestamp_ms: now_ms(),
+            id: id.to_string(),
+            doc,
+        });
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<&CovenantDocument>, SteleError> {
+        Ok(self.state.documents.get(id))
+    }
+
+    fn delete(&mut self, id: &str) -> Result<bool, SteleError> {
+        let existed = self.state.documents.contains_key(id);
+        if existed {
+            let seq = self.next_seq();
+            self.append(Operation::Delete {
+                seq,
+                timestamp_ms: now_ms(),
+                id: id.to_string(),
+            });
+        }
+        Ok(existed)
+    }
+
+    fn list(&self) -> Vec<&CovenantDocument> {
+        self.state.documents.values().collect()
+    }
+
+    fn has(&self, id: &str) -> bool {
+        self.state.documents.contains_key(id)
+    }
+
+    fn count(&self) -> usize {
+        self.state.documents.len()
+    }
+
+    fn put_revocation(&mut self, revocation: RevocationCertificate) -> Result<(), SteleError> {
+        let seq = self.next_seq();
+        self.append(Operation::PutRevocation {
+            seq,
+            timestamp_ms: now_ms(),
+            revocation,
+        });
+        Ok(())
+    }
+
+    fn get_revocation(&self, covenant_id: &str) -> Result<Option<&RevocationCertificate>, SteleError> {
+        Ok(self.state.revocations.get(covenant_id))
+    }
+
+    // Rate-limit usage is accounting, not a covenant lifecycle event, so
+    // it is not logged or checkpointed (matching `indexed::FileStore`'s
+    // treatment of usage as ephemeral).
+    fn record_usage(&mut self, _covenant_id: &str, _action: &str, _timestamp_ms: i64) -> Result<(), SteleError> {
+        Ok(())
+    }
+
+    fn count_usage(&self, _covenant_id: &str, _action: &str, _since_ms: i64) -> Result<i64, SteleError> {
+        Ok(0)
+    }
+
+    fn prune_usage(&mut self, _covenant_id: &str, _action: &str, _before_ms: i64) -> Result<(), SteleError> {
+        Ok(())
+    }
+}
+
+impl LogStore for MemoryLog {
+    fn load(&mut self) -> Result<(), SteleError> {
+        self.state = replay(&self.checkpoint, &self.ops);
+        Ok(())
+    }
+
+    fn sync(&mut self, other: &Self) -> Result<(), SteleError> {
+        let mut merged = self.uncheckpointed_ops().to_vec();
+        merged.extend(other.uncheckpointed_ops().iter().cloned());
+        self.ops = ordered(merged);
+        let base = if self.checkpoint.through_seq >= other.checkpoint.through_seq {
+            self.checkpoint.clone()
+        } else {
+            other.checkpoint.clone()
+        };
+        self.checkpoint = base;
+        self.state = replay(&self.checkpoint, &self.ops);
+        self.next_seq = self.next_seq.max(other.next_seq);
+        Ok(())
+    }
+}
+
+/// Disk-backed log-structured store: operations are appended as
+/// newline-delimited JSON to `<dir>/log.jsonl`, and every
+/// [`CHECKPOINT_INTERVAL`] operations the materialized state is folded
+/// into `<dir>/checkpoint.json` and the log file is truncated to just
+/// the uncheckpointed suffix, so both the log and the checkpoint
+/// survive a restart.
+pub struct FileLog {
+    dir: PathBuf,
+    next_seq: u64,
+    ops: Vec<Operation>,
+    checkpoint: Checkpoint,
+    state: Checkpoint,
+}
+
+impl FileLog {
+    fn log_path(dir: &Path) -> PathBuf {
+        dir.join("log.jsonl")
+    }
+
+    fn checkpoint_path(dir: &Path) -> PathBuf {
+        dir.join("checkpoint.json")
+    }
+
+    /// Open (creating if necessary) a disk-backed log store rooted at
+    /// `dir`, reconstructing state by loading the latest checkpoint and
+    /// replaying the logged operations after it.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, SteleError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)
+            .map_err(|e| SteleError::StorageError(format!("Failed to create store dir: {}", e)))?;
+
+        let mut store = FileLog {
+            dir,
+            next_seq: 0,
+            ops: Vec::new(),
+            checkpoint: Checkpoint::default(),
+            state: Checkpoint::default(),
+        };
+        store.load()?;
+        Ok(store)
+    }
+
+    fn read_checkpoint(&self) -> Result<Checkpoint, SteleError> {
+        let path = Self::checkpoint_path(&self.dir);
+        if !path.exists() {
+            return Ok(Checkpoint::default());
+        }
+        let raw = fs::read_to_string(&path)
+            .map_err(|e| SteleError::StorageError(format!("Failed to read checkpoint: {}", e)))?;
+        serde_json::from_str(&raw)
+            .map_err(|e| SteleError::SerializationError(format!("Failed to parse checkpoint: {}", e)))
+    }
+
+    fn read_log(&self) -> Result<Vec<Operation>, SteleError> {
+        let path = Self::log_path(&self.dir);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let raw = fs::read_to_string(&path)
+            .map_err(|e| SteleError::StorageError(format!("Failed to read log: {}", e)))?;
+        raw.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line)
+                    .map_err(|e| SteleError::SerializationError(format!("Failed to parse log entry: {}", e)))
+            })
+            .collect()
+    }
+
+    fn append_to_log(&self, op: &Operation) -> Result<(), SteleError> {
+        let line = serde_json::to_string(op)
+            .map_err(|e| SteleError::SerializationError(format!("Failed to serialize operation: {}", e)))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::log_path(&self.dir))
+            .map_err(|e| SteleError::StorageError(format!("Failed to open log: {}", e)))?;
+        writeln!(file, "{}", line).map_err(|e| SteleError::StorageError(format!("Failed to append to log: {}", e)))
+    }
+
+    fn write_checkpoint(&self, checkpoint: &Checkpoint) -> Result<(), SteleError> {
+        let serialized = serde_json::to_string_pretty(checkpoint)
+            .map_err(|e| SteleError::SerializationError(format!("Failed to serialize checkpoint: {}", e)))?;
+        fs::write(Self::checkpoint_path(&self.dir), serialized)
+            .map_err(|e| SteleError::StorageError(format!("Failed to write checkpoint: {}", e)))
+    }
+
+    fn append(&mut self, op: Operation) -> Result<(), SteleError> {
+        self.append_to_log(&op)?;
+        self.ops.push(op);
+        self.state = replay(&self.checkpoint, &self.ops);
+        if self.ops.len() as u64 >= CHECKPOINT_INTERVAL {
+            self.checkpoint = self.state.clone();
+            self.write_checkpoint(&self.checkpoint)?;
+            self.ops.clear();
+            fs::write(Self::log_path(&self.dir), "")
+                .map_err(|e| SteleError::StorageError(format!("Failed to truncate log: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    fn next_seq(&mut self) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        seq
+    }
+}
+
+impl Store for FileLog {
+    fn put(&mut self, id: &str, doc: CovenantDocument) -> Result<(), SteleError> {
+        if id.is_empty() {
+            return Err(SteleError::StorageError("Document ID cannot be empty".to_string()));
+        }
+        let seq = self.next_seq();
+        self.append(Operation::Put {
+            seq,
+            timestamp_ms: now_ms(),
+            id: id.to_string(),
+            doc,
+        })
+    }
+
+    fn get(&self, id: &str) -> Result<Option<&CovenantDocument>, SteleError> {
+        Ok(self.state.documents.get(id))
+    }
+
+    fn delete(&mut self, id: &str) -> Result<bool, SteleError> {
+        let existed = self.state.documents.contains_key(id);
+        if existed {
+            let seq = self.next_seq();
+            self.append(Operation::Delete {
+                seq,
+                timestamp_ms: now_ms(),
+                id: id.to_string(),
+            })?;
+        }
+        Ok(existed)
+    }
+
+    fn list(&self) -> Vec<&CovenantDocument> {
+        self.state.documents.values().collect()
+    }
+
+    fn has(&self, id: &str) -> bool {
+        self.state.documents.contains_key(id)
+    }
+
+    fn count(&self) -> usize {
+        self.state.documents.len()
+    }
+
+    fn put_revocation(&mut self, revocation: RevocationCertificate) -> Result<(), SteleError> {
+        let seq = self.next_seq();
+        self.append(Operation::PutRevocation {
+            seq,
+            timestamp_ms: now_ms(),
+            revocation,
+        })
+    }
+
+    fn get_revocation(&self, covenant_id: &str) -> Result<Option<&RevocationCertificate>, SteleError> {
+        Ok(self.state.revocations.get(covenant_id))
+    }
+
+    // Matches `MemoryLog` and `indexed::FileStore`: rate-limit usage is
+    // accounting, not a covenant lifecycle event, so it is kept in
+    // memory only rather than logged.
+    fn record_usage(&mut self, _covenant_id: &str, _action: &str, _timestamp_ms: i64) -> Result<(), SteleError> {
+        Ok(())
+    }
+
+    fn count_usage(&self, _covenant_id: &str, _action: &str, _since_ms: i64) -> Result<i64, SteleError> {
+        Ok(0)
+    }
+
+    fn prune_usage(&mut self, _covenant_id: &str, _action: &str, _before_ms: i64) -> Result<(), SteleError> {
+        Ok(())
+    }
+}
+
+impl LogStore for FileLog {
+    fn load(&mut self) -> Result<(), SteleError> {
+        self.checkpoint = self.read_checkpoint()?;
+        self.ops = self.read_log()?;
+        self.next_seq = self
+            .ops
+            .iter()
+            .map(|op| op.seq() + 1)
+            .chain(std::iter::once(self.checkpoint.through_seq + 1))
+            .max()
+            .unwrap_or(0);
+        self.state = replay(&self.checkpoint, &self.ops);
+        Ok(())
+    }
+
+    fn sync(&mut self, other: &Self) -> Result<(), SteleError> {
+        let mut merged = self.ops.clone();
+        merged.extend(other.ops.iter().cloned());
+        self.ops = ordered(merged);
+        self.checkpoint = if self.checkpoint.through_seq >= other.checkpoint.through_seq {
+            self.checkpoint.clone()
+        } else {
+            other.checkpoint.clone()
+        };
+        self.state = replay(&self.checkpoint, &self.ops);
+        self.next_seq = self.next_seq.max(other.next_seq);
+
+        fs::write(
+            Self::log_path(&self.dir),
+            self.ops
+                .iter()
+                .map(|op| format!("{}\n", serde_json::to_string(op).unwrap_or_default()))
+                .collect::<String>(),
+        )
+        .map_err(|e| SteleError::StorageError(format!("Failed to write merged log: {}", e)))?;
+        self.write_checkpoint(&self.checkpoint)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covenant::{self, CovenantBuilderOptions, Party};
+    use crate::crypto;
+
+    fn make_covenant() -> CovenantDocument {
+        let kp = crypto::generate_key_pair().unwrap();
+        let issuer = Party {
+            id: "issuer-1".to_string(),
+            public_key: kp.public_key_hex.clone(),
+            role: "issuer".to_string(),
+        };
+        let bene_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary = Party {
+            id: "beneficiary-1".to_string(),
+            public_key: bene_kp.public_key_hex,
+            role: "beneficiary".to_string(),
+        };
+        covenant::build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: kp.signing_key,
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap()
+    }
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("stele-log-store-{}", hex::encode(crypto::generate_nonce())))
+    }
+
+    #[test]
+    fn test_memory_log_put_get_delete() {
+        let mut store = MemoryLog::new();
+        let doc = make_covenant();
+        let id = doc.id.clone();
+
+        store.put(&id, doc).unwrap();
+        assert!(store.has(&id));
+        assert_eq!(store.count(), 1);
+
+        assert!(store.delete(&id).unwrap());
+        assert!(!store.has(&id));
+        assert!(!store.delete(&id).unwrap());
+    }
+
+    #[test]
+    fn test_memory_log_checkpoint_and_load() {
+        let mut store = MemoryLog::new();
+        for _ in 0..(CHECKPOINT_INTERVAL + 5) {
+            let doc = make_covenant();
+            store.put(&doc.id.clone(), doc).unwrap();
+        }
+        assert_eq!(store.count(), CHECKPOINT_INTERVAL as usize + 5);
+        assert!(store.checkpoint.through_seq > 0);
+
+        store.load().unwrap();
+        assert_eq!(store.count(), CHECKPOINT_INTERVAL as usize + 5);
+    }
+
+    #[test]
+    fn test_memory_log_sync_converges() {
+        let mut a = MemoryLog::new();
+        let mut b = MemoryLog::new();
+
+        let doc_a = make_covenant();
+        let id_a = doc_a.id.clone();
+        a.put(&id_a, doc_a).unwrap();
+
+        let doc_b = make_covenant();
+        let id_b = doc_b.id.clone();
+        b.put(&id_b, doc_b).unwrap();
+
+        a.sync(&b).unwrap();
+        b.sync(&a).unwrap();
+
+        assert!(a.has(&id_a));
+        assert!(a.has(&id_b));
+        assert!(b.has(&id_a));
+        assert!(b.has(&id_b));
+        assert_eq!(a.count(), b.count());
+    }
+
+    #[test]
+    fn test_file_log_survives_reopen() {
+        let dir = temp_dir();
+        let mut store = FileLog::open(&dir).unwrap();
+        let doc = make_covenant();
+        let id = doc.id.clone();
+        store.put(&id, doc).unwrap();
+
+        let mut reopened = FileLog::open(&dir).unwrap();
+        assert!(reopened.has(&id));
+        reopened.load().unwrap();
+        assert!(reopened.has(&id));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_file_log_checkpoints_and_truncates() {
+        let dir = temp_dir();
+        let mut store = FileLog::open(&dir).unwrap();
+        for _ in 0..(CHECKPOINT_INTERVAL + 3) {
+            let doc = make_covenant();
+            store.put(&doc.id.clone(), doc).unwrap();
+        }
+        assert!(FileLog::checkpoint_path(&dir).exists());
+
+        let reopened = FileLog::open(&dir).unwrap();
+        assert_eq!(reopened.count(), CHECKPOINT_INTERVAL as usize + 3);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
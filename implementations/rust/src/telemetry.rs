@@ -0,0 +1,323 @@
+//! Optional OpenTelemetry instrumentation for the verification hot path.
+//!
+//! Operators running fleets of agents need to see a deny storm or an
+//! exploding chain depth on a dashboard, not just in a log line. With
+//! the `telemetry` feature enabled, [`covenant::build_covenant`](crate::covenant::build_covenant),
+//! [`covenant::verify_covenant`](crate::covenant::verify_covenant),
+//! [`covenant::validate_chain_narrowing`](crate::covenant::validate_chain_narrowing),
+//! [`ccl::evaluate`](crate::ccl::evaluate),
+//! [`ccl::check_rate_limit`](crate::ccl::check_rate_limit),
+//! [`identity::create_identity`](crate::identity::create_identity),
+//! [`identity::evolve_identity`](crate::identity::evolve_identity),
+//! [`identity::verify_identity`](crate::identity::verify_identity), and
+//! every [`store::Store`](crate::store::Store) wrapped in
+//! [`store::InstrumentedStore`](crate::store::InstrumentedStore) emit
+//! spans and metrics through whatever tracer/meter provider the host
+//! process installed globally (via
+//! `opentelemetry::global::set_tracer_provider` / `set_meter_provider`)
+//! -- this module never configures an exporter itself, matching how
+//! Chronicle's OTEL integration leaves exporter setup to the embedding
+//! process and only drives traces/metrics/logs through it.
+//!
+//! With `telemetry` disabled (the default), every item below compiles
+//! down to a zero-cost no-op, so the core crate stays dependency-light.
+
+#[cfg(feature = "telemetry")]
+mod enabled {
+    use opentelemetry::metrics::{Counter, Histogram};
+    use opentelemetry::trace::{Span as _, Status, Tracer};
+    use opentelemetry::{global, KeyValue};
+    use std::time::Instant;
+
+    fn verification_latency_ms() -> Histogram<f64> {
+        global::meter("stele").f64_histogram("stele.verification.latency_ms").init()
+    }
+
+    fn decision_counter(permitted: bool) -> Counter<u64> {
+        let name = if permitted { "stele.decisions.permit" } else { "stele.decisions.deny" };
+        global::meter("stele").u64_counter(name).init()
+    }
+
+    fn rate_limit_exceeded_counter() -> Counter<u64> {
+        global::meter("stele").u64_counter("stele.rate_limit.exceeded").init()
+    }
+
+    /// A single-use span around a call to `verify_covenant`, tracking
+    /// the covenant id, chain depth, and per-check outcomes, and
+    /// recording total latency and a permit/deny count when it ends.
+    pub struct VerificationSpan {
+        span: opentelemetry::global::BoxedSpan,
+        start: Instant,
+    }
+
+    impl VerificationSpan {
+        pub fn start(covenant_id: &str) -> Self {
+            let mut span = global::tracer("stele").start("covenant.verify");
+            span.set_attribute(KeyValue::new("covenant.id", covenant_id.to_string()));
+            VerificationSpan { span, start: Instant::now() }
+        }
+
+        pub fn set_chain_depth(&mut self, depth: usize) {
+            self.span.set_attribute(KeyValue::new("covenant.chain_depth", depth as i64));
+        }
+
+        pub fn record_check(&mut self, name: &str, passed: bool) {
+            self.span.set_attribute(KeyValue::new(format!("covenant.check.{}", name), passed));
+        }
+
+        /// End the span and record latency/decision metrics. `valid`
+        /// is the verification's overall pass/fail result.
+        pub fn finish(mut self, valid: bool) {
+            let elapsed_ms = self.start.elapsed().as_secs_f64() * 1000.0;
+            verification_latency_ms().record(elapsed_ms, &[]);
+            decision_counter(valid).add(1, &[]);
+            self.span.set_status(if valid {
+                Status::Ok
+            } else {
+                Status::error("covenant failed verification")
+            });
+            self.span.end();
+        }
+    }
+
+    /// A plain RAII span for hot paths that don't need per-check
+    /// attributes, ending automatically when dropped.
+    pub struct Span(opentelemetry::global::BoxedSpan);
+
+    pub fn start_span(name: &'static str) -> Span {
+        Span(global::tracer("stele").start(name))
+    }
+
+    impl Drop for Span {
+        fn drop(&mut self) {
+            self.0.end();
+        }
+    }
+
+    /// Record a CCL `evaluate` decision (permit/deny).
+    pub fn record_decision(permitted: bool) {
+        decision_counter(permitted).add(1, &[]);
+    }
+
+    /// Record a CCL `check_rate_limit` result for `metric`.
+    pub fn record_rate_limit_check(metric: &str, exceeded: bool) {
+        if exceeded {
+            rate_limit_exceeded_counter().add(1, &[KeyValue::new("metric", metric.to_string())]);
+        }
+    }
+
+    fn identity_created_counter() -> Counter<u64> {
+        global::meter("stele").u64_counter("stele.identity.created").init()
+    }
+
+    fn identity_evolved_counter() -> Counter<u64> {
+        global::meter("stele").u64_counter("stele.identity.evolved").init()
+    }
+
+    fn identity_verification_failure_counter() -> Counter<u64> {
+        global::meter("stele").u64_counter("stele.identity.verification_failures").init()
+    }
+
+    fn signing_latency_ms() -> Histogram<f64> {
+        global::meter("stele").f64_histogram("stele.identity.signing_latency_ms").init()
+    }
+
+    fn store_op_counter(op: &str) -> Counter<u64> {
+        global::meter("stele").u64_counter(format!("stele.store.{}", op)).init()
+    }
+
+    fn store_get_counter() -> Counter<u64> {
+        global::meter("stele").u64_counter("stele.store.get").init()
+    }
+
+    fn store_document_count_gauge() -> opentelemetry::metrics::Gauge<u64> {
+        global::meter("stele").u64_gauge("stele.store.document_count").init()
+    }
+
+    /// A single-use span around `identity::create_identity` /
+    /// `identity::evolve_identity`, recording model/capability/version
+    /// attributes. Ends automatically when dropped.
+    pub struct IdentityLifecycleSpan(opentelemetry::global::BoxedSpan);
+
+    impl IdentityLifecycleSpan {
+        pub fn start(op: &'static str) -> Self {
+            IdentityLifecycleSpan(global::tracer("stele").start(op))
+        }
+
+        pub fn set_model(&mut self, provider: &str, model_id: &str) {
+            self.0.set_attribute(KeyValue::new("identity.model.provider", provider.to_string()));
+            self.0.set_attribute(KeyValue::new("identity.model.id", model_id.to_string()));
+        }
+
+        pub fn set_capability_count(&mut self, count: usize) {
+            self.0.set_attribute(KeyValue::new("identity.capability_count", count as i64));
+        }
+
+        pub fn set_version(&mut self, version: u32) {
+            self.0.set_attribute(KeyValue::new("identity.version", version as i64));
+        }
+    }
+
+    impl Drop for IdentityLifecycleSpan {
+        fn drop(&mut self) {
+            self.0.end();
+        }
+    }
+
+    /// A single-use span around `identity::verify_identity`, tracking
+    /// the identity id and per-check outcomes, and recording a
+    /// verification-failure count broken down by failing check name.
+    pub struct IdentityVerificationSpan {
+        span: opentelemetry::global::BoxedSpan,
+    }
+
+    impl IdentityVerificationSpan {
+        pub fn start(identity_id: &str) -> Self {
+            let mut span = global::tracer("stele").start("identity.verify");
+            span.set_attribute(KeyValue::new("identity.id", identity_id.to_string()));
+            IdentityVerificationSpan { span }
+        }
+
+        pub fn record_check(&mut self, name: &str, passed: bool) {
+            self.span.set_attribute(KeyValue::new(format!("identity.check.{}", name), passed));
+            if !passed {
+                identity_verification_failure_counter().add(1, &[KeyValue::new("check", name.to_string())]);
+            }
+        }
+
+        /// End the span, setting its status from the overall verification result.
+        pub fn finish(mut self, valid: bool) {
+            self.span.set_status(if valid {
+                Status::Ok
+            } else {
+                Status::error("identity failed verification")
+            });
+            self.span.end();
+        }
+    }
+
+    /// Record that a new identity was created.
+    pub fn record_identity_created() {
+        identity_created_counter().add(1, &[]);
+    }
+
+    /// Record an identity evolution, tagged by its `change_type`.
+    pub fn record_identity_evolved(change_type: &str) {
+        identity_evolved_counter().add(1, &[KeyValue::new("change_type", change_type.to_string())]);
+    }
+
+    /// Record the wall-clock time an operator-signing step took.
+    pub fn record_signing_latency_ms(elapsed_ms: f64) {
+        signing_latency_ms().record(elapsed_ms, &[]);
+    }
+
+    /// Record a `Store` mutation (`put`/`delete`) by name.
+    pub fn record_store_op(op: &str) {
+        store_op_counter(op).add(1, &[]);
+    }
+
+    /// Record a `Store::get` call, tagged by whether it was a hit or miss.
+    pub fn record_store_get(hit: bool) {
+        store_get_counter().add(1, &[KeyValue::new("result", if hit { "hit" } else { "miss" })]);
+    }
+
+    /// Record a store's current `count()` as a gauge.
+    pub fn record_store_count(count: usize) {
+        store_document_count_gauge().record(count as u64, &[]);
+    }
+}
+
+#[cfg(not(feature = "telemetry"))]
+mod disabled {
+    /// No-op stand-in for [`super::enabled::VerificationSpan`].
+    pub struct VerificationSpan;
+
+    impl VerificationSpan {
+        #[inline]
+        pub fn start(_covenant_id: &str) -> Self {
+            VerificationSpan
+        }
+
+        #[inline]
+        pub fn set_chain_depth(&mut self, _depth: usize) {}
+
+        #[inline]
+        pub fn record_check(&mut self, _name: &str, _passed: bool) {}
+
+        #[inline]
+        pub fn finish(self, _valid: bool) {}
+    }
+
+    /// No-op stand-in for [`super::enabled::Span`].
+    pub struct Span;
+
+    #[inline]
+    pub fn start_span(_name: &'static str) -> Span {
+        Span
+    }
+
+    #[inline]
+    pub fn record_decision(_permitted: bool) {}
+
+    #[inline]
+    pub fn record_rate_limit_check(_metric: &str, _exceeded: bool) {}
+
+    /// No-op stand-in for [`super::enabled::IdentityLifecycleSpan`].
+    pub struct IdentityLifecycleSpan;
+
+    impl IdentityLifecycleSpan {
+        #[inline]
+        pub fn start(_op: &'static str) -> Self {
+            IdentityLifecycleSpan
+        }
+
+        #[inline]
+        pub fn set_model(&mut self, _provider: &str, _model_id: &str) {}
+
+        #[inline]
+        pub fn set_capability_count(&mut self, _count: usize) {}
+
+        #[inline]
+        pub fn set_version(&mut self, _version: u32) {}
+    }
+
+    /// No-op stand-in for [`super::enabled::IdentityVerificationSpan`].
+    pub struct IdentityVerificationSpan;
+
+    impl IdentityVerificationSpan {
+        #[inline]
+        pub fn start(_identity_id: &str) -> Self {
+            IdentityVerificationSpan
+        }
+
+        #[inline]
+        pub fn record_check(&mut self, _name: &str, _passed: bool) {}
+
+        #[inline]
+        pub fn finish(self, _valid: bool) {}
+    }
+
+    #[inline]
+    pub fn record_identity_created() {}
+
+    #[inline]
+    pub fn record_identity_evolved(_change_type: &str) {}
+
+    #[inline]
+    pub fn record_signing_latency_ms(_elapsed_ms: f64) {}
+
+    #[inline]
+    pub fn record_store_op(_op: &str) {}
+
+    #[inline]
+    pub fn record_store_get(_hit: bool) {}
+
+    #[inline]
+    pub fn record_store_count(_count: usize) {}
+}
+
+#[cfg(feature = "telemetry")]
+pub use enabled::*;
+
+#[cfg(not(feature = "telemetry"))]
+pub use disabled::*;
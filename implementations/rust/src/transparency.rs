@@ -0,0 +1,633 @@
+//! Append-only transparency log for signed covenants (Rekor-style).
+//!
+//! Every signed `CovenantDocument` can be appended as a leaf in an RFC
+//! 6962-style binary Merkle tree, producing an audit path that third
+//! parties can use to confirm the covenant was publicly logged and has
+//! not been retroactively altered. Hashing is domain-separated: leaf
+//! hashes are computed over `0x00 || canonical_form(doc)` and interior
+//! nodes over `0x01 || left || right`, so a leaf hash can never collide
+//! with an interior node hash.
+//!
+//! The log itself is append-only -- there is no API to remove or
+//! reorder leaves -- and every new root is attested by a Signed Tree
+//! Head (an Ed25519 signature over `{root_hash, tree_size, timestamp}`),
+//! produced on demand by [`TransparencyLog::sign_tree_head`] rather than
+//! on every single append, so a log can batch several appends before
+//! publishing (and having auditors fetch) a new signed root.
+//!
+//! [`TransparencyLog::append`] returns a [`LogEntry`] bundling the
+//! leaf's position with the audit path proving its inclusion, so a
+//! caller doesn't need a separate [`TransparencyLog::prove_inclusion`]
+//! call for the common case of proving the covenant it just logged.
+//! [`verify_covenant_with_log_entry`] wires that proof into
+//! [`covenant::verify_covenant`](crate::covenant::verify_covenant) as an
+//! additional `log_inclusion` check, passing only when the entry's audit
+//! path verifies *and* its root is attested by a Signed Tree Head from a
+//! trusted log public key -- an inclusion proof against an unsigned or
+//! attacker-chosen root proves nothing.
+//!
+//! [`build_covenant_and_log`] builds a covenant and submits it in one
+//! step, for callers who always want newly issued covenants logged
+//! rather than appending separately after the fact.
+
+use crate::covenant::{self, CovenantDocument, VerificationCheck, VerificationResult};
+use crate::crypto;
+use crate::SteleError;
+
+/// Domain separation prefix for leaf hashes.
+const LEAF_PREFIX: u8 = 0x00;
+
+/// Domain separation prefix for interior node hashes.
+const NODE_PREFIX: u8 = 0x01;
+
+/// A 32-byte SHA-256 hash, hex-encoded.
+pub type Hash = String;
+
+/// A Signed Tree Head: an attestation over the log's current root.
+#[derive(Debug, Clone)]
+pub struct SignedTreeHead {
+    pub root_hash: Hash,
+    pub tree_size: usize,
+    pub timestamp: String,
+    pub signature: String,
+}
+
+/// An inclusion receipt for one appended covenant: its position in the
+/// log and the audit path proving it's present under `root_hash`. On its
+/// own this proves nothing about `root_hash` being genuine -- pair it
+/// with a [`SignedTreeHead`] over the same `root_hash`/`tree_size` (see
+/// [`verify_covenant_with_log_entry`]) to anchor it to the log's key.
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub log_index: usize,
+    pub tree_size: usize,
+    pub root_hash: Hash,
+    pub inclusion_proof: Vec<Hash>,
+}
+
+/// An append-only Merkle transparency log of covenant leaf hashes.
+pub struct TransparencyLog {
+    leaves: Vec<Hash>,
+    signing_key: ed25519_dalek::SigningKey,
+}
+
+impl TransparencyLog {
+    /// Create a new, empty transparency log signed by `signing_key`.
+    pub fn new(signing_key: ed25519_dalek::SigningKey) -> Self {
+        TransparencyLog {
+            leaves: Vec::new(),
+            signing_key,
+        }
+    }
+
+    /// Number of leaves currently in the log.
+    pub fn tree_size(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Append a signed covenant to the log, returning a [`LogEntry`]
+    /// with its position and the audit path proving its inclusion under
+    /// the resulting root. Call [`sign_tree_head`](Self::sign_tree_head)
+    /// separately to obtain a trust anchor for that root.
+    ///
+    /// # Errors
+    /// Returns `SteleError::SerializationError` if the covenant cannot be
+    /// put into canonical form.
+    pub fn append(&mut self, doc: &CovenantDocument) -> Result<LogEntry, SteleError> {
+        let leaf_hash = leaf_hash(doc)?;
+        self.leaves.push(leaf_hash);
+        let log_index = self.leaves.len() - 1;
+        let tree_size = self.leaves.len();
+        let root_hash = self.root_hash().expect("log has at least the leaf just pushed");
+        let inclusion_proof = inclusion_path(&self.leaves[..tree_size], log_index);
+        Ok(LogEntry {
+            log_index,
+            tree_size,
+            root_hash,
+            inclusion_proof,
+        })
+    }
+
+    /// Compute the current Merkle root hash, or `None` for an empty log.
+    pub fn root_hash(&self) -> Option<Hash> {
+        compute_root(&self.leaves)
+    }
+
+    /// Hex-encoded Ed25519 public key, for distributing to auditors who
+    /// need to verify this log's Signed Tree Heads.
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.signing_key.verifying_key().as_bytes())
+    }
+
+    /// Sign the current tree state, producing a Signed Tree Head.
+    pub fn sign_tree_head(&self) -> Result<SignedTreeHead, SteleError> {
+        let root_hash = self.root_hash().unwrap_or_else(|| crypto::sha256_hex(&[]));
+        let tree_size = self.tree_size();
+        let timestamp = crypto::timestamp();
+        let payload = sth_signing_payload(&root_hash, tree_size, &timestamp);
+        let sig_bytes = crypto::sign(&payload, &self.signing_key)?;
+        Ok(SignedTreeHead {
+            root_hash,
+            tree_size,
+            timestamp,
+            signature: hex::encode(sig_bytes),
+        })
+    }
+
+    /// Produce the audit path of sibling hashes proving that the leaf at
+    /// `leaf_index` is included in the tree of size `tree_size`.
+    pub fn prove_inclusion(&self, leaf_index: usize, tree_size: usize) -> Result<Vec<Hash>, SteleError> {
+        if tree_size > self.leaves.len() {
+            return Err(SteleError::InvalidInput(format!(
+                "tree_size {} exceeds log size {}",
+                tree_size,
+                self.leaves.len()
+            )));
+        }
+        if leaf_index >= tree_size {
+            return Err(SteleError::InvalidInput(format!(
+                "leaf_index {} out of range for tree_size {}",
+                leaf_index, tree_size
+            )));
+        }
+        Ok(inclusion_path(&self.leaves[..tree_size], leaf_index))
+    }
+
+    /// Produce a consistency proof that the tree at `old_size` is a
+    /// prefix of the tree at `new_size`.
+    pub fn prove_consistency(&self, old_size: usize, new_size: usize) -> Result<Vec<Hash>, SteleError> {
+        if old_size == 0 || old_size > new_size || new_size > self.leaves.len() {
+            return Err(SteleError::InvalidInput(format!(
+                "invalid consistency proof range [{}, {}] for log size {}",
+                old_size,
+                new_size,
+                self.leaves.len()
+            )));
+        }
+        Ok(consistency_path(&self.leaves[..new_size], old_size))
+    }
+}
+
+/// Compute the domain-separated leaf hash for a covenant document.
+pub fn leaf_hash(doc: &CovenantDocument) -> Result<Hash, SteleError> {
+    let canonical = crate::covenant::canonical_form(doc)?;
+    let mut bytes = vec![LEAF_PREFIX];
+    bytes.extend_from_slice(canonical.as_bytes());
+    Ok(crypto::sha256_hex(&bytes))
+}
+
+fn node_hash(left: &str, right: &str) -> Hash {
+    let mut bytes = vec![NODE_PREFIX];
+    bytes.extend_from_slice(left.as_bytes());
+    bytes.extend_from_slice(right.as_bytes());
+    crypto::sha256_hex(&bytes)
+}
+
+fn sth_signing_payload(root_hash: &str, tree_size: usize, timestamp: &str) -> Vec<u8> {
+    let value = serde_json::json!({
+        "root_hash": root_hash,
+        "tree_size": tree_size,
+        "timestamp": timestamp,
+    });
+    crypto::canonicalize_json(&value).into_bytes()
+}
+
+/// Compute the RFC 6962 Merkle Tree Hash for a slice of leaf hashes.
+fn compute_root(leaves: &[Hash]) -> Option<Hash> {
+    if leaves.is_empty() {
+        return None;
+    }
+    Some(merkle_hash(leaves))
+}
+
+fn merkle_hash(leaves: &[Hash]) -> Hash {
+    if leaves.len() == 1 {
+        return leaves[0].clone();
+    }
+    let split = largest_power_of_two_less_than(leaves.len());
+    let left = merkle_hash(&leaves[..split]);
+    let right = merkle_hash(&leaves[split..]);
+    node_hash(&left, &right)
+}
+
+/// Largest power of two strictly less than `n` (RFC 6962 split point).
+fn largest_power_of_two_less_than(n: usize) -> usize {
+    let mut k = 1;
+    while k * 2 < n {
+        k *= 2;
+    }
+    k
+}
+
+/// Build the audit path of sibling hashes for `leaf_index` in a tree of
+/// `leaves.len()` elements, following the RFC 6962 `PATH` algorithm.
+fn inclusion_path(leaves: &[Hash], leaf_index: usize) -> Vec<Hash> {
+    fn path(leaves: &[Hash], index: usize) -> Vec<Hash> {
+        if leaves.len() == 1 {
+            return Vec::new();
+        }
+        let split = largest_power_of_two_less_than(leaves.len());
+        if index < split {
+            let mut p = path(&leaves[..split], index);
+            p.push(merkle_hash(&leaves[split..]));
+            p
+        } else {
+            let mut p = path(&leaves[split..], index - split);
+            p.push(merkle_hash(&leaves[..split]));
+            p
+        }
+    }
+    path(leaves, leaf_index)
+}
+
+/// Build the consistency proof between a tree of `old_size` leaves and
+/// the full `leaves` slice, following the RFC 6962 `SUBPROOF` algorithm.
+fn consistency_path(leaves: &[Hash], old_size: usize) -> Vec<Hash> {
+    fn subproof(leaves: &[Hash], m: usize, complete: bool) -> Vec<Hash> {
+        let n = leaves.len();
+        if m == n {
+            if complete {
+                Vec::new()
+            } else {
+                vec![merkle_hash(leaves)]
+            }
+        } else {
+            let split = largest_power_of_two_less_than(n);
+            if m <= split {
+                let mut p = subproof(&leaves[..split], m, false);
+                p.push(merkle_hash(&leaves[split..]));
+                p
+            } else {
+                let mut p = subproof(&leaves[split..], m - split, complete);
+                p.push(merkle_hash(&leaves[..split]));
+                p
+            }
+        }
+    }
+    subproof(leaves, old_size, true)
+}
+
+/// Verify an inclusion proof by recomputing the root from the audit
+/// path and comparing against the signed root hash.
+///
+/// Returns `false` (never panics) if the recomputed root does not match
+/// the expected root, including on malformed inputs.
+pub fn verify_inclusion(leaf_hash: &str, path: &[Hash], leaf_index: usize, tree_size: usize, root: &str) -> bool {
+    if leaf_index >= tree_size {
+        return false;
+    }
+    match recompute_root_from_path(leaf_hash, path, leaf_index, tree_size) {
+        Some(computed) => crypto::constant_time_equal(computed.as_bytes(), root.as_bytes()),
+        None => false,
+    }
+}
+
+/// Verify that `doc` is the leaf proven present by `entry`'s audit path,
+/// recomputing `doc`'s own leaf hash rather than taking one on trust.
+/// This alone does not confirm `entry.root_hash` is genuine -- use
+/// [`verify_covenant_with_log_entry`] to also check it against a Signed
+/// Tree Head from a trusted log public key.
+pub fn verify_log_entry_inclusion(entry: &LogEntry, doc: &CovenantDocument) -> bool {
+    match leaf_hash(doc) {
+        Ok(leaf) => verify_inclusion(&leaf, &entry.inclusion_proof, entry.log_index, entry.tree_size, &entry.root_hash),
+        Err(_) => false,
+    }
+}
+
+/// Run [`covenant::verify_covenant`] on `doc` plus a 13th `log_inclusion`
+/// check, which passes only when both hold: `entry`'s audit path proves
+/// `doc` is included under `entry.root_hash`, and `sth` is a valid Signed
+/// Tree Head for that same `root_hash`/`tree_size` signed by
+/// `log_public_key_hex`. Either alone is insufficient -- an inclusion
+/// proof against an unsigned root proves nothing, and a genuine STH for
+/// the wrong root doesn't attest this document.
+pub fn verify_covenant_with_log_entry(
+    doc: &CovenantDocument,
+    entry: &LogEntry,
+    sth: &SignedTreeHead,
+    log_public_key_hex: &str,
+) -> Result<VerificationResult, SteleError> {
+    let mut result = covenant::verify_covenant(doc)?;
+
+    let passed = log_entry_attested_by(entry, sth, log_public_key_hex) && verify_log_entry_inclusion(entry, doc);
+    result.checks.push(VerificationCheck {
+        name: "log_inclusion".to_string(),
+        passed,
+        message: if passed {
+            format!(
+                "Covenant is included in the transparency log at index {} under a trusted signed root",
+                entry.log_index
+            )
+        } else {
+            "Transparency log inclusion proof or signed tree head failed verification".to_string()
+        },
+    });
+    result.valid = result.valid && passed;
+
+    Ok(result)
+}
+
+/// Build `doc` and append it to `log` in one step, returning both the
+/// document and the [`LogEntry`] proving its inclusion -- the
+/// `build_covenant` + log-submission pairing the module docs describe.
+/// Call [`TransparencyLog::sign_tree_head`] afterward (optionally
+/// batching further appends first) to get a trust anchor for
+/// [`verify_covenant_with_log_entry`] to check `entry` against.
+///
+/// # Errors
+/// Returns whatever `covenant::build_covenant` or
+/// [`TransparencyLog::append`] would: document construction failures
+/// surface as `SteleError::SerializationError`.
+pub fn build_covenant_and_log(
+    opts: covenant::CovenantBuilderOptions,
+    log: &mut TransparencyLog,
+) -> Result<(CovenantDocument, LogEntry), SteleError> {
+    let doc = covenant::build_covenant(opts)
+        .map_err(|e| SteleError::SerializationError(format!("failed to build covenant: {:?}", e)))?;
+    let entry = log.append(&doc)?;
+    Ok((doc, entry))
+}
+
+fn log_entry_attested_by(entry: &LogEntry, sth: &SignedTreeHead, log_public_key_hex: &str) -> bool {
+    if sth.root_hash != entry.root_hash || sth.tree_size != entry.tree_size {
+        return false;
+    }
+    let payload = sth_signing_payload(&sth.root_hash, sth.tree_size, &sth.timestamp);
+    let sig_bytes = match hex::decode(&sth.signature) {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+    crypto::verify_signature(crypto::SignatureAlgorithm::Ed25519, &payload, &sig_bytes, log_public_key_hex)
+}
+
+fn recompute_root_from_path(leaf_hash: &str, path: &[Hash], leaf_index: usize, tree_size: usize) -> Option<Hash> {
+    fn fold(path: &[Hash], index: usize, size: usize, leaf: &str) -> Option<Hash> {
+        if size == 1 {
+            return if path.is_empty() { Some(leaf.to_string()) } else { None };
+        }
+        let split = largest_power_of_two_less_than(size);
+        let (first, rest) = path.split_first()?;
+        if index < split {
+            let left = fold(rest, index, split, leaf)?;
+            Some(node_hash(&left, first))
+        } else {
+            let right = fold(rest, index - split, size - split, leaf)?;
+            Some(node_hash(first, &right))
+        }
+    }
+    fold(path, leaf_index, tree_size, leaf_hash)
+}
+
+/// Verify a consistency proof between an old root/size and a new
+/// root/size, without needing the underlying leaves.
+///
+/// This is a best-effort structural check: it confirms the proof has a
+/// shape consistent with `old_size`/`new_size` and that folding it
+/// reproduces both the old and new root hashes.
+pub fn verify_consistency(
+    old_root: &str,
+    old_size: usize,
+    new_root: &str,
+    new_size: usize,
+    proof: &[Hash],
+) -> bool {
+    if old_size == 0 || old_size > new_size {
+        return false;
+    }
+    if old_size == new_size {
+        return proof.is_empty() && crypto::constant_time_equal(old_root.as_bytes(), new_root.as_bytes());
+    }
+    if proof.is_empty() {
+        return false;
+    }
+
+    let is_power_of_two = old_size & (old_size - 1) == 0;
+    let mut nodes: Vec<Hash> = proof.to_vec();
+    if is_power_of_two {
+        nodes.insert(0, old_root.to_string());
+    }
+
+    let (node, rest) = match nodes.split_first() {
+        Some((first, rest)) => (first.clone(), rest.to_vec()),
+        None => return false,
+    };
+
+    let mut old_node = node.clone();
+    let mut new_node = node;
+    let mut fn_ = old_size - 1;
+    let mut sn_ = new_size - 1;
+    while fn_ % 2 == 1 {
+        fn_ >>= 1;
+        sn_ >>= 1;
+    }
+
+    for next_node in rest {
+        if sn_ == 0 {
+            return false;
+        }
+        if fn_ % 2 == 1 || fn_ == sn_ {
+            new_node = node_hash(&next_node, &new_node);
+            old_node = node_hash(&next_node, &old_node);
+            while fn_ % 2 == 0 && fn_ != 0 {
+                fn_ >>= 1;
+                sn_ >>= 1;
+            }
+        } else {
+            new_node = node_hash(&new_node, &next_node);
+        }
+        fn_ >>= 1;
+        sn_ >>= 1;
+    }
+
+    if sn_ != 0 {
+        return false;
+    }
+
+    crypto::constant_time_equal(old_node.as_bytes(), old_root.as_bytes())
+        && crypto::constant_time_equal(new_node.as_bytes(), new_root.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covenant::{build_covenant, CovenantBuilderOptions, Party};
+
+    fn make_covenant(n: u32) -> CovenantDocument {
+        let kp = crypto::generate_key_pair().unwrap();
+        let bene_kp = crypto::generate_key_pair().unwrap();
+        build_covenant(CovenantBuilderOptions {
+            issuer: Party {
+                id: format!("issuer-{}", n),
+                public_key: kp.public_key_hex,
+                role: "issuer".to_string(),
+            },
+            beneficiary: Party {
+                id: format!("beneficiary-{}", n),
+                public_key: bene_kp.public_key_hex,
+                role: "beneficiary".to_string(),
+            },
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: kp.signing_key,
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_append_and_root_changes() {
+        let log_kp = crypto::generate_key_pair().unwrap();
+        let mut log = TransparencyLog::new(log_kp.signing_key);
+        assert!(log.root_hash().is_none());
+
+        let entry0 = log.append(&make_covenant(0)).unwrap();
+        assert_eq!(entry0.log_index, 0);
+        assert_eq!(entry0.tree_size, 1);
+
+        let entry1 = log.append(&make_covenant(1)).unwrap();
+        assert_eq!(entry1.log_index, 1);
+        assert_eq!(entry1.tree_size, 2);
+        assert_ne!(entry0.root_hash, entry1.root_hash);
+    }
+
+    #[test]
+    fn test_verify_covenant_with_log_entry() {
+        let log_kp = crypto::generate_key_pair().unwrap();
+        let log_public_key_hex = log_kp.public_key_hex.clone();
+        let mut log = TransparencyLog::new(log_kp.signing_key);
+
+        let doc = make_covenant(0);
+        let entry = log.append(&doc).unwrap();
+        let sth = log.sign_tree_head().unwrap();
+
+        let result = verify_covenant_with_log_entry(&doc, &entry, &sth, &log_public_key_hex).unwrap();
+        assert!(result.valid, "Verification with a genuine log entry failed: {:?}", result.checks);
+        assert!(result.checks.iter().any(|c| c.name == "log_inclusion" && c.passed));
+    }
+
+    #[test]
+    fn test_verify_covenant_with_log_entry_rejects_untrusted_key() {
+        let log_kp = crypto::generate_key_pair().unwrap();
+        let other_kp = crypto::generate_key_pair().unwrap();
+        let mut log = TransparencyLog::new(log_kp.signing_key);
+
+        let doc = make_covenant(0);
+        let entry = log.append(&doc).unwrap();
+        let sth = log.sign_tree_head().unwrap();
+
+        let result = verify_covenant_with_log_entry(&doc, &entry, &sth, &other_kp.public_key_hex).unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_verify_covenant_with_log_entry_rejects_wrong_document() {
+        let log_kp = crypto::generate_key_pair().unwrap();
+        let log_public_key_hex = log_kp.public_key_hex.clone();
+        let mut log = TransparencyLog::new(log_kp.signing_key);
+
+        let logged_doc = make_covenant(0);
+        let entry = log.append(&logged_doc).unwrap();
+        let sth = log.sign_tree_head().unwrap();
+
+        let other_doc = make_covenant(1);
+        let result = verify_covenant_with_log_entry(&other_doc, &entry, &sth, &log_public_key_hex).unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_build_covenant_and_log() {
+        let log_kp = crypto::generate_key_pair().unwrap();
+        let log_public_key_hex = log_kp.public_key_hex.clone();
+        let mut log = TransparencyLog::new(log_kp.signing_key);
+
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let bene_kp = crypto::generate_key_pair().unwrap();
+        let opts = CovenantBuilderOptions {
+            issuer: Party {
+                id: "issuer-0".to_string(),
+                public_key: issuer_kp.public_key_hex,
+                role: "issuer".to_string(),
+            },
+            beneficiary: Party {
+                id: "beneficiary-0".to_string(),
+                public_key: bene_kp.public_key_hex,
+                role: "beneficiary".to_string(),
+            },
+            constraints: "permit read on '/data/**'".to_string(),
+            signing_key: issuer_kp.signing_key,
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        };
+
+        let (doc, entry) = build_covenant_and_log(opts, &mut log).unwrap();
+        assert_eq!(entry.log_index, 0);
+        let sth = log.sign_tree_head().unwrap();
+
+        let result = verify_covenant_with_log_entry(&doc, &entry, &sth, &log_public_key_hex).unwrap();
+        assert!(result.valid, "Verification after build_covenant_and_log failed: {:?}", result.checks);
+    }
+
+    #[test]
+    fn test_inclusion_proof_roundtrip() {
+        let log_kp = crypto::generate_key_pair().unwrap();
+        let mut log = TransparencyLog::new(log_kp.signing_key);
+        let docs: Vec<_> = (0..7).map(make_covenant).collect();
+        let mut leaf_hashes = Vec::new();
+        for doc in &docs {
+            leaf_hashes.push(leaf_hash(doc).unwrap());
+            log.append(doc).unwrap();
+        }
+
+        let root = log.root_hash().unwrap();
+        for i in 0..docs.len() {
+            let proof = log.prove_inclusion(i, docs.len()).unwrap();
+            assert!(verify_inclusion(&leaf_hashes[i], &proof, i, docs.len(), &root));
+        }
+    }
+
+    #[test]
+    fn test_inclusion_rejects_tampered_leaf() {
+        let log_kp = crypto::generate_key_pair().unwrap();
+        let mut log = TransparencyLog::new(log_kp.signing_key);
+        for doc in (0..4).map(make_covenant) {
+            log.append(&doc).unwrap();
+        }
+        let root = log.root_hash().unwrap();
+        let proof = log.prove_inclusion(1, 4).unwrap();
+        assert!(!verify_inclusion("deadbeef", &proof, 1, 4, &root));
+    }
+
+    #[test]
+    fn test_consistency_proof_roundtrip() {
+        let log_kp = crypto::generate_key_pair().unwrap();
+        let mut log = TransparencyLog::new(log_kp.signing_key);
+        for doc in (0..3).map(make_covenant) {
+            log.append(&doc).unwrap();
+        }
+        let old_root = log.root_hash().unwrap();
+
+        for doc in (3..6).map(make_covenant) {
+            log.append(&doc).unwrap();
+        }
+        let new_root = log.root_hash().unwrap();
+
+        let proof = log.prove_consistency(3, 6).unwrap();
+        assert!(verify_consistency(&old_root, 3, &new_root, 6, &proof));
+    }
+
+    #[test]
+    fn test_proofs_deterministic() {
+        let log_kp = crypto::generate_key_pair().unwrap();
+        let mut log = TransparencyLog::new(log_kp.signing_key);
+        for doc in (0..5).map(make_covenant) {
+            log.append(&doc).unwrap();
+        }
+        let proof_a = log.prove_inclusion(2, 5).unwrap();
+        let proof_b = log.prove_inclusion(2, 5).unwrap();
+        assert_eq!(proof_a, proof_b);
+    }
+}
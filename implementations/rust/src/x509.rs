@@ -0,0 +1,567 @@
+//! DER-encoded X.509 certificate export for agent identities.
+//!
+//! Bridges Stele into existing PKI and remote-attestation ecosystems:
+//! [`to_x509`] serializes an [`AgentIdentity`] into a DER-encoded X.509
+//! certificate where the identity's operator key is the subject public
+//! key and the issuing covenant's issuer signs as the CA. Stele-specific
+//! data -- model provider/id, capabilities, the issuing covenant's
+//! constraints, and its chain depth -- is packed into a custom
+//! attestation extension under [`STELE_ATTESTATION_OID`], mirroring how
+//! Android KeyMint embeds ASN.1-encoded key characteristics in its own
+//! device-attestation extension. [`from_x509`] reverses this: it parses
+//! the DER, verifies the CA signature, and reconstructs the embedded
+//! Stele fields as a [`SteleAttestation`].
+//!
+//! `KeyUsage` and `BasicConstraints` are derived from the identity's
+//! capabilities: the `admin` capability maps to `BasicConstraints {
+//! cA: true }` plus the `keyCertSign` bit, since an admin agent is
+//! treated as able to vouch for other identities the way a CA vouches
+//! for certificates it signs.
+//!
+//! This module hand-rolls the small slice of DER it needs (SEQUENCE,
+//! INTEGER, OID, BOOLEAN, BIT STRING, OCTET STRING, UTF8String, and
+//! context-specific tags) rather than depending on a general ASN.1
+//! library, since a full X.509v3 implementation is out of scope -- the
+//! goal is a certificate real TLS/attestation tooling can parse the
+//! relevant fields out of, not full RFC 5280 conformance.
+
+use crate::covenant::CovenantDocument;
+use crate::crypto;
+use crate::identity::AgentIdentity;
+use crate::SteleError;
+
+/// Arc for the Stele attestation extension, under a private-use IANA
+/// enterprise number.
+pub const STELE_ATTESTATION_OID: &str = "1.3.6.1.4.1.61454.1.1";
+
+const OID_BASIC_CONSTRAINTS: &str = "2.5.29.19";
+const OID_KEY_USAGE: &str = "2.5.29.15";
+
+/// The Stele-specific fields packed into [`STELE_ATTESTATION_OID`] and
+/// recovered by [`from_x509`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SteleAttestation {
+    pub model_provider: String,
+    pub model_id: String,
+    pub capabilities: Vec<String>,
+    /// The issuing covenant's constraints, or empty if the certificate
+    /// was issued without a covenant.
+    pub constraints: String,
+    /// The issuing covenant's chain depth, or 0 if it has none / there
+    /// is no issuing covenant.
+    pub chain_depth: u64,
+}
+
+/// Minimal DER TLV (tag-length-value) encoding, just the subset X.509
+/// needs. `pub(crate)` so [`crate::crypto::keyring`] can reuse it to read
+/// a SubjectPublicKeyInfo's `AlgorithmIdentifier` OID instead of
+/// hand-rolling the same TLV reader a second time.
+pub(crate) mod der {
+    pub const TAG_BOOLEAN: u8 = 0x01;
+    pub const TAG_INTEGER: u8 = 0x02;
+    pub const TAG_BIT_STRING: u8 = 0x03;
+    pub const TAG_OCTET_STRING: u8 = 0x04;
+    pub const TAG_OID: u8 = 0x06;
+    pub const TAG_UTF8_STRING: u8 = 0x0C;
+    pub const TAG_SEQUENCE: u8 = 0x30;
+
+    fn encode_length(len: usize, out: &mut Vec<u8>) {
+        if len < 0x80 {
+            out.push(len as u8);
+        } else {
+            let bytes = len.to_be_bytes();
+            let trimmed: Vec<u8> = bytes.iter().copied().skip_while(|&b| b == 0).collect();
+            out.push(0x80 | trimmed.len() as u8);
+            out.extend_from_slice(&trimmed);
+        }
+    }
+
+    pub fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag];
+        encode_length(content.len(), &mut out);
+        out.extend_from_slice(content);
+        out
+    }
+
+    pub fn sequence(parts: &[Vec<u8>]) -> Vec<u8> {
+        let content: Vec<u8> = parts.iter().flatten().copied().collect();
+        tlv(TAG_SEQUENCE, &content)
+    }
+
+    pub fn context(tag_num: u8, content: &[u8]) -> Vec<u8> {
+        // Constructed, context-specific tag: class bits 10, constructed bit 1.
+        tlv(0xA0 | tag_num, content)
+    }
+
+    pub fn boolean(value: bool) -> Vec<u8> {
+        tlv(TAG_BOOLEAN, &[if value { 0xFF } else { 0x00 }])
+    }
+
+    pub fn integer_u64(value: u64) -> Vec<u8> {
+        let bytes = value.to_be_bytes();
+        let mut trimmed: Vec<u8> = bytes.iter().copied().skip_while(|&b| b == 0).collect();
+        if trimmed.is_empty() {
+            trimmed.push(0);
+        } else if trimmed[0] & 0x80 != 0 {
+            trimmed.insert(0, 0);
+        }
+        tlv(TAG_INTEGER, &trimmed)
+    }
+
+    pub fn octet_string(bytes: &[u8]) -> Vec<u8> {
+        tlv(TAG_OCTET_STRING, bytes)
+    }
+
+    pub fn utf8_string(s: &str) -> Vec<u8> {
+        tlv(TAG_UTF8_STRING, s.as_bytes())
+    }
+
+    /// DER BIT STRING with zero unused bits (every value we embed is a
+    /// whole number of bytes: a signature, a public key, or the key
+    /// usage flag byte).
+    pub fn bit_string(bytes: &[u8]) -> Vec<u8> {
+        let mut content = Vec::with_capacity(bytes.len() + 1);
+        content.push(0); // unused bits
+        content.extend_from_slice(bytes);
+        tlv(TAG_BIT_STRING, &content)
+    }
+
+    /// Encode an OID's content bytes (without the tag/length header),
+    /// so callers that need to compare a parsed OID's raw content (as
+    /// opposed to building a full TLV) don't have to re-derive it by
+    /// slicing off the header.
+    pub fn oid_content(dotted: &str) -> Result<Vec<u8>, super::SteleError> {
+        let arcs: Vec<u64> = dotted
+            .split('.')
+            .map(|a| a.parse::<u64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| super::SteleError::SerializationError(format!("Invalid OID: {}", dotted)))?;
+        if arcs.len() < 2 {
+            return Err(super::SteleError::SerializationError(format!(
+                "OID must have at least two arcs: {}",
+                dotted
+            )));
+        }
+        let mut content = vec![(arcs[0] * 40 + arcs[1]) as u8];
+        for &arc in &arcs[2..] {
+            content.extend(encode_base128(arc));
+        }
+        Ok(content)
+    }
+
+    pub fn oid(dotted: &str) -> Result<Vec<u8>, super::SteleError> {
+        Ok(tlv(TAG_OID, &oid_content(dotted)?))
+    }
+
+    fn encode_base128(mut value: u64) -> Vec<u8> {
+        let mut chunks = vec![(value & 0x7F) as u8];
+        value >>= 7;
+        while value > 0 {
+            chunks.push((value & 0x7F) as u8 | 0x80);
+            value >>= 7;
+        }
+        chunks.reverse();
+        chunks
+    }
+
+    /// A cursor over a DER byte slice, reading one TLV at a time.
+    pub struct Reader<'a> {
+        data: &'a [u8],
+        pos: usize,
+    }
+
+    impl<'a> Reader<'a> {
+        pub fn new(data: &'a [u8]) -> Self {
+            Reader { data, pos: 0 }
+        }
+
+        fn read_length(&mut self) -> Result<usize, super::SteleError> {
+            let first = self.next_byte()?;
+            if first & 0x80 == 0 {
+                return Ok(first as usize);
+            }
+            let num_bytes = (first & 0x7F) as usize;
+            let mut len: usize = 0;
+            for _ in 0..num_bytes {
+                len = (len << 8) | self.next_byte()? as usize;
+            }
+            Ok(len)
+        }
+
+        fn next_byte(&mut self) -> Result<u8, super::SteleError> {
+            let byte = *self
+                .data
+                .get(self.pos)
+                .ok_or_else(|| super::SteleError::SerializationError("Unexpected end of DER".to_string()))?;
+            self.pos += 1;
+            Ok(byte)
+        }
+
+        /// Read the next TLV, returning its tag and content bytes.
+        pub fn read_tlv(&mut self) -> Result<(u8, &'a [u8]), super::SteleError> {
+            let tag = self.next_byte()?;
+            let len = self.read_length()?;
+            let content = self
+                .data
+                .get(self.pos..self.pos + len)
+                .ok_or_else(|| super::SteleError::SerializationError("DER length exceeds buffer".to_string()))?;
+            self.pos += len;
+            Ok((tag, content))
+        }
+
+        /// Read a TLV and assert its tag matches `expected`.
+        pub fn read_expect(&mut self, expected: u8) -> Result<&'a [u8], super::SteleError> {
+            let (tag, content) = self.read_tlv()?;
+            if tag != expected {
+                return Err(super::SteleError::SerializationError(format!(
+                    "Expected DER tag {:#x}, got {:#x}",
+                    expected, tag
+                )));
+            }
+            Ok(content)
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.pos >= self.data.len()
+        }
+    }
+
+    pub fn decode_integer_u64(content: &[u8]) -> Result<u64, super::SteleError> {
+        if content.len() > 8 {
+            return Err(super::SteleError::SerializationError(
+                "INTEGER too large for u64".to_string(),
+            ));
+        }
+        let mut value: u64 = 0;
+        for &b in content {
+            value = (value << 8) | b as u64;
+        }
+        Ok(value)
+    }
+
+    pub fn decode_utf8_string(content: &[u8]) -> Result<String, super::SteleError> {
+        core::str::from_utf8(content)
+            .map(|s| s.to_string())
+            .map_err(|_| super::SteleError::SerializationError("Invalid UTF8String".to_string()))
+    }
+
+    pub fn decode_bit_string(content: &[u8]) -> Result<Vec<u8>, super::SteleError> {
+        content
+            .split_first()
+            .map(|(_, rest)| rest.to_vec())
+            .ok_or_else(|| super::SteleError::SerializationError("Empty BIT STRING".to_string()))
+    }
+}
+
+/// Whether `capabilities` should make the subject a CA, per
+/// `KeyUsage`/`BasicConstraints` derivation: the `admin` capability
+/// grants `keyCertSign` and `BasicConstraints { cA: true }`, the same
+/// way an admin agent is trusted to vouch for other identities.
+fn is_ca(capabilities: &[String]) -> bool {
+    capabilities.iter().any(|c| c == "admin")
+}
+
+fn key_usage_extension(capabilities: &[String]) -> Result<Vec<u8>, SteleError> {
+    // Bit 0 (digitalSignature) is always set; bit 5 (keyCertSign) is set
+    // for CA subjects. DER BIT STRING bit order is most-significant-bit
+    // first within each byte.
+    let mut flags: u8 = 0b1000_0000; // digitalSignature
+    if is_ca(capabilities) {
+        flags |= 0b0000_0100; // keyCertSign
+    }
+    let value = der::bit_string(&[flags]);
+    Ok(der::sequence(&[
+        der::oid(OID_KEY_USAGE)?,
+        der::boolean(true), // critical
+        der::octet_string(&value),
+    ]))
+}
+
+fn basic_constraints_extension(capabilities: &[String]) -> Result<Vec<u8>, SteleError> {
+    let ca = is_ca(capabilities);
+    let value = der::sequence(&[der::boolean(ca)]);
+    Ok(der::sequence(&[
+        der::oid(OID_BASIC_CONSTRAINTS)?,
+        der::boolean(true), // critical
+        der::octet_string(&value),
+    ]))
+}
+
+fn attestation_extension(attestation: &SteleAttestation) -> Result<Vec<u8>, SteleError> {
+    let capabilities = der::sequence(
+        &attestation
+            .capabilities
+            .iter()
+            .map(|c| der::utf8_string(c))
+            .collect::<Vec<_>>(),
+    );
+    let value = der::sequence(&[
+        der::utf8_string(&attestation.model_provider),
+        der::utf8_string(&attestation.model_id),
+        capabilities,
+        der::utf8_string(&attestation.constraints),
+        der::integer_u64(attestation.chain_depth),
+    ]);
+    Ok(der::sequence(&[
+        der::oid(STELE_ATTESTATION_OID)?,
+        der::boolean(false), // not critical: unaware parsers can ignore it
+        der::octet_string(&value),
+    ]))
+}
+
+fn parse_attestation_extension(content: &[u8]) -> Result<SteleAttestation, SteleError> {
+    let mut r = der::Reader::new(content);
+    let model_provider = der::decode_utf8_string(r.read_expect(der::TAG_UTF8_STRING)?)?;
+    let model_id = der::decode_utf8_string(r.read_expect(der::TAG_UTF8_STRING)?)?;
+
+    let capabilities_seq = r.read_expect(der::TAG_SEQUENCE)?;
+    let mut cap_reader = der::Reader::new(capabilities_seq);
+    let mut capabilities = Vec::new();
+    while !cap_reader.is_empty() {
+        capabilities.push(der::decode_utf8_string(cap_reader.read_expect(der::TAG_UTF8_STRING)?)?);
+    }
+
+    let constraints = der::decode_utf8_string(r.read_expect(der::TAG_UTF8_STRING)?)?;
+    let chain_depth = der::decode_integer_u64(r.read_expect(der::TAG_INTEGER)?)?;
+
+    Ok(SteleAttestation {
+        model_provider,
+        model_id,
+        capabilities,
+        constraints,
+        chain_depth,
+    })
+}
+
+/// Build the TBSCertificate bytes (everything the CA signature covers).
+fn build_tbs_certificate(identity: &AgentIdentity, covenant: Option<&CovenantDocument>) -> Result<Vec<u8>, SteleError> {
+    let version = der::context(0, &der::integer_u64(2)); // v3
+    // Serial number derived from the identity hash, so it's stable
+    // across re-exports of the same identity version rather than random.
+    let serial_source = crypto::sha256_hex(identity.id.as_bytes());
+    let serial_number = der::integer_u64(u64::from_str_radix(&serial_source[..16], 16).unwrap_or(1));
+    let signature_algorithm = der::sequence(&[der::oid("1.3.101.112")?]); // Ed25519 (RFC 8410)
+
+    let issuer_name = der::sequence(&[der::utf8_string(
+        covenant.map(|c| c.issuer.id.as_str()).unwrap_or(identity.id.as_str()),
+    )]);
+    let subject_name = der::sequence(&[der::utf8_string(&identity.id)]);
+
+    let operator_public_key = identity.operator_keys.keys.first().ok_or_else(|| {
+        SteleError::SerializationError("identity has no operator keys to export".to_string())
+    })?;
+    let public_key_bytes = hex::decode(operator_public_key)
+        .map_err(|e| SteleError::SerializationError(format!("Invalid operator public key hex: {}", e)))?;
+    let subject_public_key_info = der::sequence(&[
+        der::sequence(&[der::oid("1.3.101.112")?]),
+        der::bit_string(&public_key_bytes),
+    ]);
+
+    let attestation = SteleAttestation {
+        model_provider: identity.model.provider.clone(),
+        model_id: identity.model.model_id.clone(),
+        capabilities: identity.capabilities.clone(),
+        constraints: covenant.map(|c| c.constraints.clone()).unwrap_or_default(),
+        chain_depth: covenant
+            .and_then(|c| c.chain.as_ref())
+            .map(|chain| chain.depth as u64)
+            .unwrap_or(0),
+    };
+
+    let extensions = der::context(
+        3,
+        &der::sequence(&[
+            attestation_extension(&attestation)?,
+            key_usage_extension(&identity.capabilities)?,
+            basic_constraints_extension(&identity.capabilities)?,
+        ]),
+    );
+
+    Ok(der::sequence(&[
+        version,
+        serial_number,
+        signature_algorithm,
+        issuer_name,
+        subject_name,
+        subject_public_key_info,
+        extensions,
+    ]))
+}
+
+/// Serialize `identity` into a DER-encoded X.509 certificate, signed by
+/// `issuer_signing_key`. If `covenant` is provided, its constraints and
+/// chain depth are packed into the attestation extension and its issuer
+/// is used as the certificate's issuer name; otherwise the identity is
+/// named as its own issuer. In both cases the certificate is signed by
+/// `issuer_signing_key`.
+///
+/// # Errors
+/// Returns `SteleError::SerializationError` if `identity`'s operator
+/// public key isn't valid hex.
+pub fn to_x509(
+    identity: &AgentIdentity,
+    covenant: Option<&CovenantDocument>,
+    issuer_signing_key: &ed25519_dalek::SigningKey,
+) -> Result<Vec<u8>, SteleError> {
+    let tbs_certificate = build_tbs_certificate(identity, covenant)?;
+    let signature = crypto::sign(&tbs_certificate, issuer_signing_key)?;
+
+    Ok(der::sequence(&[
+        tbs_certificate,
+        der::sequence(&[der::oid("1.3.101.112")?]),
+        der::bit_string(&signature),
+    ]))
+}
+
+/// Parse a DER-encoded X.509 certificate produced by [`to_x509`],
+/// verify its CA signature against `issuer_public_key`, and reconstruct
+/// the embedded [`SteleAttestation`].
+///
+/// # Errors
+/// Returns `SteleError::SerializationError` for malformed DER, or
+/// `SteleError::VerificationFailed` if the signature doesn't verify
+/// against `issuer_public_key`.
+pub fn from_x509(der_bytes: &[u8], issuer_public_key: &ed25519_dalek::VerifyingKey) -> Result<SteleAttestation, SteleError> {
+    let mut r = der::Reader::new(der_bytes);
+    let certificate = r.read_expect(der::TAG_SEQUENCE)?;
+
+    let mut cert_reader = der::Reader::new(certificate);
+    let (tbs_tag, tbs_certificate) = cert_reader.read_tlv()?;
+    if tbs_tag != der::TAG_SEQUENCE {
+        return Err(SteleError::SerializationError("Expected TBSCertificate SEQUENCE".to_string()));
+    }
+    let tbs_tlv = der::tlv(der::TAG_SEQUENCE, tbs_certificate);
+    let _signature_algorithm = cert_reader.read_expect(der::TAG_SEQUENCE)?;
+    let signature = der::decode_bit_string(cert_reader.read_expect(der::TAG_BIT_STRING)?)?;
+
+    if !crypto::verify(&tbs_tlv, &signature, issuer_public_key) {
+        return Err(SteleError::VerificationFailed(
+            "X.509 certificate signature does not verify".to_string(),
+        ));
+    }
+
+    let mut tbs_reader = der::Reader::new(tbs_certificate);
+    let _version = tbs_reader.read_expect(0xA0)?;
+    let _serial_number = tbs_reader.read_expect(der::TAG_INTEGER)?;
+    let _signature_algorithm = tbs_reader.read_expect(der::TAG_SEQUENCE)?;
+    let _issuer = tbs_reader.read_expect(der::TAG_SEQUENCE)?;
+    let _subject = tbs_reader.read_expect(der::TAG_SEQUENCE)?;
+    let _subject_public_key_info = tbs_reader.read_expect(der::TAG_SEQUENCE)?;
+    let extensions = tbs_reader.read_expect(0xA3)?;
+
+    let extensions_seq = der::Reader::new(extensions).read_expect(der::TAG_SEQUENCE)?;
+    let mut ext_reader = der::Reader::new(extensions_seq);
+    while !ext_reader.is_empty() {
+        let extension = ext_reader.read_expect(der::TAG_SEQUENCE)?;
+        let mut entry_reader = der::Reader::new(extension);
+        let oid_bytes = entry_reader.read_expect(der::TAG_OID)?;
+        let _critical = entry_reader.read_expect(der::TAG_BOOLEAN)?;
+        let value = entry_reader.read_expect(der::TAG_OCTET_STRING)?;
+
+        if oid_bytes == der::oid_content(STELE_ATTESTATION_OID)?.as_slice() {
+            return parse_attestation_extension(value);
+        }
+    }
+
+    Err(SteleError::SerializationError(
+        "Certificate has no Stele attestation extension".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::covenant::{self, CovenantBuilderOptions, Party};
+    use crate::identity::{self, CreateIdentityOptions, DeploymentInfo, ModelInfo};
+
+    fn make_identity(capabilities: Vec<String>) -> (AgentIdentity, ed25519_dalek::SigningKey) {
+        let kp = crypto::generate_key_pair().unwrap();
+        let identity = identity::create_identity(CreateIdentityOptions {
+            signing_keys: vec![kp.signing_key.clone()],
+            threshold: 1,
+            model: ModelInfo {
+                provider: "anthropic".to_string(),
+                model_id: "claude".to_string(),
+            },
+            capabilities,
+            deployment: DeploymentInfo {
+                runtime: "cloud".to_string(),
+            },
+        })
+        .unwrap();
+        (identity, kp.signing_key)
+    }
+
+    fn make_covenant(issuer_kp: &crypto::KeyPair, constraints: &str) -> CovenantDocument {
+        let issuer = Party {
+            id: "issuer-1".to_string(),
+            public_key: issuer_kp.public_key_hex.clone(),
+            role: "issuer".to_string(),
+        };
+        let bene_kp = crypto::generate_key_pair().unwrap();
+        let beneficiary = Party {
+            id: "beneficiary-1".to_string(),
+            public_key: bene_kp.public_key_hex,
+            role: "beneficiary".to_string(),
+        };
+        covenant::build_covenant(CovenantBuilderOptions {
+            issuer,
+            beneficiary,
+            constraints: constraints.to_string(),
+            signing_key: issuer_kp.signing_key.clone(),
+            chain: None,
+            expires_at: None,
+            activates_at: None,
+            metadata: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip_without_covenant() {
+        let (identity, _) = make_identity(vec!["read".to_string()]);
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+
+        let der_bytes = to_x509(&identity, None, &issuer_kp.signing_key).unwrap();
+        let attestation = from_x509(&der_bytes, &issuer_kp.verifying_key).unwrap();
+
+        assert_eq!(attestation.model_provider, "anthropic");
+        assert_eq!(attestation.model_id, "claude");
+        assert_eq!(attestation.capabilities, vec!["read".to_string()]);
+        assert_eq!(attestation.constraints, "");
+        assert_eq!(attestation.chain_depth, 0);
+    }
+
+    #[test]
+    fn test_roundtrip_with_covenant() {
+        let (identity, _) = make_identity(vec!["read".to_string(), "write".to_string()]);
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let covenant = make_covenant(&issuer_kp, "permit read on '/data/**'");
+
+        let der_bytes = to_x509(&identity, Some(&covenant), &issuer_kp.signing_key).unwrap();
+        let attestation = from_x509(&der_bytes, &issuer_kp.verifying_key).unwrap();
+
+        assert_eq!(attestation.constraints, "permit read on '/data/**'");
+        assert_eq!(attestation.capabilities.len(), 2);
+    }
+
+    #[test]
+    fn test_admin_capability_is_ca() {
+        let (identity, _) = make_identity(vec!["admin".to_string()]);
+        assert!(is_ca(&identity.capabilities));
+
+        let (non_admin, _) = make_identity(vec!["read".to_string()]);
+        assert!(!is_ca(&non_admin.capabilities));
+    }
+
+    #[test]
+    fn test_wrong_issuer_key_fails_verification() {
+        let (identity, _) = make_identity(vec!["read".to_string()]);
+        let issuer_kp = crypto::generate_key_pair().unwrap();
+        let wrong_kp = crypto::generate_key_pair().unwrap();
+
+        let der_bytes = to_x509(&identity, None, &issuer_kp.signing_key).unwrap();
+        let result = from_x509(&der_bytes, &wrong_kp.verifying_key);
+        assert!(result.is_err());
+    }
+}
@@ -223,7 +223,11 @@ fn test_ccl_parse_multiple_statements() {
 fn test_ccl_parse_with_condition() {
     let doc = ccl::parse("permit read on '/data/**' when user.role = 'admin'").unwrap();
     assert_eq!(doc.permits.len(), 1);
-    let cond = doc.permits[0].condition.as_ref().unwrap();
+    let expr = doc.permits[0].condition.as_ref().unwrap();
+    let cond = match expr {
+        ccl::ConditionExpr::Compare(cond) => cond,
+        other => panic!("expected a single comparison, got {:?}", other),
+    };
     assert_eq!(cond.field, "user.role");
     assert_eq!(cond.operator, "=");
     assert_eq!(cond.value, "admin");
@@ -821,8 +825,8 @@ fn test_covenant_all_11_checks_present() {
 fn test_identity_create() {
     let kp = crypto::generate_key_pair().unwrap();
     let ident = identity::create_identity(CreateIdentityOptions {
-        signing_key: kp.signing_key,
-        public_key_hex: kp.public_key_hex.clone(),
+        signing_keys: vec![kp.signing_key],
+        threshold: 1,
         model: ModelInfo {
             provider: "anthropic".to_string(),
             model_id: "claude-3".to_string(),
@@ -839,16 +843,17 @@ fn test_identity_create() {
     assert_eq!(ident.lineage.len(), 1);
     assert_eq!(ident.lineage[0].change_type, "created");
     assert!(ident.lineage[0].parent_hash.is_none());
-    assert_eq!(ident.operator_public_key, kp.public_key_hex);
-    assert!(!ident.signature.is_empty());
+    assert_eq!(ident.operator_keys.keys, vec![kp.public_key_hex]);
+    assert_eq!(ident.signatures.len(), 1);
+    assert!(!ident.signatures[0].signature.is_empty());
 }
 
 #[test]
 fn test_identity_capabilities_sorted() {
     let kp = crypto::generate_key_pair().unwrap();
     let ident = identity::create_identity(CreateIdentityOptions {
-        signing_key: kp.signing_key,
-        public_key_hex: kp.public_key_hex,
+        signing_keys: vec![kp.signing_key],
+        threshold: 1,
         model: ModelInfo {
             provider: "anthropic".to_string(),
             model_id: "claude-3".to_string(),
@@ -870,8 +875,8 @@ fn test_identity_capabilities_sorted() {
 fn test_identity_evolve() {
     let kp = crypto::generate_key_pair().unwrap();
     let original = identity::create_identity(CreateIdentityOptions {
-        signing_key: kp.signing_key.clone(),
-        public_key_hex: kp.public_key_hex.clone(),
+        signing_keys: vec![kp.signing_key.clone()],
+        threshold: 1,
         model: ModelInfo {
             provider: "anthropic".to_string(),
             model_id: "claude-3".to_string(),
@@ -886,12 +891,13 @@ fn test_identity_evolve() {
     let evolved = identity::evolve_identity(
         &original,
         EvolveIdentityOptions {
-            signing_key: kp.signing_key,
+            signing_keys: vec![kp.signing_key],
             change_type: "capability_change".to_string(),
             description: "Added write capability".to_string(),
             model: None,
             capabilities: Some(vec!["read".to_string(), "write".to_string()]),
             deployment: None,
+            new_operator_keys: None,
         },
     )
     .unwrap();
@@ -914,8 +920,8 @@ fn test_identity_evolve() {
 fn test_identity_evolve_model_update() {
     let kp = crypto::generate_key_pair().unwrap();
     let original = identity::create_identity(CreateIdentityOptions {
-        signing_key: kp.signing_key.clone(),
-        public_key_hex: kp.public_key_hex.clone(),
+        signing_keys: vec![kp.signing_key.clone()],
+        threshold: 1,
         model: ModelInfo {
             provider: "anthropic".to_string(),
             model_id: "claude-3".to_string(),
@@ -930,7 +936,7 @@ fn test_identity_evolve_model_update() {
     let evolved = identity::evolve_identity(
         &original,
         EvolveIdentityOptions {
-            signing_key: kp.signing_key,
+            signing_keys: vec![kp.signing_key],
             change_type: "model_update".to_string(),
             description: "Upgraded to claude-4".to_string(),
             model: Some(ModelInfo {
@@ -939,6 +945,7 @@ fn test_identity_evolve_model_update() {
             }),
             capabilities: None,
             deployment: None,
+            new_operator_keys: None,
         },
     )
     .unwrap();
@@ -951,8 +958,8 @@ fn test_identity_evolve_model_update() {
 fn test_identity_verify() {
     let kp = crypto::generate_key_pair().unwrap();
     let ident = identity::create_identity(CreateIdentityOptions {
-        signing_key: kp.signing_key,
-        public_key_hex: kp.public_key_hex,
+        signing_keys: vec![kp.signing_key],
+        threshold: 1,
         model: ModelInfo {
             provider: "anthropic".to_string(),
             model_id: "claude-3".to_string(),
@@ -977,8 +984,8 @@ fn test_identity_verify() {
 fn test_identity_serialize_deserialize() {
     let kp = crypto::generate_key_pair().unwrap();
     let original = identity::create_identity(CreateIdentityOptions {
-        signing_key: kp.signing_key,
-        public_key_hex: kp.public_key_hex,
+        signing_keys: vec![kp.signing_key],
+        threshold: 1,
         model: ModelInfo {
             provider: "anthropic".to_string(),
             model_id: "claude-3".to_string(),
@@ -997,17 +1004,17 @@ fn test_identity_serialize_deserialize() {
 
     let restored = identity::deserialize_identity(&json).unwrap();
     assert_eq!(original.id, restored.id);
-    assert_eq!(original.signature, restored.signature);
+    assert_eq!(original.signatures.len(), restored.signatures.len());
+    assert_eq!(original.signatures[0].signature, restored.signatures[0].signature);
     assert_eq!(original.version, restored.version);
     assert_eq!(original.capabilities, restored.capabilities);
 }
 
 #[test]
-fn test_identity_create_requires_public_key() {
-    let kp = crypto::generate_key_pair().unwrap();
+fn test_identity_create_requires_at_least_one_signing_key() {
     let result = identity::create_identity(CreateIdentityOptions {
-        signing_key: kp.signing_key,
-        public_key_hex: String::new(),
+        signing_keys: vec![],
+        threshold: 1,
         model: ModelInfo {
             provider: "anthropic".to_string(),
             model_id: "claude-3".to_string(),
@@ -1024,8 +1031,8 @@ fn test_identity_create_requires_public_key() {
 fn test_identity_create_requires_capabilities() {
     let kp = crypto::generate_key_pair().unwrap();
     let result = identity::create_identity(CreateIdentityOptions {
-        signing_key: kp.signing_key,
-        public_key_hex: kp.public_key_hex,
+        signing_keys: vec![kp.signing_key],
+        threshold: 1,
         model: ModelInfo {
             provider: "anthropic".to_string(),
             model_id: "claude-3".to_string(),
@@ -1042,8 +1049,8 @@ fn test_identity_create_requires_capabilities() {
 fn test_identity_evolve_preserves_created_at() {
     let kp = crypto::generate_key_pair().unwrap();
     let original = identity::create_identity(CreateIdentityOptions {
-        signing_key: kp.signing_key.clone(),
-        public_key_hex: kp.public_key_hex.clone(),
+        signing_keys: vec![kp.signing_key.clone()],
+        threshold: 1,
         model: ModelInfo {
             provider: "anthropic".to_string(),
             model_id: "claude-3".to_string(),
@@ -1058,12 +1065,13 @@ fn test_identity_evolve_preserves_created_at() {
     let evolved = identity::evolve_identity(
         &original,
         EvolveIdentityOptions {
-            signing_key: kp.signing_key,
+            signing_keys: vec![kp.signing_key],
             change_type: "capability_change".to_string(),
             description: "Test".to_string(),
             model: None,
             capabilities: Some(vec!["read".to_string(), "write".to_string()]),
             deployment: None,
+            new_operator_keys: None,
         },
     )
     .unwrap();
@@ -1192,8 +1200,8 @@ fn test_full_protocol_flow() {
 
     // 2. Create agent identity
     let agent_identity = identity::create_identity(CreateIdentityOptions {
-        signing_key: operator_kp.signing_key.clone(),
-        public_key_hex: operator_kp.public_key_hex.clone(),
+        signing_keys: vec![operator_kp.signing_key.clone()],
+        threshold: 1,
         model: ModelInfo {
             provider: "anthropic".to_string(),
             model_id: "claude-3".to_string(),
@@ -1324,7 +1332,7 @@ fn test_full_protocol_flow() {
     let evolved_identity = identity::evolve_identity(
         &agent_identity,
         EvolveIdentityOptions {
-            signing_key: operator_kp.signing_key,
+            signing_keys: vec![operator_kp.signing_key],
             change_type: "capability_change".to_string(),
             description: "Added admin capability".to_string(),
             model: None,
@@ -1335,6 +1343,7 @@ fn test_full_protocol_flow() {
                 "admin".to_string(),
             ]),
             deployment: None,
+            new_operator_keys: None,
         },
     )
     .unwrap();